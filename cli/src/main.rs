@@ -31,6 +31,21 @@ enum Commands {
         /// Caminho para o arquivo com comandos LogLine
         #[arg(short, long)]
         file: PathBuf,
+
+        /// Mostra os eventos que o script registraria, sem persistir nada
+        #[arg(long)]
+        plan: bool,
+    },
+
+    /// Formata scripts LogLine na forma canônica
+    Fmt {
+        /// Arquivos com comandos LogLine a formatar
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Apenas verifica se os arquivos já estão formatados, sem alterá-los
+        #[arg(long)]
+        check: bool,
     },
 
     /// Gerencia o serviço runtime do LogLine
@@ -92,9 +107,77 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Run { file } => {
+        Commands::Run { file, plan } => {
+            let source = std::fs::read_to_string(file)
+                .with_context(|| format!("Falha ao ler o arquivo {}", file.display()))?;
+            if let Err(e) = parser::parse_program(&source) {
+                eprint!("{}", e.syntax().render(&file.display().to_string()));
+                std::process::exit(1);
+            }
+
+            let instance = runtime::Runtime::from_env()?;
+            if *plan {
+                println!("Planejando comandos do arquivo: {}", file.display());
+                let plan = instance
+                    .scope(async {
+                        runtime::load().await?;
+                        runtime::plan_command(&source).await
+                    })
+                    .await?;
+                println!("{} evento(s) seriam registrados:", plan.events.len());
+                for event in &plan.events {
+                    println!("  {}", serde_json::to_string(&event.kind)?);
+                }
+                if let Some(output) = &plan.output {
+                    println!("{}", output);
+                }
+                if let Some(error) = &plan.error {
+                    eprintln!("O script falharia: {}", error);
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+
             println!("Executando comandos do arquivo: {}", file.display());
-            // TODO: Implementar parser de arquivo LogLine e executor
+            let result = instance
+                .scope(async {
+                    runtime::init().await?;
+                    let result = runtime::process_command(&source).await;
+                    runtime::shutdown().await?;
+                    result
+                })
+                .await?;
+            println!("{}", result);
+            Ok(())
+        }
+
+        Commands::Fmt { files, check } => {
+            let mut unformatted = 0;
+            for file in files {
+                let source = std::fs::read_to_string(file)
+                    .with_context(|| format!("Falha ao ler o arquivo {}", file.display()))?;
+                let formatted = match parser::format_source(&source) {
+                    Ok(formatted) => formatted,
+                    Err(e) => {
+                        eprint!("{}", e.syntax().render(&file.display().to_string()));
+                        std::process::exit(1);
+                    }
+                };
+                if formatted == source {
+                    continue;
+                }
+                if *check {
+                    println!("Arquivo não formatado: {}", file.display());
+                    unformatted += 1;
+                } else {
+                    std::fs::write(file, formatted)
+                        .with_context(|| format!("Falha ao escrever o arquivo {}", file.display()))?;
+                    println!("Formatado: {}", file.display());
+                }
+            }
+            if unformatted > 0 {
+                std::process::exit(1);
+            }
             Ok(())
         }
 
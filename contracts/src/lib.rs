@@ -26,6 +26,8 @@ pub struct Contract {
 
 // Um simples store em memória para os contratos
 // Em uma implementação real, isso seria persistido em um banco de dados
+// O runtime não usa este store: os contratos dos comandos DEFINE e AMEND são
+// eventos da timeline, lidos do estado dela (ver `runtime::view`)
 static CONTRACT_STORE: Lazy<Mutex<HashMap<String, Contract>>> = Lazy::new(|| {
     Mutex::new(HashMap::new())
 });
@@ -10,24 +10,130 @@
     Contact: dan@danvoulez.com
 */
 
+use std::fmt;
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 /// Representa uma entidade rastreada pelo sistema LogLine
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// A versão começa em 1 e aumenta a cada alteração, o que permite
+/// alterações com controle de concorrência otimista (ver
+/// `registry::Registry::update_entity`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Entity {
     /// Identificador único da entidade
     pub id: String,
-    /// Tipo de entidade (CONTRATO, IDEIA, etc)
-    pub entity_type: String,
+    /// Tipo de entidade
+    pub entity_type: EntityType,
+    /// Dono ou tenant da entidade
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Metadados livres da entidade
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+    /// Versão da entidade
+    pub version: u64,
     /// Quando a entidade foi criada
     pub created_at: DateTime<Utc>,
-    /// Metadados opcionais da entidade
-    pub metadata: Option<EntityMetadata>,
+    /// Quando a entidade foi alterada pela última vez
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Entity {
+    /// Cria uma entidade na versão 1, sem dono nem metadados
+    pub fn new(id: impl Into<String>, entity_type: EntityType) -> Self {
+        let now = Utc::now();
+        Self {
+            id: id.into(),
+            entity_type,
+            owner: None,
+            metadata: serde_json::Value::Null,
+            version: 1,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Define o dono ou tenant da entidade
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Define os metadados da entidade
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+/// Tipo de uma entidade, escrito em maiúsculas (`CONTRACT`, `IDEA`...)
+///
+/// Tipos sem variante própria são mantidos como escritos em `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum EntityType {
+    /// Contrato (`CONTRACT`)
+    Contract,
+    /// Ideia (`IDEA`)
+    Idea,
+    /// Agente de um lllpack (`AGENT`)
+    Agent,
+    /// Entidade genérica, como as simuladas sem registro prévio (`ENTITY`)
+    Generic,
+    /// Qualquer outro tipo
+    Other(String),
+}
+
+impl EntityType {
+    /// Nome do tipo
+    pub fn as_str(&self) -> &str {
+        match self {
+            EntityType::Contract => "CONTRACT",
+            EntityType::Idea => "IDEA",
+            EntityType::Agent => "AGENT",
+            EntityType::Generic => "ENTITY",
+            EntityType::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for EntityType {
+    fn from(name: &str) -> Self {
+        match name {
+            "CONTRACT" => EntityType::Contract,
+            "IDEA" => EntityType::Idea,
+            "AGENT" => EntityType::Agent,
+            "ENTITY" => EntityType::Generic,
+            other => EntityType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for EntityType {
+    fn from(name: String) -> Self {
+        EntityType::from(name.as_str())
+    }
+}
+
+impl From<EntityType> for String {
+    fn from(entity_type: EntityType) -> Self {
+        entity_type.as_str().to_string()
+    }
+}
+
+impl fmt::Display for EntityType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Metadados opcionais que podem ser associados a uma entidade
+///
+/// Substituído pelos campos `owner`, `metadata` e `version` de [`Entity`];
+/// mantido apenas por compatibilidade.
+#[deprecated(note = "use os campos `owner`, `metadata` e `version` de `Entity`")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityMetadata {
     /// Quem criou a entidade
@@ -13,11 +13,9 @@
 use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 use serde_json::json;
-use uuid::Uuid;
 use crate::models::{Span, SpanFilter};
 use crate::error::DataTrackError;
 use db::Db;
-use chrono::Utc;
 
 static DB: Lazy<Mutex<Db>> = Lazy::new(|| {
     let rt = tokio::runtime::Handle::current();
@@ -43,7 +41,8 @@ pub async fn fetch_spans(filter: SpanFilter) -> Result<Vec<Span>, DataTrackError
     if let Some(kind) = filter.kind {
         conds.push(format!("kind=eq.{}", kind));
     }
-    let predicate = if conds.is_empty() { None } else { Some(conds.join("&").as_str()) };
+    let predicate = conds.join("&");
+    let predicate = if conds.is_empty() { None } else { Some(predicate.as_str()) };
 
     let db = DB.lock().await;
     // Note: The generic select in the db crate might not handle limits directly.
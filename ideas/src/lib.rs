@@ -27,6 +27,8 @@ pub struct Idea {
 
 // Um simples store em memória para as ideias
 // Em uma implementação real, isso seria persistido em um banco de dados
+// O runtime não usa este store: as ideias dos comandos DEFINE, UPDATE e TAG
+// são eventos da timeline, lidos do estado dela (ver `runtime::view`)
 static IDEA_STORE: Lazy<Mutex<HashMap<String, Idea>>> = Lazy::new(|| {
     Mutex::new(HashMap::new())
 });
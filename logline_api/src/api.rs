@@ -11,11 +11,11 @@
 */
 
 use crate::config::ApiConfig;
-use crate::error::{ApiError, ErrorResponse};
+use crate::error::{ApiError, ErrorResponse, SyntaxDiagnostic};
 use crate::health;
 use streaming::{EventBroker, Event};
 use plugin_manager::PluginManager;
-use runtime::{process_command, timeline};
+use runtime::{process_command, timeline, Runtime};
 use parser::ast::Command;
 use warp::{Filter, Rejection, Reply};
 use std::sync::Arc;
@@ -39,6 +39,7 @@ struct DslResponse {
 
 // Configura todas as rotas da API
 pub async fn setup_routes(
+    runtime: Runtime,
     broker: Arc<EventBroker>,
     plugin_manager: Arc<PluginManager>,
     config: &ApiConfig,
@@ -51,6 +52,7 @@ pub async fn setup_routes(
         .and(warp::path("dsl"))
         .and(warp::post())
         .and(warp::body::json())
+        .and(with_runtime(runtime))
         .and(with_broker(broker.clone()))
         .and(with_plugin_manager(plugin_manager.clone()))
         .and_then(handle_dsl);
@@ -106,6 +108,7 @@ pub async fn setup_routes(
 // Handler para a rota /dsl
 async fn handle_dsl(
     request: DslRequest,
+    runtime: Runtime,
     broker: Arc<EventBroker>,
     plugin_manager: Arc<PluginManager>,
 ) -> Result<impl Reply, Rejection> {
@@ -113,16 +116,19 @@ async fn handle_dsl(
     // debug!("Recebido comando DSL: {}", request.command);
     
     // Processa o comando DSL
-    let result = match process_command(&request.command).await {
+    let result = match runtime.scope(process_command(&request.command)).await {
         Ok(result) => result,
         Err(e) => {
             error!("Erro ao processar comando DSL: {}", e);
+            if let Some(parse_error) = e.downcast_ref::<parser::Error>() {
+                return Err(warp::reject::custom(ApiError::SyntaxError(parse_error.syntax().clone())));
+            }
             return Err(warp::reject::custom(ApiError::CommandProcessingError(e.to_string())));
         }
     };
     
     // Recupera os eventos gerados pelo comando
-    let events = timeline::list_events().await
+    let events = runtime.scope(timeline::list_events()).await
         .map_err(|e| warp::reject::custom(ApiError::InternalError(e.to_string())))?;
     
     // Converte os eventos para JSON
@@ -212,6 +218,7 @@ async fn handle_stream(broker: Arc<EventBroker>) -> Result<impl Reply, Rejection
 async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
     let code;
     let message;
+    let mut diagnostic = None;
     
     if err.is_not_found() {
         code = warp::http::StatusCode::NOT_FOUND;
@@ -226,6 +233,11 @@ async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
                 code = warp::http::StatusCode::BAD_REQUEST;
                 message = format!("Formato de comando inválido: {}", e);
             }
+            ApiError::SyntaxError(e) => {
+                code = warp::http::StatusCode::BAD_REQUEST;
+                message = api_error.to_string();
+                diagnostic = Some(SyntaxDiagnostic::from(e));
+            }
             ApiError::InternalError(e) => {
                 code = warp::http::StatusCode::INTERNAL_SERVER_ERROR;
                 message = format!("Erro interno do servidor: {}", e);
@@ -244,12 +256,19 @@ async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
         status: code.as_u16(),
         message,
         timestamp: chrono::Utc::now().to_rfc3339(),
+        diagnostic,
     });
     
     Ok(warp::reply::with_status(json, code))
 }
 
 // Utilidades para injeção de dependências nos handlers
+fn with_runtime(
+    runtime: Runtime
+) -> impl Filter<Extract = (Runtime,), Error = Infallible> + Clone {
+    warp::any().map(move || runtime.clone())
+}
+
 fn with_broker(
     broker: Arc<EventBroker>
 ) -> impl Filter<Extract = (Arc<EventBroker>,), Error = Infallible> + Clone {
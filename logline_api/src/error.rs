@@ -23,6 +23,9 @@ pub enum ApiError {
     #[error("Formato de comando inválido: {0}")]
     InvalidCommandFormat(String),
     
+    #[error("Erro de sintaxe na linha {}, coluna {}: {}", .0.line, .0.column, .0.message)]
+    SyntaxError(parser::SyntaxError),
+    
     #[error("Erro interno: {0}")]
     InternalError(String),
 }
@@ -36,4 +39,30 @@ pub struct ErrorResponse {
     pub status: u16,
     pub message: String,
     pub timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostic: Option<SyntaxDiagnostic>,
+}
+
+// Localização de um erro de sintaxe no comando DSL enviado
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyntaxDiagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+    pub expected: Vec<String>,
+    pub found: String,
+    pub rendered: String,
+}
+
+impl From<&parser::SyntaxError> for SyntaxDiagnostic {
+    fn from(e: &parser::SyntaxError) -> Self {
+        Self {
+            line: e.line,
+            column: e.column,
+            offset: e.offset,
+            expected: e.expected.clone(),
+            found: e.found.clone(),
+            rendered: e.render("dsl"),
+        }
+    }
 }
\ No newline at end of file
@@ -36,7 +36,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Inicializa o runtime do LogLine
     info!("Inicializando runtime...");
-    runtime::init().await?;
+    let instance = runtime::Runtime::from_env()?;
+    instance.scope(runtime::init()).await?;
     
     // Inicializa o broker de eventos
     info!("Inicializando broker de eventos...");
@@ -64,7 +65,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Configura as rotas da API
     info!("Configurando rotas da API...");
-    let routes = setup_routes(broker, Arc::new(plugin_manager), &config).await?;
+    let routes = setup_routes(instance.clone(), broker, Arc::new(plugin_manager), &config).await?;
     
     // Inicia o servidor HTTP
     let addr = config.bind_address.parse()?;
@@ -72,7 +73,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     warp::serve(routes).run(addr).await;
     
     // Nunca deve chegar aqui, mas por precaução
-    runtime::shutdown().await?;
+    instance.scope(runtime::shutdown()).await?;
     
     Ok(())
 }
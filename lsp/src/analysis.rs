@@ -0,0 +1,321 @@
+// === File: lsp/src/analysis.rs ===
+/*
+    Description: Análise de documentos LogLine para o servidor de linguagem: diagnósticos, completação, hover e definição.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionTextEdit, Diagnostic, DiagnosticSeverity, Documentation,
+    Position, Range, TextEdit,
+};
+use parser::ast::{Command, ImperativeKind};
+use parser::error::SyntaxError;
+
+/// Linguagem de um documento aberto no editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentKind {
+    /// Script de comandos LogLine.
+    Script,
+    /// Contrato `.lll`.
+    Contract,
+}
+
+impl DocumentKind {
+    /// Deduz a linguagem pela extensão do caminho ou URI.
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".lll") {
+            DocumentKind::Contract
+        } else {
+            DocumentKind::Script
+        }
+    }
+}
+
+/// Verbo da linguagem oferecido na completação.
+pub struct Verb {
+    /// Palavras-chave do verbo, como escritas no script.
+    pub keyword: &'static str,
+    /// Forma de uso exibida ao usuário.
+    pub usage: &'static str,
+    /// Descrição curta.
+    pub doc: &'static str,
+}
+
+/// Verbos dos comandos imperativos e de controle, na ordem da completação.
+pub const VERBS: &[Verb] = &[
+    Verb { keyword: "DEFINE CONTRACT", usage: "DEFINE CONTRACT id \"cláusula\", ...", doc: "Define um contrato com suas cláusulas." },
+    Verb { keyword: "DEFINE IDEA", usage: "DEFINE IDEA id \"texto\"", doc: "Define uma ideia." },
+    Verb { keyword: "SIMULATE ENTITY", usage: "SIMULATE ENTITY id rodadas", doc: "Executa uma simulação sobre uma entidade." },
+    Verb { keyword: "ORCHESTRATE", usage: "ORCHESTRATE modo [concorrência] { [STEP nome [DEPENDS ON a, b]:] comando }", doc: "Executa um bloco de passos em sequência (sequential), em paralelo (parallel) ou pelo grafo de dependências (dag)." },
+    Verb { keyword: "INVOKE RULESET", usage: "INVOKE RULESET regras ON entidade", doc: "Invoca um conjunto de regras sobre uma entidade." },
+    Verb { keyword: "AMEND CONTRACT", usage: "AMEND CONTRACT id \"cláusula\", ...", doc: "Substitui as cláusulas de um contrato existente." },
+    Verb { keyword: "TAG IDEA", usage: "TAG IDEA id tag, ...", doc: "Adiciona tags a uma ideia." },
+    Verb { keyword: "UPDATE IDEA", usage: "UPDATE IDEA id \"texto\"", doc: "Substitui o texto de uma ideia." },
+    Verb { keyword: "RETIRE ENTITY", usage: "RETIRE ENTITY id", doc: "Remove uma entidade do registro." },
+    Verb { keyword: "REVERT", usage: "REVERT id-do-evento", doc: "Reverte um evento da timeline com eventos de compensação." },
+    Verb { keyword: "SHOW CONTRACT", usage: "SHOW CONTRACT id", doc: "Consulta um contrato." },
+    Verb { keyword: "SHOW SIMULATION", usage: "SHOW SIMULATION id LAST n", doc: "Consulta os últimos resultados de simulação de uma entidade." },
+    Verb { keyword: "LIST IDEAS", usage: "LIST IDEAS WHERE TAG = tag", doc: "Lista as ideias, opcionalmente filtradas por tag." },
+    Verb { keyword: "FIND SPANS", usage: "FIND SPANS KIND = tipo LIMIT n", doc: "Busca spans registrados." },
+    Verb { keyword: "LET", usage: "LET nome = \"valor\"", doc: "Associa um valor a uma variável do script." },
+    Verb { keyword: "IF", usage: "IF condição THEN { ... } ELSE { ... }", doc: "Execução condicional." },
+    Verb { keyword: "FOR EACH", usage: "FOR EACH id IN LIST CONTRACTS DO { ... }", doc: "Repetição sobre uma lista." },
+    Verb { keyword: "WITH", usage: "WITH TIMEOUT 30s RETRY 3 BACKOFF 200ms DO { ... } ON FAILURE { ... }", doc: "Execução com tempo limite, novas tentativas e tratamento de falha." },
+    Verb { keyword: "BEGIN", usage: "BEGIN ... COMMIT (um comando por linha)", doc: "Transação: os comandos têm efeito todos juntos ou nenhum." },
+];
+
+/// Converte os erros de sintaxe do documento em diagnósticos LSP.
+///
+/// Scripts são lidos com recuperação de erros, o que produz um diagnóstico
+/// por instrução inválida; contratos `.lll` param no primeiro erro.
+pub fn diagnostics(kind: DocumentKind, text: &str) -> Vec<Diagnostic> {
+    let errors = match kind {
+        DocumentKind::Script => parser::parse_with_recovery(text).diagnostics,
+        DocumentKind::Contract => match parser::lll::parse(text) {
+            Ok(_) => Vec::new(),
+            Err(e) => vec![e.syntax().clone()],
+        },
+    };
+    errors.iter().map(|error| diagnostic(text, error)).collect()
+}
+
+/// Monta o diagnóstico de um erro, destacando o trecho encontrado.
+fn diagnostic(text: &str, error: &SyntaxError) -> Diagnostic {
+    let line = error.line.saturating_sub(1);
+    let line_text = text.lines().nth(line).unwrap_or("");
+    let start = utf16_column(line_text, error.column.saturating_sub(1));
+    let end = start + error.found.encode_utf16().count() as u32;
+
+    Diagnostic {
+        range: Range::new(Position::new(line as u32, start), Position::new(line as u32, end)),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("logline".to_string()),
+        message: error.message.clone(),
+        ..Default::default()
+    }
+}
+
+/// Sugere os verbos que começam com o texto digitado na linha até o cursor.
+///
+/// A sugestão substitui o texto digitado, de modo que `SHOW C` completa para
+/// `SHOW CONTRACT`.
+pub fn completions(kind: DocumentKind, text: &str, position: Position) -> Vec<CompletionItem> {
+    if kind != DocumentKind::Script {
+        return Vec::new();
+    }
+    let line_text = text.lines().nth(position.line as usize).unwrap_or("");
+    let before = &line_text[..byte_offset(line_text, position.character)];
+    let typed = before.trim_start();
+    if typed.contains(|c: char| !(c.is_ascii_alphabetic() || c == ' ')) {
+        return Vec::new();
+    }
+
+    let prefix = typed.to_uppercase();
+    let start = utf16_column(before, before.chars().count() - typed.chars().count());
+    let range = Range::new(Position::new(position.line, start), position);
+
+    VERBS
+        .iter()
+        .filter(|verb| verb.keyword.starts_with(&prefix))
+        .map(|verb| CompletionItem {
+            label: verb.keyword.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some(verb.usage.to_string()),
+            documentation: Some(Documentation::String(verb.doc.to_string())),
+            text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(range, verb.keyword.to_string()))),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Texto de hover para a posição: a descrição do verbo sob o cursor ou,
+/// sobre um ID, o tipo da entidade.
+///
+/// `lookup` consulta o tipo de uma entidade no registro. IDs definidos no
+/// próprio documento também são reconhecidos, mesmo fora do registro.
+pub fn hover(
+    kind: DocumentKind,
+    text: &str,
+    position: Position,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<(Range, String)> {
+    if kind != DocumentKind::Script {
+        return None;
+    }
+    let (range, word) = word_at(text, position)?;
+    let line_text = text.lines().nth(position.line as usize).unwrap_or("");
+
+    if let Some(verb) = verb_at(line_text, range.start.character) {
+        return Some((range, format!("**{}**\n\n{}\n\n`{}`", verb.keyword, verb.doc, verb.usage)));
+    }
+
+    let mut lines = Vec::new();
+    if let Some(entity_type) = lookup(&word) {
+        lines.push(format!("Entidade `{}` do tipo **{}**", word, entity_type));
+    }
+    if let Some(definition) = definitions(text).into_iter().find(|d| d.id == word) {
+        lines.push(format!("{} na linha {}", definition.label, definition.range.start.line + 1));
+    }
+    if lines.is_empty() {
+        None
+    } else {
+        Some((range, lines.join("\n\n")))
+    }
+}
+
+/// Posição da definição do ID sob o cursor no próprio documento.
+///
+/// Em scripts, leva ao `DEFINE CONTRACT` ou `DEFINE IDEA` do ID; em
+/// contratos `.lll`, à declaração `span` com o nome sob o cursor.
+pub fn definition(kind: DocumentKind, text: &str, position: Position) -> Option<Range> {
+    let (_, word) = word_at(text, position)?;
+    match kind {
+        DocumentKind::Script => definitions(text).into_iter().find(|d| d.id == word).map(|d| d.range),
+        DocumentKind::Contract => {
+            let contract = parser::lll::parse(text).ok()?;
+            let span = contract.spans().into_iter().find(|span| span.name == word)?;
+            let line = span.line - 1;
+            let line_text = text.lines().nth(line)?;
+            let start = find_word(line_text, &span.name, 0)?;
+            Some(word_range(line_text, line, start, &span.name))
+        }
+    }
+}
+
+/// ID definido por uma linha `DEFINE` do script.
+struct Definition {
+    id: String,
+    label: &'static str,
+    range: Range,
+}
+
+/// Procura as definições de contratos e ideias, uma por linha.
+fn definitions(text: &str) -> Vec<Definition> {
+    let mut found = Vec::new();
+    for (line, line_text) in text.lines().enumerate() {
+        let trimmed = line_text.trim();
+        if !trimmed.starts_with("DEFINE") {
+            continue;
+        }
+        let (id, label) = match parser::parse(trimmed) {
+            Ok(Command::Imperative(imperative)) => match imperative.kind {
+                ImperativeKind::DefineContract { id, .. } => (id, "Contrato definido"),
+                ImperativeKind::DefineIdea { id, .. } => (id, "Ideia definida"),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        // O ID vem logo depois de `DEFINE CONTRACT` ou `DEFINE IDEA`
+        let after_keyword = line_text.find("DEFINE").map(|i| i + "DEFINE".len()).unwrap_or(0);
+        if let Some(start) = find_word(line_text, &id, after_keyword) {
+            let range = word_range(line_text, line, start, &id);
+            found.push(Definition { id, label, range });
+        }
+    }
+    found
+}
+
+/// Verbo cujas palavras-chave ocupam a coluna (UTF-16) indicada da linha.
+fn verb_at(line_text: &str, column: u32) -> Option<&'static Verb> {
+    let column = byte_offset(line_text, column);
+    let mut words = Vec::new();
+    let mut rest = line_text;
+    while words.len() < 2 {
+        let start = line_text.len() - rest.trim_start().len();
+        let end = line_text[start..].find(char::is_whitespace).map(|i| start + i).unwrap_or(line_text.len());
+        if start == end {
+            break;
+        }
+        words.push((&line_text[start..end], end));
+        rest = &line_text[end..];
+    }
+
+    // Verbos de duas palavras têm preferência sobre os de uma
+    VERBS
+        .iter()
+        .filter_map(|verb| {
+            let keyword: Vec<&str> = verb.keyword.split(' ').collect();
+            let matches = words.len() >= keyword.len()
+                && words.iter().zip(&keyword).all(|((word, _), keyword)| word == keyword);
+            matches.then(|| (verb, words[keyword.len() - 1].1))
+        })
+        .max_by_key(|(verb, _)| verb.keyword.len())
+        .filter(|(_, end)| column < *end)
+        .map(|(verb, _)| verb)
+}
+
+/// Palavra (ID ou palavra-chave) sob o cursor.
+fn word_at(text: &str, position: Position) -> Option<(Range, String)> {
+    let line_text = text.lines().nth(position.line as usize)?;
+    let offset = byte_offset(line_text, position.character);
+
+    let start = line_text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word_char(*c))
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(offset);
+    let end = line_text[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_word_char(*c))
+        .map(|(i, _)| offset + i)
+        .unwrap_or(line_text.len());
+    if start == end {
+        return None;
+    }
+
+    let word = &line_text[start..end];
+    Some((word_range(line_text, position.line as usize, start, word), word.to_string()))
+}
+
+/// Caracteres que compõem um identificador LogLine.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '@')
+}
+
+/// Posição em bytes de `word` como palavra inteira, a partir de `from`.
+fn find_word(line_text: &str, word: &str, from: usize) -> Option<usize> {
+    let mut search = from;
+    while let Some(i) = line_text[search..].find(word) {
+        let start = search + i;
+        let end = start + word.len();
+        let before = !matches!(line_text[..start].chars().next_back(), Some(c) if is_word_char(c));
+        let after = !matches!(line_text[end..].chars().next(), Some(c) if is_word_char(c));
+        if before && after {
+            return Some(start);
+        }
+        search = end;
+    }
+    None
+}
+
+/// Intervalo LSP de `word`, que começa no byte `start` da linha.
+fn word_range(line_text: &str, line: usize, start: usize, word: &str) -> Range {
+    let start = line_text[..start].encode_utf16().count() as u32;
+    let end = start + word.encode_utf16().count() as u32;
+    Range::new(Position::new(line as u32, start), Position::new(line as u32, end))
+}
+
+/// Coluna UTF-16 do caractere de índice `chars` da linha.
+fn utf16_column(line_text: &str, chars: usize) -> u32 {
+    line_text.chars().take(chars).map(char::len_utf16).sum::<usize>() as u32
+}
+
+/// Deslocamento em bytes da coluna UTF-16 da linha, limitado ao seu fim.
+fn byte_offset(line_text: &str, column: u32) -> usize {
+    let mut units = 0;
+    for (i, c) in line_text.char_indices() {
+        if units >= column as usize {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    line_text.len()
+}
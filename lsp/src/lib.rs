@@ -0,0 +1,17 @@
+// === File: lsp/src/lib.rs ===
+/*
+    Description: Servidor de linguagem (LSP) para scripts LogLine e contratos .lll.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+//! A análise dos documentos fica em [`analysis`], sem dependência do
+//! protocolo; [`server`] a expõe por LSP.
+
+pub mod analysis;
+pub mod server;
@@ -0,0 +1,27 @@
+// === File: lsp/src/main.rs ===
+/*
+    Description: Binário logline-lsp: servidor de linguagem LogLine sobre stdio.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use lsp_server::Connection;
+use registry::Registry;
+
+fn main() -> anyhow::Result<()> {
+    // stdout é o canal do protocolo; mensagens de log vão para stderr
+    eprintln!("logline-lsp iniciado");
+
+    let (connection, io_threads) = Connection::stdio();
+    // O registro persistido só pode ser aberto por um processo: o do runtime
+    lsp::server::run(connection, Registry::in_memory())?;
+    io_threads.join()?;
+
+    eprintln!("logline-lsp encerrado");
+    Ok(())
+}
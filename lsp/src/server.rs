@@ -0,0 +1,197 @@
+// === File: lsp/src/server.rs ===
+/*
+    Description: Laço principal do servidor de linguagem LogLine: protocolo LSP, documentos abertos e consultas ao registro.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{Completion, GotoDefinition, HoverRequest};
+use lsp_types::request::Request as _;
+use lsp_types::{
+    CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use registry::Registry;
+use tokio::runtime::Runtime;
+
+use crate::analysis::{self, DocumentKind};
+
+/// Capacidades anunciadas ao editor na inicialização.
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions::default()),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+/// Executa o servidor sobre a conexão até o editor pedir o encerramento.
+///
+/// O hover de entidades consulta `registry`.
+pub fn run(connection: Connection, registry: Registry) -> Result<()> {
+    let capabilities = serde_json::to_value(capabilities())?;
+    connection.initialize(capabilities)?;
+
+    let mut server = Server {
+        connection: &connection,
+        documents: HashMap::new(),
+        registry,
+        runtime: Runtime::new()?,
+    };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                server.handle_request(request)?;
+            }
+            Message::Notification(notification) => server.handle_notification(notification)?,
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// Estado do servidor: os documentos abertos no editor.
+struct Server<'a> {
+    connection: &'a Connection,
+    /// Texto atual de cada documento aberto.
+    documents: HashMap<Url, String>,
+    /// Registro de entidades consultado no hover.
+    registry: Registry,
+    /// Runtime usado para consultar o registro, que é assíncrono.
+    runtime: Runtime,
+}
+
+impl Server<'_> {
+    fn handle_request(&mut self, request: Request) -> Result<()> {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            Completion::METHOD => serde_json::from_value(request.params).map(|params| self.completion(params)),
+            HoverRequest::METHOD => serde_json::from_value(request.params).map(|params| self.hover(params)),
+            GotoDefinition::METHOD => serde_json::from_value(request.params).map(|params| self.definition(params)),
+            method => {
+                let message = format!("Método não suportado: {}", method);
+                return self.send(Response::new_err(id, ErrorCode::MethodNotFound as i32, message));
+            }
+        };
+        match result {
+            Ok(result) => self.send(Response::new_ok(id, result)),
+            Err(e) => {
+                let message = format!("Parâmetros inválidos: {}", e);
+                self.send(Response::new_err(id, ErrorCode::InvalidParams as i32, message))
+            }
+        }
+    }
+
+    fn completion(&self, params: CompletionParams) -> serde_json::Value {
+        let position = params.text_document_position;
+        let items = self
+            .document(&position.text_document.uri)
+            .map(|(kind, text)| analysis::completions(kind, text, position.position))
+            .unwrap_or_default();
+        json(CompletionResponse::Array(items))
+    }
+
+    fn hover(&self, params: HoverParams) -> serde_json::Value {
+        let position = params.text_document_position_params;
+        let hover = self
+            .document(&position.text_document.uri)
+            .and_then(|(kind, text)| analysis::hover(kind, text, position.position, |id| self.entity_type(id)))
+            .map(|(range, value)| Hover {
+                contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value }),
+                range: Some(range),
+            });
+        json(hover)
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> serde_json::Value {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let location = self
+            .document(&uri)
+            .and_then(|(kind, text)| analysis::definition(kind, text, position.position))
+            .map(|range| GotoDefinitionResponse::Scalar(Location::new(uri.clone(), range)));
+        json(location)
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                self.publish_diagnostics(document.uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                // Sincronização completa: a última alteração traz o texto inteiro
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(params.text_document.uri.clone(), change.text);
+                }
+                self.publish_diagnostics(params.text_document.uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                // Limpa os diagnósticos do documento fechado
+                self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(params.text_document.uri, Vec::new(), None))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Linguagem e texto de um documento aberto.
+    fn document(&self, uri: &Url) -> Option<(DocumentKind, &str)> {
+        let text = self.documents.get(uri)?;
+        Some((DocumentKind::from_path(uri.path()), text.as_str()))
+    }
+
+    /// Tipo de uma entidade do registro, se existir.
+    fn entity_type(&self, id: &str) -> Option<String> {
+        self.runtime
+            .block_on(self.registry.get_entity(id))
+            .ok()
+            .map(|entity| entity.entity_type.to_string())
+    }
+
+    fn publish_diagnostics(&self, uri: Url) -> Result<()> {
+        let diagnostics = self
+            .document(&uri)
+            .map(|(kind, text)| analysis::diagnostics(kind, text))
+            .unwrap_or_default();
+        self.notify::<PublishDiagnostics>(PublishDiagnosticsParams::new(uri, diagnostics, None))
+    }
+
+    fn send(&self, response: Response) -> Result<()> {
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn notify<N: lsp_types::notification::Notification>(&self, params: N::Params) -> Result<()> {
+        self.connection.sender.send(Notification::new(N::METHOD.to_string(), params).into())?;
+        Ok(())
+    }
+}
+
+/// Serializa a resposta de uma requisição.
+fn json(result: impl serde::Serialize) -> serde_json::Value {
+    serde_json::to_value(result).unwrap_or(serde_json::Value::Null)
+}
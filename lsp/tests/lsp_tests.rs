@@ -0,0 +1,146 @@
+// === File: lsp/tests/lsp_tests.rs ===
+/*
+    Description: Testes do servidor de linguagem LogLine: análise dos documentos e sessão LSP completa.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use lsp::analysis::{self, DocumentKind};
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::{CompletionTextEdit, Position, Range};
+use registry::Registry;
+use serde_json::json;
+use std::thread;
+
+const SCRIPT: &str = "DEFINE CONTRACT contrato-1 \"Pagamento em 30 dias\"\n\
+                      DEFINE IDEA ideia-ção \"Texto\"\n\
+                      SIMULATE ENTITY contrato-1 3\n\
+                      TAG IDEA ideia-ção urgente\n";
+
+#[test]
+fn test_diagnostics_point_at_each_error() {
+    let text = "SIMULATE ENTITY e-1 três\nDEFINE CONTRACT c-1 \"a\"\nORCHESTRATE\n";
+    let diagnostics = analysis::diagnostics(DocumentKind::Script, text);
+
+    assert_eq!(diagnostics.len(), 2, "Deveria haver um diagnóstico por linha inválida");
+    assert_eq!(
+        diagnostics[0].range,
+        Range::new(Position::new(0, 20), Position::new(0, 24)),
+        "Intervalo deveria cobrir o trecho inválido"
+    );
+    assert_eq!(diagnostics[1].range.start.line, 2, "Segundo erro deveria estar na linha 3");
+
+    let contract = analysis::diagnostics(DocumentKind::Contract, "contract C {\n  span S {\n    depends_on: \"A\"\n  }\n}\n");
+    assert_eq!(contract.len(), 1, "Contrato .lll inválido deveria ter um diagnóstico");
+    assert_eq!(contract[0].range.start, Position::new(2, 4), "Posição do erro .lll incorreta");
+    assert!(analysis::diagnostics(DocumentKind::Script, SCRIPT).is_empty(), "Script válido não tem diagnósticos");
+}
+
+#[test]
+fn test_completion_replaces_typed_prefix() {
+    let items = analysis::completions(DocumentKind::Script, "  show c", Position::new(0, 8));
+    let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+    assert_eq!(labels, vec!["SHOW CONTRACT"], "Completação incorreta");
+
+    match &items[0].text_edit {
+        Some(CompletionTextEdit::Edit(edit)) => {
+            assert_eq!(edit.range, Range::new(Position::new(0, 2), Position::new(0, 8)), "Intervalo incorreto");
+        }
+        other => panic!("Edição inesperada: {:?}", other),
+    }
+
+    let all = analysis::completions(DocumentKind::Script, "", Position::new(0, 0));
+    assert_eq!(all.len(), analysis::VERBS.len(), "Linha vazia deveria sugerir todos os verbos");
+    assert!(
+        analysis::completions(DocumentKind::Script, "DEFINE IDEA i-1 ", Position::new(0, 16)).is_empty(),
+        "Não há verbos depois dos argumentos"
+    );
+}
+
+#[test]
+fn test_hover_shows_verbs_and_entity_types() {
+    let lookup = |id: &str| (id == "contrato-1").then(|| "contract".to_string());
+
+    let (_, verb) = analysis::hover(DocumentKind::Script, SCRIPT, Position::new(2, 10), lookup).expect("Hover do verbo");
+    assert!(verb.contains("SIMULATE ENTITY"), "Hover deveria descrever o verbo: {}", verb);
+
+    let (range, entity) = analysis::hover(DocumentKind::Script, SCRIPT, Position::new(2, 18), lookup).expect("Hover do ID");
+    assert_eq!(range, Range::new(Position::new(2, 16), Position::new(2, 26)), "Intervalo do ID incorreto");
+    assert!(entity.contains("**contract**"), "Hover deveria mostrar o tipo da entidade: {}", entity);
+    assert!(entity.contains("linha 1"), "Hover deveria mostrar a definição: {}", entity);
+
+    assert!(
+        analysis::hover(DocumentKind::Script, SCRIPT, Position::new(3, 30), lookup).is_none(),
+        "Tag desconhecida não tem hover"
+    );
+}
+
+#[test]
+fn test_definition_of_script_ids_and_lll_spans() {
+    // `ideia-ção` tem caracteres fora do ASCII: colunas em UTF-16
+    let range = analysis::definition(DocumentKind::Script, SCRIPT, Position::new(3, 12)).expect("Definição da ideia");
+    assert_eq!(range, Range::new(Position::new(1, 12), Position::new(1, 21)), "Definição incorreta");
+
+    assert!(
+        analysis::definition(DocumentKind::Script, SCRIPT, Position::new(3, 24)).is_none(),
+        "Tag não tem definição"
+    );
+
+    let contract = "contract C {\n  span A {\n    type: \"x\"\n  }\n  span B {\n    depends_on: [\"A\"]\n  }\n}\n";
+    let range = analysis::definition(DocumentKind::Contract, contract, Position::new(5, 18)).expect("Definição do span");
+    assert_eq!(range, Range::new(Position::new(1, 7), Position::new(1, 8)), "Span A deveria estar na linha 2");
+}
+
+#[test]
+fn test_server_session_over_memory_connection() {
+    let (server, client) = Connection::memory();
+    let handle = thread::spawn(move || lsp::server::run(server, Registry::in_memory()));
+
+    let send = |message: Message| client.sender.send(message).expect("Falha ao enviar");
+    let receive = || client.receiver.recv().expect("Servidor encerrou a conexão");
+
+    send(Request::new(RequestId::from(1), "initialize".to_string(), json!({ "capabilities": {} })).into());
+    match receive() {
+        Message::Response(response) => {
+            let capabilities = &response.result.expect("Resposta sem resultado")["capabilities"];
+            assert_eq!(capabilities["definitionProvider"], json!(true), "Capacidade de definição ausente");
+        }
+        other => panic!("Mensagem inesperada: {:?}", other),
+    }
+    send(Notification::new("initialized".to_string(), json!({})).into());
+
+    let uri = "file:///tmp/script.logline";
+    send(Notification::new(
+        "textDocument/didOpen".to_string(),
+        json!({ "textDocument": { "uri": uri, "languageId": "logline", "version": 1, "text": "ORCHESTRATE\n" } }),
+    )
+    .into());
+    match receive() {
+        Message::Notification(notification) => {
+            assert_eq!(notification.method, "textDocument/publishDiagnostics", "Notificação incorreta");
+            assert_eq!(notification.params["diagnostics"].as_array().map(Vec::len), Some(1), "Diagnóstico ausente");
+        }
+        other => panic!("Mensagem inesperada: {:?}", other),
+    }
+
+    let position = json!({ "textDocument": { "uri": uri }, "position": { "line": 0, "character": 3 } });
+    send(Request::new(RequestId::from(2), "textDocument/completion".to_string(), position).into());
+    match receive() {
+        Message::Response(response) => {
+            let items = response.result.expect("Resposta sem resultado");
+            assert_eq!(items[0]["label"], json!("ORCHESTRATE"), "Completação incorreta");
+        }
+        other => panic!("Mensagem inesperada: {:?}", other),
+    }
+
+    send(Request::new(RequestId::from(3), "shutdown".to_string(), json!(null)).into());
+    assert!(matches!(receive(), Message::Response(_)), "Shutdown deveria ser respondido");
+    send(Notification::new("exit".to_string(), json!(null)).into());
+
+    handle.join().expect("Thread do servidor falhou").expect("Servidor terminou com erro");
+}
@@ -12,44 +12,555 @@
 
 use std::fmt;
 
+use crate::error::SyntaxError;
+
 /// Um comando LogLine completo.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Command {
     /// Um comando imperativo que executa uma ação.
     Imperative(Imperative),
+
+    /// Associa um valor a uma variável do script (`LET nome = valor`).
+    ///
+    /// O valor pode conter interpolações `${nome}`, resolvidas pelo runtime
+    /// no momento da execução.
+    Let {
+        /// Nome da variável.
+        name: String,
+        /// Valor (modelo de texto) atribuído à variável.
+        value: String,
+    },
+
+    /// Execução condicional:
+    /// `IF condição THEN { ... } ELSE { ... }`.
+    If {
+        /// Condição avaliada pelo runtime.
+        condition: Condition,
+        /// Comandos executados quando a condição é verdadeira.
+        then_branch: Vec<Command>,
+        /// Comandos executados quando a condição é falsa.
+        else_branch: Option<Vec<Command>>,
+    },
+
+    /// Repetição sobre uma lista: `FOR EACH id IN LIST CONTRACTS DO { ... }`.
+    ForEach {
+        /// Variável que recebe cada item da lista.
+        variable: String,
+        /// Origem dos itens.
+        source: ListSource,
+        /// Comandos executados para cada item.
+        body: Vec<Command>,
+    },
+
+    /// Execução sob uma política de tempo limite, novas tentativas e
+    /// tratamento de falha:
+    /// `WITH TIMEOUT 30s RETRY 3 BACKOFF 200ms DO { ... } ON FAILURE { ... }`.
+    With {
+        /// Política aplicada ao bloco.
+        policy: Policy,
+        /// Comandos executados sob a política.
+        body: Vec<Command>,
+        /// Comandos executados quando todas as tentativas falham.
+        on_failure: Option<Vec<Command>>,
+    },
+
+    /// Transação: os comandos, um por linha entre `BEGIN` e `COMMIT`, têm
+    /// efeito todos juntos ou nenhum.
+    Transaction {
+        /// Comandos da transação.
+        body: Vec<Command>,
+    },
+}
+
+impl Command {
+    /// Escreve o comando na forma canônica, com blocos indentados a partir
+    /// de `indent` níveis.
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        match self {
+            Command::Imperative(imperative) => imperative.fmt_indented(f, indent),
+            Command::Let { name, value } => write!(f, "LET {} = {}", name, Quoted(value)),
+            Command::If { condition, then_branch, else_branch } => {
+                write!(f, "IF {} THEN ", condition)?;
+                write_block(f, then_branch, indent)?;
+                if let Some(else_branch) = else_branch {
+                    f.write_str(" ELSE ")?;
+                    write_block(f, else_branch, indent)?;
+                }
+                Ok(())
+            }
+            Command::ForEach { variable, source, body } => {
+                write!(f, "FOR EACH {} IN {} DO ", variable, source)?;
+                write_block(f, body, indent)
+            }
+            Command::With { policy, body, on_failure } => {
+                write!(f, "WITH{} DO ", policy)?;
+                write_block(f, body, indent)?;
+                if let Some(on_failure) = on_failure {
+                    f.write_str(" ON FAILURE ")?;
+                    write_block(f, on_failure, indent)?;
+                }
+                Ok(())
+            }
+            Command::Transaction { body } => {
+                f.write_str("BEGIN\n")?;
+                write_lines(f, body, indent + 1)?;
+                write!(f, "{}COMMIT", "    ".repeat(indent))
+            }
+        }
+    }
+}
+
+/// Escreve um bloco `{ ... }`, um comando por linha.
+fn write_block(f: &mut fmt::Formatter<'_>, commands: &[Command], indent: usize) -> fmt::Result {
+    if commands.is_empty() {
+        return f.write_str("{}");
+    }
+    f.write_str("{\n")?;
+    write_lines(f, commands, indent + 1)?;
+    write!(f, "{}}}", "    ".repeat(indent))
+}
+
+/// Escreve os comandos, um por linha, indentados em `indent` níveis.
+fn write_lines(f: &mut fmt::Formatter<'_>, commands: &[Command], indent: usize) -> fmt::Result {
+    for command in commands {
+        write!(f, "{}", "    ".repeat(indent))?;
+        command.fmt_indented(f, indent)?;
+        f.write_str("\n")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+/// Política de execução de um bloco `WITH`.
+///
+/// As opções são escritas nesta ordem, todas opcionais:
+/// `TIMEOUT duração`, `RETRY n` e `BACKOFF duração`. Durações aceitam as
+/// unidades `ms`, `s`, `m` e `h`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Policy {
+    /// Tempo limite de cada tentativa, em milissegundos.
+    pub timeout_ms: Option<u64>,
+    /// Novas tentativas depois da primeira falha.
+    pub retries: usize,
+    /// Espera antes da primeira nova tentativa, em milissegundos; dobra a
+    /// cada tentativa seguinte.
+    pub backoff_ms: Option<u64>,
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(timeout) = self.timeout_ms {
+            write!(f, " TIMEOUT {}", Millis(timeout))?;
+        }
+        if self.retries > 0 {
+            write!(f, " RETRY {}", self.retries)?;
+        }
+        if let Some(backoff) = self.backoff_ms {
+            write!(f, " BACKOFF {}", Millis(backoff))?;
+        }
+        Ok(())
+    }
+}
+
+/// Duração em milissegundos escrita na maior unidade que a representa
+/// exatamente.
+struct Millis(u64);
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = self.0;
+        match ms {
+            0 => f.write_str("0ms"),
+            _ if ms % 3_600_000 == 0 => write!(f, "{}h", ms / 3_600_000),
+            _ if ms % 60_000 == 0 => write!(f, "{}m", ms / 60_000),
+            _ if ms % 1_000 == 0 => write!(f, "{}s", ms / 1_000),
+            _ => write!(f, "{}ms", ms),
+        }
+    }
+}
+
+/// Condição de um comando `IF`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// Compara dois valores: `a == b` ou `a != b`.
+    Compare {
+        /// Valor à esquerda do operador.
+        left: Value,
+        /// Operador de comparação.
+        op: CompareOp,
+        /// Valor à direita do operador.
+        right: Value,
+    },
+    /// Verdadeiro se o item existe: `EXISTS CONTRACT id`.
+    Exists {
+        /// Tipo do item procurado.
+        kind: ItemKind,
+        /// Identificador do item.
+        id: String,
+    },
+    /// Negação de uma condição: `NOT condição`.
+    Not(Box<Condition>),
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::Compare { left, op, right } => write!(f, "{} {} {}", left, op, right),
+            Condition::Exists { kind, id } => write!(f, "EXISTS {} {}", kind, id),
+            Condition::Not(inner) => write!(f, "NOT {}", inner),
+        }
+    }
+}
+
+/// Operadores de comparação.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+}
+
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+        })
+    }
+}
+
+/// Valor usado em condições e listas.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Texto literal (identificador ou entre aspas), possivelmente com
+    /// interpolações `${nome}`.
+    Text(String),
+    /// Veredicto de um ruleset sobre uma entidade:
+    /// `VERDICT(ruleset ON entidade)`, avaliado como `Accepted` ou `Rejected`.
+    Verdict {
+        /// Identificador do conjunto de regras.
+        ruleset_id: String,
+        /// Identificador da entidade avaliada.
+        entity_id: String,
+    },
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Text(text) => write!(f, "{}", Word(text)),
+            Value::Verdict { ruleset_id, entity_id } => write!(f, "VERDICT({} ON {})", ruleset_id, entity_id),
+        }
+    }
+}
+
+/// Tipos de item consultáveis em condições `EXISTS`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ItemKind {
+    /// Contrato (`CONTRACT`).
+    Contract,
+    /// Ideia (`IDEA`).
+    Idea,
+    /// Entidade do registro (`ENTITY`).
+    Entity,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ItemKind::Contract => "CONTRACT",
+            ItemKind::Idea => "IDEA",
+            ItemKind::Entity => "ENTITY",
+        })
+    }
+}
+
+/// Origem dos itens de um `FOR EACH`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListSource {
+    /// Todos os contratos: `LIST CONTRACTS`.
+    Contracts,
+    /// Todas as ideias: `LIST IDEAS`.
+    Ideas,
+    /// Entidades do registro de um tipo: `LIST ENTITIES tipo`.
+    Entities {
+        /// Tipo das entidades.
+        entity_type: String,
+    },
+    /// Lista literal: `[a, b, "c d"]`.
+    Items(Vec<Value>),
+}
+
+impl fmt::Display for ListSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListSource::Contracts => f.write_str("LIST CONTRACTS"),
+            ListSource::Ideas => f.write_str("LIST IDEAS"),
+            ListSource::Entities { entity_type } => write!(f, "LIST ENTITIES {}", entity_type),
+            ListSource::Items(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+/// Texto escrito sem aspas quando é uma palavra simples, ou entre aspas
+/// caso contrário.
+pub(crate) struct Word<'a>(&'a str);
+
+impl fmt::Display for Word<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if is_bare_word(self.0) {
+            f.write_str(self.0)
+        } else {
+            write!(f, "{}", Quoted(self.0))
+        }
+    }
+}
+
+/// Indica se o texto pode ser escrito sem aspas como valor: um
+/// identificador válido que não seja palavra reservada.
+fn is_bare_word(text: &str) -> bool {
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("${") {
+            match after.find('}') {
+                Some(end) if crate::template::is_variable_name(&after[..end]) => rest = &after[end + 1..],
+                _ => return false,
+            }
+        } else {
+            let c = rest.chars().next().unwrap_or(' ');
+            if !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '@') {
+                return false;
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    !text.is_empty() && !matches!(text, "NOT" | "EXISTS" | "VERDICT")
+}
+
+/// Uma instrução de um programa LogLine, com sua posição no código-fonte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    /// Linha (base 1) onde a instrução começa.
+    pub line: usize,
+    /// Comando da instrução.
+    pub command: Command,
+}
+
+/// Nó de um programa analisado com recuperação de erros.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    /// Instrução válida.
+    Statement(Statement),
+    /// Trecho inválido, descartado até a próxima fronteira de instrução.
+    Error {
+        /// Linha (base 1) onde o trecho começa.
+        line: usize,
+        /// Texto descartado.
+        text: String,
+    },
+    /// Instrução incompleta: a linha ou a entrada terminou antes de um
+    /// elemento obrigatório.
+    Missing {
+        /// Linha (base 1) onde a instrução começa.
+        line: usize,
+        /// Texto da instrução incompleta.
+        text: String,
+        /// Elementos que faltaram.
+        expected: Vec<String>,
+    },
+}
+
+/// Resultado do parsing com recuperação de erros: a AST parcial e todos os
+/// diagnósticos, na ordem do código-fonte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered {
+    /// Nós do programa, incluindo os trechos inválidos.
+    pub nodes: Vec<Node>,
+    /// Um diagnóstico por nó `Error` ou `Missing`.
+    pub diagnostics: Vec<SyntaxError>,
+}
+
+impl Recovered {
+    /// As instruções válidas do programa.
+    pub fn statements(&self) -> impl Iterator<Item = &Statement> {
+        self.nodes.iter().filter_map(|node| match node {
+            Node::Statement(statement) => Some(statement),
+            _ => None,
+        })
+    }
+}
+
+/// Passo de uma orquestração: `[STEP nome [DEPENDS ON a, b]:] comando`.
+///
+/// As dependências seguem o `depends_on` dos spans de `boot_sequence.lll`:
+/// um passo só começa depois que todos os passos nomeados terminam.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// Nome do passo, usado em `DEPENDS ON`.
+    pub name: Option<String>,
+    /// Passos que precisam terminar antes deste.
+    pub depends_on: Vec<String>,
+    /// Comando executado pelo passo.
+    pub imperative: Imperative,
 }
 
 /// Um comando imperativo LogLine.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Imperative {
     /// Tipo específico do comando imperativo.
     pub kind: ImperativeKind,
 }
 
+/// Forma canônica do comando: o texto produzido sempre volta ao mesmo
+/// comando quando passado pelo parser.
 impl fmt::Display for Imperative {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl Imperative {
+    /// Escreve o comando na forma canônica; os passos de um `ORCHESTRATE`
+    /// são indentados a partir de `indent` níveis.
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
         match &self.kind {
             ImperativeKind::DefineContract { id, clauses } => {
-                write!(f, "DEFINE CONTRACT {} {}", id, clauses.join(", "))
+                write!(f, "DEFINE CONTRACT {}", id)?;
+                for (i, clause) in clauses.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, Quoted(clause))?;
+                }
+                Ok(())
             }
             ImperativeKind::DefineIdea { id, text } => {
-                write!(f, "DEFINE IDEA {} \"{}\"", id, text)
+                write!(f, "DEFINE IDEA {} {}", id, Quoted(text))
             }
             ImperativeKind::SimulateEntity { id, rounds } => {
                 write!(f, "SIMULATE ENTITY {} {}", id, rounds)
             }
-            ImperativeKind::Orchestrate { mode } => {
-                write!(f, "ORCHESTRATE {}", mode)
+            ImperativeKind::Orchestrate { mode, concurrency, steps } => {
+                write!(f, "ORCHESTRATE {}", mode)?;
+                if let Some(concurrency) = concurrency {
+                    write!(f, " {}", concurrency)?;
+                }
+                if steps.is_empty() {
+                    return Ok(());
+                }
+                f.write_str(" {\n")?;
+                for step in steps {
+                    write!(f, "{}", "    ".repeat(indent + 1))?;
+                    if let Some(name) = &step.name {
+                        write!(f, "STEP {}", name)?;
+                        for (i, dependency) in step.depends_on.iter().enumerate() {
+                            let separator = if i == 0 { " DEPENDS ON " } else { ", " };
+                            write!(f, "{}{}", separator, dependency)?;
+                        }
+                        f.write_str(": ")?;
+                    }
+                    step.imperative.fmt_indented(f, indent + 1)?;
+                    f.write_str("\n")?;
+                }
+                write!(f, "{}}}", "    ".repeat(indent))
             }
             ImperativeKind::InvokeRuleset { entity_id, ruleset_id } => {
                 write!(f, "INVOKE RULESET {} ON {}", ruleset_id, entity_id)
             }
+            ImperativeKind::AmendContract { id, clauses } => {
+                write!(f, "AMEND CONTRACT {}", id)?;
+                for (i, clause) in clauses.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, Quoted(clause))?;
+                }
+                Ok(())
+            }
+            ImperativeKind::TagIdea { id, tags } => {
+                write!(f, "TAG IDEA {}", id)?;
+                for (i, tag) in tags.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, Word(tag))?;
+                }
+                Ok(())
+            }
+            ImperativeKind::UpdateIdea { id, text } => {
+                write!(f, "UPDATE IDEA {} {}", id, Quoted(text))
+            }
+            ImperativeKind::RetireEntity { id } => {
+                write!(f, "RETIRE ENTITY {}", id)
+            }
+            ImperativeKind::Revert { event_id } => {
+                write!(f, "REVERT {}", event_id)
+            }
+            ImperativeKind::ShowContract { id } => {
+                write!(f, "SHOW CONTRACT {}", id)
+            }
+            ImperativeKind::ListIdeas { tag } => {
+                f.write_str("LIST IDEAS")?;
+                if let Some(tag) = tag {
+                    write!(f, " WHERE TAG = {}", Word(tag))?;
+                }
+                Ok(())
+            }
+            ImperativeKind::FindSpans { kind, limit } => {
+                f.write_str("FIND SPANS")?;
+                if let Some(kind) = kind {
+                    write!(f, " KIND = {}", Word(kind))?;
+                }
+                if let Some(limit) = limit {
+                    write!(f, " LIMIT {}", limit)?;
+                }
+                Ok(())
+            }
+            ImperativeKind::ShowSimulation { entity_id, last } => {
+                write!(f, "SHOW SIMULATION {}", entity_id)?;
+                if let Some(last) = last {
+                    write!(f, " LAST {}", last)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
+/// Texto entre aspas duplas, com `\\`, `"` e quebras de linha escapados.
+pub(crate) struct Quoted<'a>(&'a str);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
 /// Tipos específicos de comandos imperativos LogLine.
-#[derive(Debug, PartialEq)]
+///
+/// Identificadores e textos podem conter interpolações `${nome}` (ver
+/// [`crate::template`]); o runtime as resolve antes de executar o comando.
+#[derive(Debug, Clone, PartialEq)]
 pub enum ImperativeKind {
     /// Define um contrato LogLine.
     DefineContract {
@@ -75,10 +586,15 @@ pub enum ImperativeKind {
         rounds: usize,
     },
     
-    /// Inicia uma orquestração com o modo especificado.
+    /// Executa um bloco de passos no modo especificado:
+    /// `ORCHESTRATE modo [concorrência] { ... }`.
     Orchestrate {
-        /// Modo de orquestração (sequencial, paralelo, etc).
+        /// Modo de orquestração (`sequential`, `parallel` ou `dag`).
         mode: String,
+        /// Limite de passos executados ao mesmo tempo.
+        concurrency: Option<usize>,
+        /// Passos da orquestração, na ordem do bloco.
+        steps: Vec<Step>,
     },
     
     /// Invoca um conjunto de regras sobre uma entidade.
@@ -88,4 +604,96 @@ pub enum ImperativeKind {
         /// Identificador do conjunto de regras.
         ruleset_id: String,
     },
+
+    /// Substitui as cláusulas de um contrato existente.
+    AmendContract {
+        /// Identificador do contrato.
+        id: String,
+        /// Novas cláusulas do contrato.
+        clauses: Vec<String>,
+    },
+
+    /// Adiciona tags a uma ideia.
+    TagIdea {
+        /// Identificador da ideia.
+        id: String,
+        /// Tags a adicionar (ao menos uma).
+        tags: Vec<String>,
+    },
+
+    /// Substitui o texto de uma ideia.
+    UpdateIdea {
+        /// Identificador da ideia.
+        id: String,
+        /// Novo texto da ideia.
+        text: String,
+    },
+
+    /// Remove uma entidade do registro.
+    RetireEntity {
+        /// Identificador da entidade.
+        id: String,
+    },
+
+    /// Reverte um evento da timeline com eventos de compensação
+    /// (`REVERT id-do-evento`).
+    Revert {
+        /// Identificador do evento a reverter.
+        event_id: String,
+    },
+
+    /// Consulta um contrato (`SHOW CONTRACT id`).
+    ShowContract {
+        /// Identificador do contrato.
+        id: String,
+    },
+
+    /// Lista as ideias, opcionalmente filtradas por tag
+    /// (`LIST IDEAS WHERE TAG = x`).
+    ListIdeas {
+        /// Tag que as ideias devem ter.
+        tag: Option<String>,
+    },
+
+    /// Busca spans registrados (`FIND SPANS KIND = x LIMIT n`).
+    FindSpans {
+        /// Tipo dos spans buscados.
+        kind: Option<String>,
+        /// Número máximo de spans.
+        limit: Option<usize>,
+    },
+
+    /// Consulta os últimos resultados de simulação de uma entidade
+    /// (`SHOW SIMULATION id LAST n`).
+    ShowSimulation {
+        /// Identificador da entidade simulada.
+        entity_id: String,
+        /// Número de resultados mais recentes.
+        last: Option<usize>,
+    },
+}
+
+impl ImperativeKind {
+    /// Indica se o comando altera um contrato, ideia ou entidade existente.
+    pub fn is_mutation(&self) -> bool {
+        matches!(
+            self,
+            ImperativeKind::AmendContract { .. }
+                | ImperativeKind::TagIdea { .. }
+                | ImperativeKind::UpdateIdea { .. }
+                | ImperativeKind::RetireEntity { .. }
+                | ImperativeKind::Revert { .. }
+        )
+    }
+
+    /// Indica se o comando apenas consulta dados, sem alterá-los.
+    pub fn is_query(&self) -> bool {
+        matches!(
+            self,
+            ImperativeKind::ShowContract { .. }
+                | ImperativeKind::ListIdeas { .. }
+                | ImperativeKind::FindSpans { .. }
+                | ImperativeKind::ShowSimulation { .. }
+        )
+    }
 }
\ No newline at end of file
@@ -0,0 +1,139 @@
+// === File: parser/src/error.rs ===
+/*
+    Description: Erros de parsing da linguagem LogLine, com posição no código-fonte e diagnóstico.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fmt;
+
+/// Erros que podem ocorrer durante o parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// Erro de sintaxe localizado no código-fonte.
+    Syntax(SyntaxError),
+}
+
+impl Error {
+    /// Retorna o erro de sintaxe subjacente.
+    pub fn syntax(&self) -> &SyntaxError {
+        match self {
+            Error::Syntax(e) => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(e) => write!(
+                f,
+                "Erro de sintaxe na linha {}, coluna {}: {}",
+                e.line, e.column, e.message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Erro de sintaxe com a posição exata onde o parsing falhou.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    /// Mensagem explicativa.
+    pub message: String,
+    /// Linha (base 1) onde o erro ocorreu.
+    pub line: usize,
+    /// Coluna (base 1, em caracteres) onde o erro ocorreu.
+    pub column: usize,
+    /// Deslocamento em bytes a partir do início da entrada.
+    pub offset: usize,
+    /// Tokens que o parser esperava encontrar nessa posição.
+    pub expected: Vec<String>,
+    /// Trecho encontrado no lugar do esperado (vazio no fim da entrada).
+    pub found: String,
+    /// Texto completo da linha onde o erro ocorreu.
+    pub source_line: String,
+}
+
+impl SyntaxError {
+    /// Monta um erro de sintaxe a partir do deslocamento em `source`.
+    pub(crate) fn at(source: &str, offset: usize, expected: Vec<String>, message: Option<String>) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = source[offset..].find('\n').map(|i| offset + i).unwrap_or(source.len());
+        let source_line = source[line_start..line_end].trim_end_matches('\r').to_string();
+
+        let found = source[offset..line_end]
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .to_string();
+
+        let message = message.unwrap_or_else(|| {
+            let found = if found.is_empty() {
+                "fim da linha".to_string()
+            } else {
+                format!("'{}'", found)
+            };
+            match expected.len() {
+                0 => format!("encontrado {} inesperado", found),
+                _ => format!("esperado {}; encontrado {}", join_expected(&expected), found),
+            }
+        });
+
+        Self {
+            message,
+            line: before.matches('\n').count() + 1,
+            column: source[line_start..offset].chars().count() + 1,
+            offset,
+            expected,
+            found,
+            source_line,
+        }
+    }
+
+    /// Renderiza o erro como um diagnóstico com marcador, no estilo do rustc.
+    ///
+    /// `origin` identifica a fonte da entrada (nome do arquivo, `dsl`, etc).
+    pub fn render(&self, origin: &str) -> String {
+        let gutter = self.line.to_string().len();
+        let pad = " ".repeat(gutter);
+        let indent: String = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let carets = "^".repeat(self.found.chars().count().max(1));
+
+        format!(
+            "erro: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.message,
+            pad,
+            origin,
+            self.line,
+            self.column,
+            pad,
+            self.line,
+            self.source_line,
+            pad,
+            indent,
+            carets,
+        )
+    }
+}
+
+/// Junta os tokens esperados em uma lista legível ("A, B ou C").
+fn join_expected(expected: &[String]) -> String {
+    match expected.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} ou {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,84 @@
+// === File: parser/src/format.rs ===
+/*
+    Description: Formatador canônico para scripts na linguagem LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use crate::ast::Command;
+use crate::parser::{self, Item};
+use crate::Error;
+
+/// Formata um comando na sua forma canônica.
+///
+/// A forma canônica sempre volta ao mesmo comando pelo parser:
+/// `parse(&format_command(&cmd)) == Ok(cmd)`.
+pub fn format_command(command: &Command) -> String {
+    command.to_string()
+}
+
+/// Formata uma lista de comandos como um script, um comando por linha.
+pub fn format_program(commands: &[Command]) -> String {
+    commands.iter().map(|c| format!("{}\n", c)).collect()
+}
+
+/// Reformata o código-fonte de um script LogLine.
+///
+/// Cada instrução é reescrita na forma canônica; comentários são mantidos
+/// e sequências de linhas em branco são reduzidas a uma só. Instruções com
+/// comentários dentro de blocos (`{ ... }` ou `BEGIN ... COMMIT`) são
+/// mantidas como estão, para não perder os comentários.
+///
+/// # Retorno
+///
+/// O script formatado ou o erro de sintaxe que impediu a formatação.
+pub fn format_source(source: &str) -> Result<String, Error> {
+    let items = parser::parse_layout(source).map_err(Error::Syntax)?;
+    let mut out = String::new();
+
+    for item in items {
+        match item {
+            Item::Comment(text) => out.push_str(&text),
+            Item::Blank => {}
+            Item::Statement { statement, source, trailing } => {
+                if has_inner_comment(&source) {
+                    out.push_str(&source);
+                } else {
+                    out.push_str(&statement.command.to_string());
+                }
+                if let Some(comment) = trailing {
+                    out.push(' ');
+                    out.push_str(&comment);
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// Indica se o texto de uma instrução contém comentários fora de strings.
+fn has_inner_comment(source: &str) -> bool {
+    let mut in_string = false;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '#' if !in_string => return true,
+            '/' if !in_string && chars.peek() == Some(&'/') => return true,
+            _ => {}
+        }
+    }
+
+    false
+}
@@ -11,16 +11,15 @@
 */
 
 pub mod ast;
+pub mod error;
+pub mod format;
+pub mod lll;
+pub mod template;
 mod parser;
 
-use ast::Command;
-
-/// Erros que podem ocorrer durante o parsing.
-#[derive(Debug)]
-pub enum Error {
-    /// Erro de sintaxe com mensagem explicativa.
-    Syntax(String),
-}
+use ast::{Command, Recovered, Statement};
+pub use error::{Error, SyntaxError};
+pub use format::{format_command, format_program, format_source};
 
 /// Faz o parsing de uma string LogLine em uma estrutura de comando.
 ///
@@ -32,7 +31,43 @@ pub enum Error {
 ///
 /// Um `Result` que contém o comando parseado ou um erro de parsing.
 pub fn parse(input: &str) -> Result<Command, Error> {
-    parser::parse_command(input).map_err(|e| {
-        Error::Syntax(format!("Erro de sintaxe: {}", e))
+    parser::parse_command(input).map_err(Error::Syntax)
+}
+
+/// Faz o parsing de um script LogLine com várias imperativas.
+///
+/// Cada imperativa ocupa uma linha; linhas em branco e comentários
+/// iniciados por `//` ou `#` são ignorados.
+///
+/// # Argumentos
+///
+/// * `input` - O conteúdo completo do script.
+///
+/// # Retorno
+///
+/// Um `Result` com os comandos na ordem em que aparecem ou o erro de
+/// parsing da primeira instrução inválida, indicando sua linha.
+pub fn parse_program(input: &str) -> Result<Vec<Command>, Error> {
+    parse_statements(input).map(|statements| {
+        statements.into_iter().map(|s| s.command).collect()
     })
-}
\ No newline at end of file
+}
+
+/// Como [`parse_program`], mas preserva a linha de origem de cada instrução.
+pub fn parse_statements(input: &str) -> Result<Vec<Statement>, Error> {
+    parser::parse_program(input).map_err(Error::Syntax)
+}
+
+/// Faz o parsing de um script LogLine sem parar no primeiro erro, para uso
+/// em editores.
+///
+/// Instruções inválidas são registradas como nós `Error` ou `Missing` e o
+/// parsing continua na instrução seguinte; um bloco `{ ... }` com erro é
+/// descartado por inteiro.
+///
+/// # Retorno
+///
+/// A AST parcial e todos os diagnósticos encontrados.
+pub fn parse_with_recovery(input: &str) -> Recovered {
+    parser::parse_recovering(input)
+}
@@ -0,0 +1,516 @@
+// === File: parser/src/lll.rs ===
+/*
+    Description: Parser da linguagem de contratos .lll usada pelos arquivos do lllpack.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+//! Contratos `.lll`.
+//!
+//! Um arquivo `.lll` declara um único contrato:
+//!
+//! ```text
+//! contract Nome {
+//!   title: "Título"
+//!   tags: ["a", "b"]
+//!
+//!   secao {
+//!     chave: "valor"
+//!     clause 1.1 { text: "..." }
+//!   }
+//!
+//!   span Nome {
+//!     type: "tipo"
+//!     depends_on: ["Outro"]
+//!     fields: [{ name: "status", value: "ok" }]
+//!     on_failure: { span: "Recuperacao" }
+//!   }
+//! }
+//! ```
+//!
+//! Entradas e itens de listas podem ser separados por quebras de linha ou
+//! vírgulas, e comentários começam com `//`. Strings entre `"""` podem
+//! ocupar várias linhas e têm a indentação comum removida.
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until, take_while1},
+    character::complete::{char, digit1, multispace1, not_line_ending, space0, space1},
+    combinator::{cut, map, map_res, not, opt, recognize},
+    multi::many0,
+    sequence::{pair, preceded, terminated, tuple},
+    Err, Parser,
+};
+
+use crate::error::{Error, SyntaxError};
+use crate::parser::{expect, failure, first_line, keyword, line_of, quoted_string, Failure, PResult};
+
+/// Contrato declarado em um arquivo `.lll`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contract {
+    /// Nome após `contract`.
+    pub name: String,
+    /// Entrada `title`.
+    pub title: Option<String>,
+    /// Entrada `version`.
+    pub version: Option<String>,
+    /// Entrada `author`.
+    pub author: Option<String>,
+    /// Entrada `created`.
+    pub created: Option<String>,
+    /// Entrada `tags`.
+    pub tags: Vec<String>,
+    /// Demais membros do contrato, na ordem do arquivo.
+    pub body: Vec<Member>,
+}
+
+/// Membro do corpo de um contrato ou de uma seção.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Member {
+    /// Entrada `chave: valor`.
+    Entry(Entry),
+    /// Seção aninhada `tipo [rótulo] { ... }`.
+    Section(Section),
+    /// Declaração `span Nome { ... }`.
+    Span(Box<Span>),
+}
+
+/// Entrada `chave: valor`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+}
+
+/// Seção aninhada, como `principios { ... }`, `term Nome { ... }` ou
+/// `clause 1.1 { ... }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// Palavra que abre a seção (`principios`, `term`, `clause`...).
+    pub kind: String,
+    /// Rótulo opcional entre a palavra e a chave de abertura.
+    pub name: Option<String>,
+    /// Membros da seção.
+    pub body: Vec<Member>,
+    /// Linha (base 1) onde a seção começa.
+    pub line: usize,
+}
+
+/// Declaração de um span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    /// Nome após `span`.
+    pub name: String,
+    /// Entrada `id`.
+    pub id: Option<String>,
+    /// Entrada `type`.
+    pub span_type: Option<String>,
+    /// Entrada `actors`.
+    pub actors: Vec<String>,
+    /// Entrada `fields`.
+    pub fields: Vec<Field>,
+    /// Spans que precisam concluir antes deste (`depends_on`).
+    pub depends_on: Vec<String>,
+    /// Spans disparados por este (`children`).
+    pub children: Vec<String>,
+    /// Desfecho em caso de sucesso.
+    pub on_success: Option<Outcome>,
+    /// Desfecho em caso de falha.
+    pub on_failure: Option<Outcome>,
+    /// Demais entradas (`timestamp`, `parent_id`, `timeouts`...).
+    pub attributes: Vec<Entry>,
+    /// Linha (base 1) onde o span começa.
+    pub line: usize,
+}
+
+/// Campo `{ name: ..., value: ... }` de um span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub value: Value,
+}
+
+/// Desfecho de um span (`on_success` ou `on_failure`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    /// Span a ser disparado.
+    pub span: Option<String>,
+    /// Campos atualizados no span.
+    pub fields: Vec<Field>,
+    /// Demais entradas.
+    pub attributes: Vec<Entry>,
+}
+
+/// Valor de uma entrada.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+    List(Vec<Value>),
+    Object(Vec<Entry>),
+}
+
+impl Contract {
+    /// Valor de uma entrada do nível superior.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        find_entry(&self.body, key)
+    }
+
+    /// Primeira seção do nível superior com o tipo `kind`.
+    pub fn section(&self, kind: &str) -> Option<&Section> {
+        self.body.iter().find_map(|m| match m {
+            Member::Section(s) if s.kind == kind => Some(s),
+            _ => None,
+        })
+    }
+
+    /// Todos os spans do contrato, inclusive os declarados dentro de
+    /// seções, na ordem do arquivo.
+    pub fn spans(&self) -> Vec<&Span> {
+        let mut spans = Vec::new();
+        collect_spans(&self.body, &mut spans);
+        spans
+    }
+}
+
+impl Section {
+    /// Valor de uma entrada da seção.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        find_entry(&self.body, key)
+    }
+}
+
+impl Span {
+    /// Valor de um atributo do span que não tem campo próprio.
+    pub fn attribute(&self, key: &str) -> Option<&Value> {
+        self.attributes.iter().find(|e| e.key == key).map(|e| &e.value)
+    }
+}
+
+impl Value {
+    /// O texto, se o valor for uma string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Valor de uma chave, se o valor for um objeto.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries.iter().find(|e| e.key == key).map(|e| &e.value),
+            _ => None,
+        }
+    }
+}
+
+fn find_entry<'a>(members: &'a [Member], key: &str) -> Option<&'a Value> {
+    members.iter().find_map(|m| match m {
+        Member::Entry(e) if e.key == key => Some(&e.value),
+        _ => None,
+    })
+}
+
+fn collect_spans<'a>(members: &'a [Member], spans: &mut Vec<&'a Span>) {
+    for member in members {
+        match member {
+            Member::Span(span) => spans.push(span.as_ref()),
+            Member::Section(section) => collect_spans(&section.body, spans),
+            Member::Entry(_) => {}
+        }
+    }
+}
+
+/// Faz o parsing de um arquivo `.lll`.
+///
+/// # Argumentos
+///
+/// * `source` - O conteúdo completo do arquivo.
+///
+/// # Retorno
+///
+/// O contrato declarado no arquivo ou o erro de sintaxe com sua posição.
+pub fn parse(source: &str) -> Result<Contract, Error> {
+    Grammar { source }.document().map_err(Error::Syntax)
+}
+
+/// Gramática `.lll`; guarda a entrada completa para numerar as linhas.
+struct Grammar<'a> {
+    source: &'a str,
+}
+
+impl<'a> Grammar<'a> {
+    fn document(&self) -> Result<Contract, SyntaxError> {
+        let source = self.source;
+        let (rest, contract) = preceded(trivia, |i| self.contract(i))
+            .parse(source)
+            .map_err(|e| failure(e, source).into_syntax_error(source, None))?;
+        let (rest, _) = trivia(rest).map_err(|e| failure(e, rest).into_syntax_error(source, None))?;
+
+        if rest.is_empty() {
+            Ok(contract)
+        } else {
+            Err(SyntaxError::at(
+                source,
+                source.len() - rest.len(),
+                vec!["fim do arquivo".to_string()],
+                Some(format!("conteúdo inesperado após o contrato: '{}'", first_line(rest).trim_end())),
+            ))
+        }
+    }
+
+    fn contract(&self, input: &'a str) -> PResult<'a, Contract> {
+        let (input, _) = terminated(keyword("contract"), space1).parse(input)?;
+        let (input, name) = cut(name).parse(input)?;
+        let (input, _) = space0(input)?;
+        let (input, body) = cut(items('{', '}', |i| self.member(i))).parse(input)?;
+
+        let mut contract = Contract {
+            name: name.to_string(),
+            title: None,
+            version: None,
+            author: None,
+            created: None,
+            tags: Vec::new(),
+            body: Vec::new(),
+        };
+        for member in body {
+            match member {
+                Member::Entry(Entry { key, value: Value::Text(text) })
+                    if matches!(key.as_str(), "title" | "version" | "author" | "created") =>
+                {
+                    let slot = match key.as_str() {
+                        "title" => &mut contract.title,
+                        "version" => &mut contract.version,
+                        "author" => &mut contract.author,
+                        _ => &mut contract.created,
+                    };
+                    *slot = Some(text);
+                }
+                Member::Entry(Entry { ref key, ref value }) if key == "tags" && strings(value).is_some() => {
+                    contract.tags = strings(value).unwrap_or_default();
+                }
+                other => contract.body.push(other),
+            }
+        }
+        Ok((input, contract))
+    }
+
+    fn member(&self, input: &'a str) -> PResult<'a, Member> {
+        alt((
+            map(|i| self.span(i), |span| Member::Span(Box::new(span))),
+            map(entry, Member::Entry),
+            map(|i| self.section(i), Member::Section),
+        ))
+        .parse(input)
+    }
+
+    fn section(&self, input: &'a str) -> PResult<'a, Section> {
+        let line = line_of(self.source, input);
+        let (input, kind) = name(input)?;
+        let (input, label) = opt(preceded(space1, label)).parse(input)?;
+        let (input, _) = space0(input)?;
+        let (input, body) = items('{', '}', |i| self.member(i)).parse(input)?;
+
+        Ok((input, Section {
+            kind: kind.to_string(),
+            name: label.map(str::to_string),
+            body,
+            line,
+        }))
+    }
+
+    fn span(&self, input: &'a str) -> PResult<'a, Span> {
+        let line = line_of(self.source, input);
+        let (input, _) = terminated(keyword("span"), space1).parse(input)?;
+        let (input, name) = name(input)?;
+        let (input, _) = space0(input)?;
+        let (input, entries) = cut(items('{', '}', located(entry))).parse(input)?;
+
+        let mut span = Span {
+            name: name.to_string(),
+            id: None,
+            span_type: None,
+            actors: Vec::new(),
+            fields: Vec::new(),
+            depends_on: Vec::new(),
+            children: Vec::new(),
+            on_success: None,
+            on_failure: None,
+            attributes: Vec::new(),
+            line,
+        };
+        for (at, entry) in entries {
+            let invalid = |label| Err::Failure(Failure::expecting(at, label));
+            match entry.key.as_str() {
+                "id" => span.id = Some(entry.value.as_str().ok_or_else(|| invalid("string"))?.to_string()),
+                "type" => span.span_type = Some(entry.value.as_str().ok_or_else(|| invalid("string"))?.to_string()),
+                "actors" => span.actors = strings(&entry.value).ok_or_else(|| invalid("lista de strings"))?,
+                "depends_on" => span.depends_on = strings(&entry.value).ok_or_else(|| invalid("lista de strings"))?,
+                "children" => span.children = strings(&entry.value).ok_or_else(|| invalid("lista de strings"))?,
+                "fields" => span.fields = fields(&entry.value).ok_or_else(|| invalid("lista de campos { name, value }"))?,
+                "on_success" => span.on_success = Some(outcome(entry.value).map_err(invalid)?),
+                "on_failure" => span.on_failure = Some(outcome(entry.value).map_err(invalid)?),
+                _ => span.attributes.push(entry),
+            }
+        }
+        Ok((input, span))
+    }
+}
+
+/// Converte uma lista de strings.
+fn strings(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::List(items) => items.iter().map(|v| v.as_str().map(str::to_string)).collect(),
+        _ => None,
+    }
+}
+
+/// Converte uma lista de campos `{ name: ..., value: ... }`.
+fn fields(value: &Value) -> Option<Vec<Field>> {
+    match value {
+        Value::List(items) => items
+            .iter()
+            .map(|item| {
+                Some(Field {
+                    name: item.get("name")?.as_str()?.to_string(),
+                    value: item.get("value")?.clone(),
+                })
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Converte o objeto de `on_success`/`on_failure`.
+fn outcome(value: Value) -> Result<Outcome, &'static str> {
+    let Value::Object(entries) = value else {
+        return Err("objeto { span, fields }");
+    };
+
+    let mut outcome = Outcome { span: None, fields: Vec::new(), attributes: Vec::new() };
+    for entry in entries {
+        match entry.key.as_str() {
+            "span" => outcome.span = Some(entry.value.as_str().ok_or("nome do span")?.to_string()),
+            "fields" => outcome.fields = fields(&entry.value).ok_or("lista de campos { name, value }")?,
+            _ => outcome.attributes.push(entry),
+        }
+    }
+    Ok(outcome)
+}
+
+/// Itens entre `open` e `close`, separados por quebras de linha ou vírgulas.
+fn items<'a, O, P>(open: char, close: char, mut item: P) -> impl FnMut(&'a str) -> PResult<'a, Vec<O>>
+where
+    P: Parser<&'a str, O, Failure<'a>>,
+{
+    move |input: &'a str| {
+        let (mut input, _) = char(open)(input)?;
+        let mut items = Vec::new();
+        loop {
+            let (rest, _) = trivia(input)?;
+            let (rest, parsed) = alt((map(char(close), |_| None), map(|i| item.parse(i), Some))).parse(rest)?;
+            match parsed {
+                None => return Ok((rest, items)),
+                Some(parsed) => items.push(parsed),
+            }
+            let (rest, _) = opt(preceded(trivia, char(','))).parse(rest)?;
+            input = rest;
+        }
+    }
+}
+
+/// Anexa a posição inicial ao resultado de `parser`.
+fn located<'a, O, P>(mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, (&'a str, O)>
+where
+    P: Parser<&'a str, O, Failure<'a>>,
+{
+    move |input: &'a str| parser.parse(input).map(|(rest, o)| (rest, (input, o)))
+}
+
+/// Entrada `chave: valor`; a chave pode estar entre aspas.
+fn entry(input: &str) -> PResult<'_, Entry> {
+    let (input, key) = alt((quoted_string, map(name, str::to_string))).parse(input)?;
+    let (input, _) = preceded(space0, char(':')).parse(input)?;
+    let (input, _) = trivia(input)?;
+    let (input, value) = cut(value).parse(input)?;
+    Ok((input, Entry { key, value }))
+}
+
+/// Valor: string, string em bloco, número, booleano, `null`, lista ou objeto.
+fn value(input: &str) -> PResult<'_, Value> {
+    expect(
+        "valor",
+        alt((
+            map(text_block, Value::Text),
+            map(quoted_string, Value::Text),
+            map(items('[', ']', value), Value::List),
+            map(items('{', '}', entry), Value::Object),
+            map(number, Value::Number),
+            map(word("true"), |_| Value::Bool(true)),
+            map(word("false"), |_| Value::Bool(false)),
+            map(word("null"), |_| Value::Null),
+        )),
+    )(input)
+}
+
+/// String entre `"""`, que pode ocupar várias linhas.
+fn text_block(input: &str) -> PResult<'_, String> {
+    let (input, _) = tag("\"\"\"")(input)?;
+    let (input, text) = cut(terminated(take_until("\"\"\""), expect("'\"\"\"'", tag("\"\"\"")))).parse(input)?;
+    Ok((input, dedent(text)))
+}
+
+/// Remove a primeira quebra de linha, a indentação comum e os espaços finais.
+fn dedent(text: &str) -> String {
+    let text = text.strip_prefix("\r\n").or_else(|| text.strip_prefix('\n')).unwrap_or(text);
+    let indent = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    text.lines()
+        .map(|l| l.get(indent..).unwrap_or_else(|| l.trim_start()).trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+fn number(input: &str) -> PResult<'_, f64> {
+    map_res(
+        recognize(tuple((opt(char('-')), digit1, opt(pair(char('.'), digit1))))),
+        str::parse::<f64>,
+    )
+    .parse(input)
+}
+
+/// Palavra reservada que não continua em um nome (`true`, mas não `trueX`).
+fn word<'a>(w: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    terminated(keyword(w), not(name))
+}
+
+/// Nome de contrato, seção, span ou chave.
+fn name(input: &str) -> PResult<'_, &str> {
+    expect("nome", take_while1(|c: char| c.is_alphanumeric() || c == '_'))(input)
+}
+
+/// Rótulo de seção, como `Nome` ou `1.1`.
+fn label(input: &str) -> PResult<'_, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')(input)
+}
+
+/// Consome espaços em branco, quebras de linha e comentários `//`.
+fn trivia(input: &str) -> PResult<'_, ()> {
+    map(many0(alt((multispace1, preceded(tag("//"), not_line_ending)))), |_| ()).parse(input)
+}
@@ -12,83 +12,712 @@
 
 use nom::{
     IResult,
-    bytes::complete::{tag, take_till, take_while},
-    character::complete::{char, digit1, multispace0, multispace1},
-    combinator::{map, map_res},
-    multi::separated_list0,
+    bytes::complete::{tag, take_while, take_while1, take_while_m_n},
+    character::complete::{char, digit1, line_ending, multispace0, multispace1, not_line_ending, space0, space1},
+    combinator::{cut, eof, map, map_res, not, opt, peek, recognize, value},
+    error::{ErrorKind, FromExternalError, ParseError},
+    multi::{many0, many1, separated_list0, separated_list1},
     // Import the combinators we use. We retain `tuple` here to build
     // compound parsers; although the `Parser` trait is implemented
     // directly for tuples, the helper function is still required to
     // construct the parser from individual combinators.
-    sequence::{delimited, tuple},
+    sequence::{delimited, preceded, terminated, tuple},
     branch::alt,
-    Parser,
+    Err, Parser,
 };
+use std::cmp::Ordering;
 
-use crate::ast::{Command, Imperative, ImperativeKind};
+use crate::ast::{Command, CompareOp, Condition, Imperative, ImperativeKind, ItemKind, ListSource, Node, Policy, Recovered, Statement, Step, Value};
+use crate::error::SyntaxError;
+
+/// Falha interna do parser: a posição em que ocorreu e os tokens que eram
+/// esperados ali. Entre alternativas, prevalece a que avançou mais.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Failure<'a> {
+    input: &'a str,
+    expected: Vec<String>,
+}
+
+/// Resultado dos parsers internos.
+pub(crate) type PResult<'a, O> = IResult<&'a str, O, Failure<'a>>;
+
+impl<'a> ParseError<&'a str> for Failure<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        let expected = match kind {
+            ErrorKind::Space | ErrorKind::MultiSpace => vec!["espaço".to_string()],
+            ErrorKind::Digit | ErrorKind::MapRes => vec!["número".to_string()],
+            ErrorKind::CrLf | ErrorKind::Eof => vec!["fim da linha".to_string()],
+            _ => Vec::new(),
+        };
+        Failure { input, expected }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        Failure { input, expected: vec![format!("'{}'", c)] }
+    }
+
+    fn or(mut self, other: Self) -> Self {
+        // Menos entrada restante significa que a alternativa avançou mais.
+        match self.input.len().cmp(&other.input.len()) {
+            Ordering::Less => self,
+            Ordering::Greater => other,
+            Ordering::Equal => {
+                for token in other.expected {
+                    if !self.expected.contains(&token) {
+                        self.expected.push(token);
+                    }
+                }
+                self
+            }
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for Failure<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+impl<'a> Failure<'a> {
+    /// Falha em `input` com um único token esperado.
+    pub(crate) fn expecting(input: &'a str, label: &str) -> Self {
+        Failure { input, expected: vec![label.to_string()] }
+    }
+
+    /// Converte a falha em um erro de sintaxe posicionado em `source`.
+    pub(crate) fn into_syntax_error(self, source: &str, message: Option<String>) -> SyntaxError {
+        SyntaxError::at(source, source.len() - self.input.len(), self.expected, message)
+    }
+}
+
+/// Extrai a falha de um erro nom (`Incomplete` não ocorre com parsers `complete`).
+pub(crate) fn failure<'a>(e: Err<Failure<'a>>, input: &'a str) -> Failure<'a> {
+    match e {
+        Err::Error(f) | Err::Failure(f) => f,
+        Err::Incomplete(_) => Failure { input: &input[input.len()..], expected: Vec::new() },
+    }
+}
+
+/// Rotula a falha de `parser` com `label` quando ele não consome nada.
+pub(crate) fn expect<'a, O, P>(label: &'static str, mut parser: P) -> impl FnMut(&'a str) -> PResult<'a, O>
+where
+    P: Parser<&'a str, O, Failure<'a>>,
+{
+    move |input: &'a str| {
+        parser.parse(input).map_err(|e| {
+            e.map(|mut f| {
+                if f.input.len() == input.len() {
+                    f.expected = vec![label.to_string()];
+                }
+                f
+            })
+        })
+    }
+}
+
+/// Parser para uma palavra-chave da linguagem.
+pub(crate) fn keyword<'a>(word: &'static str) -> impl FnMut(&'a str) -> PResult<'a, &'a str> {
+    expect(word, tag(word))
+}
 
 /// Faz o parsing de um comando LogLine completo.
-pub fn parse_command(input: &str) -> Result<Command, String> {
-    match command(input) {
-        Ok((rest, cmd)) => {
-            if rest.trim().is_empty() {
-                Ok(cmd)
-            } else {
-                Err(format!("Entrada não foi totalmente consumida. Restante: '{}'", rest))
+pub fn parse_command(source: &str) -> Result<Command, SyntaxError> {
+    let (input, _) = trivia(source).map_err(|e| failure(e, source).into_syntax_error(source, None))?;
+    let (rest, cmd) = command(input).map_err(|e| failure(e, input).into_syntax_error(source, None))?;
+    let (rest, _) = trivia(rest).map_err(|e| failure(e, rest).into_syntax_error(source, None))?;
+
+    if rest.is_empty() {
+        Ok(cmd)
+    } else {
+        Err(SyntaxError::at(
+            source,
+            source.len() - rest.len(),
+            vec!["fim da entrada".to_string()],
+            Some(format!("entrada não foi totalmente consumida; restante: '{}'", rest.trim_end())),
+        ))
+    }
+}
+
+/// Elemento do layout de um script: instruções e o que há entre elas.
+///
+/// Usado pelo formatador para preservar comentários e linhas em branco.
+#[derive(Debug, PartialEq)]
+pub enum Item {
+    /// Comentário em linha própria (incluindo o marcador `//` ou `#`).
+    Comment(String),
+    /// Uma ou mais linhas em branco consecutivas.
+    Blank,
+    /// Uma instrução.
+    Statement {
+        /// A instrução analisada.
+        statement: Statement,
+        /// Texto original da instrução.
+        source: String,
+        /// Comentário que segue a instrução na mesma linha, se houver.
+        trailing: Option<String>,
+    },
+}
+
+/// Faz o parsing de um programa LogLine: uma imperativa por linha,
+/// intercalada com linhas em branco e comentários (`//` ou `#`).
+///
+/// Cada instrução é devolvida com a linha (base 1) onde começa.
+pub fn parse_program(source: &str) -> Result<Vec<Statement>, SyntaxError> {
+    Ok(parse_layout(source)?
+        .into_iter()
+        .filter_map(|item| match item {
+            Item::Statement { statement, .. } => Some(statement),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Faz o parsing de um programa LogLine preservando comentários e linhas
+/// em branco.
+pub fn parse_layout(source: &str) -> Result<Vec<Item>, SyntaxError> {
+    let mut items = Vec::new();
+    let mut input = source;
+    // Indica se a entrada atual começa no início de uma linha.
+    let mut at_line_start = true;
+
+    loop {
+        let (rest, pieces) = layout_trivia(input).map_err(|e| failure(e, input).into_syntax_error(source, None))?;
+        for piece in pieces {
+            match piece {
+                Trivia::Comment(text) => {
+                    items.push(Item::Comment(text.trim_end().to_string()));
+                    at_line_start = false;
+                }
+                Trivia::Newlines(n) => {
+                    let blanks = if at_line_start { n } else { n - 1 };
+                    if blanks > 0 && !items.is_empty() && items.last() != Some(&Item::Blank) {
+                        items.push(Item::Blank);
+                    }
+                    at_line_start = true;
+                }
+            }
+        }
+        if rest.is_empty() {
+            if items.last() == Some(&Item::Blank) {
+                items.pop();
+            }
+            return Ok(items);
+        }
+
+        let line = line_of(source, rest);
+        let start = rest;
+        let (rest, cmd) = command(rest).map_err(|e| failure(e, rest).into_syntax_error(source, None))?;
+        let text = &start[..start.len() - rest.len()];
+        let (rest, trailing) = statement_end(rest).map_err(|e| {
+            let f = failure(e, rest);
+            let message = format!("entrada inesperada após o comando: '{}'", first_line(f.input).trim());
+            f.into_syntax_error(source, Some(message))
+        })?;
+
+        items.push(Item::Statement {
+            statement: Statement { line, command: cmd },
+            source: text.trim_end().to_string(),
+            trailing: trailing.map(|c| c.trim_end().to_string()),
+        });
+        input = rest;
+        at_line_start = true;
+    }
+}
+
+/// Faz o parsing de um programa LogLine sem parar no primeiro erro.
+///
+/// Uma instrução inválida vira um nó `Error` (ou `Missing`, se a linha
+/// terminou antes de um elemento obrigatório) e o parsing recomeça na
+/// fronteira de instrução seguinte.
+pub fn parse_recovering(source: &str) -> Recovered {
+    let mut nodes = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut input = source;
+
+    loop {
+        let rest = match trivia(input) {
+            Ok((rest, _)) => rest,
+            Err(_) => input,
+        };
+        if rest.is_empty() {
+            return Recovered { nodes, diagnostics };
+        }
+
+        let line = line_of(source, rest);
+        let (failure, message) = match command(rest) {
+            Ok((after, cmd)) => match statement_end(after) {
+                Ok((after, _)) => {
+                    nodes.push(Node::Statement(Statement { line, command: cmd }));
+                    input = after;
+                    continue;
+                }
+                Err(e) => {
+                    let f = failure(e, after);
+                    let message = format!("entrada inesperada após o comando: '{}'", first_line(f.input).trim());
+                    (f, Some(message))
+                }
+            },
+            Err(e) => (failure(e, rest), None),
+        };
+
+        let skip = resync(rest, rest.len() - failure.input.len());
+        let text = rest[..skip].trim_end().to_string();
+        let error = failure.into_syntax_error(source, message);
+        nodes.push(if error.found.is_empty() {
+            Node::Missing { line, text, expected: error.expected.clone() }
+        } else {
+            Node::Error { line, text }
+        });
+        diagnostics.push(error);
+        input = &rest[skip..];
+    }
+}
+
+/// Tamanho do trecho a descartar após um erro em `error_at`: até o fim da
+/// linha do erro ou, se o trecho abriu blocos (`{` ou `BEGIN`), até a linha
+/// que os fecha.
+fn resync(text: &str, error_at: usize) -> usize {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut in_comment = false;
+    let mut line_start = true;
+
+    for (i, c) in text.char_indices() {
+        if line_start && !c.is_whitespace() {
+            line_start = false;
+            if text[i..].starts_with("BEGIN") {
+                depth += 1;
+            } else if text[i..].starts_with("COMMIT") {
+                depth -= 1;
+            }
+        }
+        if c == '\n' {
+            line_start = true;
+            // Strings e comentários não atravessam linhas para fins de
+            // recuperação.
+            in_string = false;
+            in_comment = false;
+            if i >= error_at && depth <= 0 {
+                return i + 1;
+            }
+        } else if in_comment {
+            continue;
+        } else if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else {
+            match c {
+                '"' => in_string = true,
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                '#' => in_comment = true,
+                '/' if text[i..].starts_with("//") => in_comment = true,
+                _ => {}
             }
         }
-        Err(e) => Err(format!("{:?}", e)),
     }
+    text.len()
+}
+
+/// Trecho entre instruções relevante para o layout.
+enum Trivia<'a> {
+    Comment(&'a str),
+    /// Espaço em branco contendo `n` quebras de linha (`n` pode ser zero).
+    Newlines(usize),
+}
+
+/// Como [`trivia`], mas devolve os comentários e quebras de linha consumidos.
+fn layout_trivia(input: &str) -> PResult<'_, Vec<Trivia<'_>>> {
+    many0(alt((
+        map(multispace1, |ws: &str| Trivia::Newlines(ws.matches('\n').count())),
+        map(recognize(comment), Trivia::Comment),
+    )))
+    .parse(input)
+}
+
+/// Número da linha (base 1) em que `rest` começa dentro de `source`.
+pub(crate) fn line_of(source: &str, rest: &str) -> usize {
+    let offset = source.len() - rest.len();
+    source[..offset].matches('\n').count() + 1
+}
+
+/// Primeira linha do texto restante, usada nas mensagens de erro.
+pub(crate) fn first_line(input: &str) -> &str {
+    input.lines().next().unwrap_or("")
+}
+
+/// Parser para um comentário de linha (`// ...` ou `# ...`).
+fn comment(input: &str) -> PResult<'_, &str> {
+    preceded(alt((tag("//"), tag("#"))), not_line_ending).parse(input)
+}
+
+/// Consome espaços em branco, quebras de linha e comentários.
+fn trivia(input: &str) -> PResult<'_, ()> {
+    value((), many0(alt((multispace1, comment)))).parse(input)
+}
+
+/// Final de uma instrução: espaços e um comentário opcional até a quebra
+/// de linha ou o fim da entrada. Devolve o comentário, se houver.
+fn statement_end(input: &str) -> PResult<'_, Option<&str>> {
+    let (input, _) = space0(input)?;
+    let (input, trailing) = opt(recognize(comment)).parse(input)?;
+    let (input, _) = alt((line_ending, eof)).parse(input)?;
+    Ok((input, trailing))
 }
 
 /// Parser para um comando LogLine.
-fn command(input: &str) -> IResult<&str, Command> {
+fn command(input: &str) -> PResult<'_, Command> {
     // Apply the Parser trait's parse method to run the parser
-    map(imperative, Command::Imperative).parse(input)
+    alt((
+        let_binding,
+        if_command,
+        for_each,
+        with_command,
+        transaction,
+        map(imperative, Command::Imperative),
+    ))
+    .parse(input)
+}
+
+/// Parser para o comando IF ... THEN ... [ELSE ...].
+fn if_command(input: &str) -> PResult<'_, Command> {
+    let (input, _) = tuple((keyword("IF"), space1)).parse(input)?;
+    let (input, condition) = condition(input)?;
+    let (input, _) = tuple((space1, keyword("THEN"), space1)).parse(input)?;
+    let (input, then_branch) = block(input)?;
+    // O ELSE pode ficar na mesma linha ou na linha seguinte ao bloco THEN.
+    let (input, else_branch) = opt(preceded(
+        tuple((multispace1, keyword("ELSE"), space1)),
+        block,
+    ))
+    .parse(input)?;
+
+    Ok((
+        input,
+        Command::If {
+            condition,
+            then_branch,
+            else_branch,
+        },
+    ))
+}
+
+/// Parser para o comando FOR EACH variável IN origem DO ....
+fn for_each(input: &str) -> PResult<'_, Command> {
+    let (input, _) = tuple((keyword("FOR"), space1, keyword("EACH"), space1)).parse(input)?;
+    let (input, variable) = variable_name(input)?;
+    let (input, _) = tuple((space1, keyword("IN"), space1)).parse(input)?;
+    let (input, source) = list_source(input)?;
+    let (input, _) = tuple((space1, keyword("DO"), space1)).parse(input)?;
+    let (input, body) = block(input)?;
+
+    Ok((
+        input,
+        Command::ForEach {
+            variable: variable.to_string(),
+            source,
+            body,
+        },
+    ))
+}
+
+/// Parser para o comando WITH política DO ... [ON FAILURE ...].
+fn with_command(input: &str) -> PResult<'_, Command> {
+    let (input, _) = keyword("WITH")(input)?;
+    let (input, policy) = policy(input)?;
+    let (input, _) = tuple((space1, keyword("DO"), space1)).parse(input)?;
+    let (input, body) = block(input)?;
+    // Como o ELSE, o ON FAILURE pode ficar na linha seguinte ao bloco.
+    let (input, on_failure) = opt(preceded(
+        tuple((multispace1, keyword("ON"), space1, keyword("FAILURE"), space1)),
+        block,
+    ))
+    .parse(input)?;
+
+    Ok((input, Command::With { policy, body, on_failure }))
+}
+
+/// Parser para a transação BEGIN ... COMMIT, com `BEGIN`, cada comando e
+/// `COMMIT` em linhas próprias.
+fn transaction(input: &str) -> PResult<'_, Command> {
+    let (mut input, _) = tuple((keyword("BEGIN"), block_line_end)).parse(input)?;
+    let mut body = Vec::new();
+
+    loop {
+        let (rest, _) = trivia(input)?;
+        let (rest, next) = alt((value(None, keyword("COMMIT")), map(command, Some))).parse(rest)?;
+        match next {
+            None => return Ok((rest, Command::Transaction { body })),
+            Some(cmd) => {
+                let (rest, _) = block_line_end(rest)?;
+                body.push(cmd);
+                input = rest;
+            }
+        }
+    }
+}
+
+/// Parser para as opções de uma política, na ordem TIMEOUT, RETRY e
+/// BACKOFF.
+fn policy(input: &str) -> PResult<'_, Policy> {
+    let (input, timeout_ms) = opt(preceded(tuple((space1, keyword("TIMEOUT"), space1)), cut(duration))).parse(input)?;
+    let (input, retries) = opt(preceded(tuple((space1, keyword("RETRY"), space1)), cut(count))).parse(input)?;
+    let (input, backoff_ms) = opt(preceded(tuple((space1, keyword("BACKOFF"), space1)), cut(duration))).parse(input)?;
+
+    Ok((
+        input,
+        Policy {
+            timeout_ms,
+            retries: retries.unwrap_or(0),
+            backoff_ms,
+        },
+    ))
+}
+
+/// Parser para uma duração (`500ms`, `30s`, `5m`, `1h`), em milissegundos.
+fn duration(input: &str) -> PResult<'_, u64> {
+    expect(
+        "duração",
+        map_res(
+            tuple((
+                digit1,
+                alt((
+                    value(1u64, tag("ms")),
+                    value(1_000, tag("s")),
+                    value(60_000, tag("m")),
+                    value(3_600_000, tag("h")),
+                )),
+            )),
+            |(amount, unit): (&str, u64)| {
+                amount
+                    .parse::<u64>()
+                    .ok()
+                    .and_then(|amount| amount.checked_mul(unit))
+                    .ok_or("duração fora do limite")
+            },
+        ),
+    )(input)
+}
+
+/// Parser para um bloco de comandos: `{ ... }` com um comando por linha,
+/// ou um único comando na mesma linha.
+fn block(input: &str) -> PResult<'_, Vec<Command>> {
+    alt((braced_block, map(command, |c| vec![c]))).parse(input)
+}
+
+/// Parser para um bloco entre chaves.
+fn braced_block(input: &str) -> PResult<'_, Vec<Command>> {
+    let (mut input, _) = char('{')(input)?;
+    let mut commands = Vec::new();
+
+    loop {
+        let (rest, _) = trivia(input)?;
+        let (rest, next) = alt((value(None, char('}')), map(command, Some))).parse(rest)?;
+        match next {
+            None => return Ok((rest, commands)),
+            Some(cmd) => {
+                let (rest, _) = block_statement_end(rest)?;
+                commands.push(cmd);
+                input = rest;
+            }
+        }
+    }
+}
+
+/// Final de uma instrução dentro de um bloco: quebra de linha ou `}`.
+fn block_statement_end(input: &str) -> PResult<'_, ()> {
+    let (input, _) = space0(input)?;
+    let (input, _) = opt(comment).parse(input)?;
+    alt((value((), line_ending), value((), peek(char('}'))))).parse(input)
+}
+
+/// Final de uma linha dentro de uma transação: a quebra de linha é
+/// obrigatória.
+fn block_line_end(input: &str) -> PResult<'_, ()> {
+    let (input, _) = space0(input)?;
+    let (input, _) = opt(comment).parse(input)?;
+    value((), line_ending).parse(input)
+}
+
+/// Parser para a condição de um IF.
+fn condition(input: &str) -> PResult<'_, Condition> {
+    alt((not_condition, exists_condition, compare_condition)).parse(input)
+}
+
+/// Parser para `NOT condição`.
+fn not_condition(input: &str) -> PResult<'_, Condition> {
+    let (input, _) = tuple((keyword("NOT"), space1)).parse(input)?;
+    let (input, inner) = condition(input)?;
+    Ok((input, Condition::Not(Box::new(inner))))
+}
+
+/// Parser para `EXISTS CONTRACT|IDEA|ENTITY id`.
+fn exists_condition(input: &str) -> PResult<'_, Condition> {
+    let (input, _) = tuple((keyword("EXISTS"), space1)).parse(input)?;
+    let (input, kind) = alt((
+        value(ItemKind::Contract, keyword("CONTRACT")),
+        value(ItemKind::Idea, keyword("IDEA")),
+        value(ItemKind::Entity, keyword("ENTITY")),
+    ))
+    .parse(input)?;
+    let (input, _) = space1(input)?;
+    let (input, id) = identifier(input)?;
+    Ok((input, Condition::Exists { kind, id: id.to_string() }))
+}
+
+/// Parser para `valor == valor` ou `valor != valor`.
+fn compare_condition(input: &str) -> PResult<'_, Condition> {
+    let (input, left) = value_expr(input)?;
+    let (input, _) = space0(input)?;
+    let (input, op) = alt((
+        value(CompareOp::Eq, keyword("==")),
+        value(CompareOp::Ne, keyword("!=")),
+    ))
+    .parse(input)?;
+    let (input, _) = space0(input)?;
+    let (input, right) = value_expr(input)?;
+    Ok((input, Condition::Compare { left, op, right }))
+}
+
+/// Parser para um valor: `VERDICT(ruleset ON entidade)`, texto entre
+/// aspas ou identificador.
+fn value_expr(input: &str) -> PResult<'_, Value> {
+    alt((
+        verdict,
+        map(quoted_string, Value::Text),
+        map(identifier, |id: &str| Value::Text(id.to_string())),
+    ))
+    .parse(input)
+}
+
+/// Parser para `VERDICT(ruleset ON entidade)`.
+fn verdict(input: &str) -> PResult<'_, Value> {
+    let (input, _) = tuple((keyword("VERDICT"), char('('), space0)).parse(input)?;
+    let (input, ruleset_id) = identifier(input)?;
+    let (input, _) = tuple((space1, keyword("ON"), space1)).parse(input)?;
+    let (input, entity_id) = identifier(input)?;
+    let (input, _) = tuple((space0, char(')'))).parse(input)?;
+    Ok((
+        input,
+        Value::Verdict {
+            ruleset_id: ruleset_id.to_string(),
+            entity_id: entity_id.to_string(),
+        },
+    ))
+}
+
+/// Parser para a origem dos itens de um FOR EACH.
+fn list_source(input: &str) -> PResult<'_, ListSource> {
+    alt((
+        preceded(
+            tuple((keyword("LIST"), space1)),
+            alt((
+                value(ListSource::Contracts, keyword("CONTRACTS")),
+                value(ListSource::Ideas, keyword("IDEAS")),
+                map(
+                    preceded(tuple((keyword("ENTITIES"), space1)), identifier),
+                    |t: &str| ListSource::Entities { entity_type: t.to_string() },
+                ),
+            )),
+        ),
+        map(
+            delimited(
+                tuple((char('['), multispace0)),
+                separated_list0(tuple((multispace0, char(','), multispace0)), value_expr),
+                tuple((multispace0, char(']'))),
+            ),
+            ListSource::Items,
+        ),
+    ))
+    .parse(input)
+}
+
+/// Parser para a instrução LET, que associa um valor a uma variável.
+fn let_binding(input: &str) -> PResult<'_, Command> {
+    let (input, _) = tuple((keyword("LET"), space1)).parse(input)?;
+    let (input, name) = variable_name(input)?;
+    let (input, _) = tuple((space0, char('='), space0)).parse(input)?;
+    let (input, value) = alt((quoted_string, map(identifier, String::from))).parse(input)?;
+
+    Ok((
+        input,
+        Command::Let {
+            name: name.to_string(),
+            value,
+        },
+    ))
 }
 
 /// Parser para um comando imperativo.
-fn imperative(input: &str) -> IResult<&str, Imperative> {
+fn imperative(input: &str) -> PResult<'_, Imperative> {
     alt((
         define_contract,
         define_idea,
         simulate_entity,
         orchestrate,
         invoke_ruleset,
+        show_contract,
+        show_simulation,
+        list_ideas,
+        find_spans,
+        amend_contract,
+        tag_idea,
+        update_idea,
+        retire_entity,
+        revert,
     ))
     .parse(input)
 }
 
 /// Parser para o comando DEFINE CONTRACT.
-fn define_contract(input: &str) -> IResult<&str, Imperative> {
+fn define_contract(input: &str) -> PResult<'_, Imperative> {
     // Parse the DEFINE CONTRACT prefix
-    let (input, _) = tuple((tag("DEFINE"), multispace1, tag("CONTRACT"), multispace1)).parse(input)?;
+    let (input, _) = tuple((keyword("DEFINE"), space1, keyword("CONTRACT"), space1)).parse(input)?;
     // Parse the contract identifier
     let (input, id) = identifier(input)?;
-    let (input, _) = multispace1(input)?;
-    // Parse the list of clauses separated by commas
-    let (input, clauses) = separated_list0(
-        tuple((multispace0, char(','), multispace0)),
-        clause,
-    )
-    .parse(input)?;
+    let (input, clauses) = contract_clauses(input)?;
     
     Ok((
         input,
         Imperative {
             kind: ImperativeKind::DefineContract {
                 id: id.to_string(),
-                clauses: clauses.into_iter().map(String::from).collect(),
+                clauses,
             },
         },
     ))
 }
 
+/// Parser para a lista opcional de cláusulas de um contrato, separadas
+/// por vírgulas.
+fn contract_clauses(input: &str) -> PResult<'_, Vec<String>> {
+    let (input, clauses) = opt(preceded(
+        space1,
+        separated_list0(
+            tuple((multispace0, char(','), multispace0)),
+            clause,
+        ),
+    ))
+    .parse(input)?;
+
+    Ok((input, clauses.unwrap_or_default().into_iter().flatten().collect()))
+}
+
 /// Parser para o comando DEFINE IDEA.
-fn define_idea(input: &str) -> IResult<&str, Imperative> {
-    let (input, _) = tuple((tag("DEFINE"), multispace1, tag("IDEA"), multispace1)).parse(input)?;
+fn define_idea(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("DEFINE"), space1, keyword("IDEA"), space1)).parse(input)?;
     let (input, id) = identifier(input)?;
-    let (input, _) = multispace1(input)?;
+    let (input, _) = space1(input)?;
     let (input, text) = quoted_string(input)?;
     
     Ok((
@@ -96,17 +725,17 @@ fn define_idea(input: &str) -> IResult<&str, Imperative> {
         Imperative {
             kind: ImperativeKind::DefineIdea {
                 id: id.to_string(),
-                text: text.to_string(),
+                text,
             },
         },
     ))
 }
 
 /// Parser para o comando SIMULATE ENTITY.
-fn simulate_entity(input: &str) -> IResult<&str, Imperative> {
-    let (input, _) = tuple((tag("SIMULATE"), multispace1, tag("ENTITY"), multispace1)).parse(input)?;
+fn simulate_entity(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("SIMULATE"), space1, keyword("ENTITY"), space1)).parse(input)?;
     let (input, id) = identifier(input)?;
-    let (input, _) = multispace1(input)?;
+    let (input, _) = space1(input)?;
     let (input, rounds) = map_res(digit1, |s: &str| s.parse::<usize>()).parse(input)?;
     
     Ok((
@@ -120,26 +749,74 @@ fn simulate_entity(input: &str) -> IResult<&str, Imperative> {
     ))
 }
 
-/// Parser para o comando ORCHESTRATE.
-fn orchestrate(input: &str) -> IResult<&str, Imperative> {
-    let (input, _) = tuple((tag("ORCHESTRATE"), multispace1)).parse(input)?;
+/// Parser para o comando ORCHESTRATE, com concorrência e bloco de passos
+/// opcionais.
+fn orchestrate(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("ORCHESTRATE"), space1)).parse(input)?;
     let (input, mode) = identifier(input)?;
+    let (input, concurrency) = opt(preceded(space1, count)).parse(input)?;
+    // Depois de `{`, um erro no bloco não deve ser confundido com o fim do comando
+    let (input, steps) = opt(preceded(tuple((space1, peek(char('{')))), cut(step_block))).parse(input)?;
     
     Ok((
         input,
         Imperative {
             kind: ImperativeKind::Orchestrate {
                 mode: mode.to_string(),
+                concurrency,
+                steps: steps.unwrap_or_default(),
             },
         },
     ))
 }
 
+/// Parser para o bloco de passos de uma orquestração, um por linha.
+fn step_block(input: &str) -> PResult<'_, Vec<Step>> {
+    let (mut input, _) = char('{')(input)?;
+    let mut steps = Vec::new();
+
+    loop {
+        let (rest, _) = trivia(input)?;
+        let (rest, next) = alt((value(None, char('}')), map(step, Some))).parse(rest)?;
+        match next {
+            None => return Ok((rest, steps)),
+            Some(step) => {
+                let (rest, _) = block_statement_end(rest)?;
+                steps.push(step);
+                input = rest;
+            }
+        }
+    }
+}
+
+/// Parser para um passo: `[STEP nome [DEPENDS ON a, b]:] comando`.
+fn step(input: &str) -> PResult<'_, Step> {
+    let (input, header) = opt(tuple((
+        preceded(tuple((keyword("STEP"), space1)), identifier),
+        opt(preceded(
+            tuple((space1, keyword("DEPENDS"), space1, keyword("ON"), space1)),
+            separated_list1(tuple((space0, char(','), space0)), identifier),
+        )),
+        tuple((space0, char(':'), space0)),
+    )))
+    .parse(input)?;
+    let (input, imperative) = imperative(input)?;
+
+    let (name, depends_on) = match header {
+        Some((name, depends_on, _)) => (
+            Some(name.to_string()),
+            depends_on.unwrap_or_default().into_iter().map(str::to_string).collect(),
+        ),
+        None => (None, Vec::new()),
+    };
+    Ok((input, Step { name, depends_on, imperative }))
+}
+
 /// Parser para o comando INVOKE RULESET.
-fn invoke_ruleset(input: &str) -> IResult<&str, Imperative> {
-    let (input, _) = tuple((tag("INVOKE"), multispace1, tag("RULESET"), multispace1)).parse(input)?;
+fn invoke_ruleset(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("INVOKE"), space1, keyword("RULESET"), space1)).parse(input)?;
     let (input, ruleset_id) = identifier(input)?;
-    let (input, _) = tuple((multispace1, tag("ON"), multispace1)).parse(input)?;
+    let (input, _) = tuple((space1, keyword("ON"), space1)).parse(input)?;
     let (input, entity_id) = identifier(input)?;
     
     Ok((
@@ -153,23 +830,239 @@ fn invoke_ruleset(input: &str) -> IResult<&str, Imperative> {
     ))
 }
 
+/// Parser para a consulta SHOW CONTRACT.
+fn show_contract(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("SHOW"), space1, keyword("CONTRACT"), space1)).parse(input)?;
+    let (input, id) = identifier(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::ShowContract { id: id.to_string() },
+        },
+    ))
+}
+
+/// Parser para a consulta SHOW SIMULATION, com `LAST n` opcional.
+fn show_simulation(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("SHOW"), space1, keyword("SIMULATION"), space1)).parse(input)?;
+    let (input, entity_id) = identifier(input)?;
+    let (input, last) = opt(preceded(tuple((space1, keyword("LAST"), space1)), cut(count))).parse(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::ShowSimulation {
+                entity_id: entity_id.to_string(),
+                last,
+            },
+        },
+    ))
+}
+
+/// Parser para a consulta LIST IDEAS, com `WHERE TAG = x` opcional.
+fn list_ideas(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("LIST"), space1, keyword("IDEAS"))).parse(input)?;
+    let (input, tag) = opt(preceded(
+        tuple((space1, keyword("WHERE"), space1, keyword("TAG"))),
+        cut(filter_value),
+    ))
+    .parse(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::ListIdeas { tag },
+        },
+    ))
+}
+
+/// Parser para a consulta FIND SPANS, com `KIND = x` e `LIMIT n` opcionais.
+fn find_spans(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("FIND"), space1, keyword("SPANS"))).parse(input)?;
+    let (input, kind) = opt(preceded(tuple((space1, keyword("KIND"))), cut(filter_value))).parse(input)?;
+    let (input, limit) = opt(preceded(tuple((space1, keyword("LIMIT"), space1)), cut(count))).parse(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::FindSpans { kind, limit },
+        },
+    ))
+}
+
+/// Parser para o comando AMEND CONTRACT, que substitui as cláusulas.
+fn amend_contract(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("AMEND"), space1, keyword("CONTRACT"), space1)).parse(input)?;
+    let (input, id) = identifier(input)?;
+    let (input, clauses) = contract_clauses(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::AmendContract {
+                id: id.to_string(),
+                clauses,
+            },
+        },
+    ))
+}
+
+/// Parser para o comando TAG IDEA, com uma ou mais tags separadas por
+/// vírgulas.
+fn tag_idea(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("TAG"), space1, keyword("IDEA"), space1)).parse(input)?;
+    let (input, id) = identifier(input)?;
+    let (input, _) = space1(input)?;
+    let (input, tags) = separated_list1(tuple((space0, char(','), space0)), text_value).parse(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::TagIdea {
+                id: id.to_string(),
+                tags,
+            },
+        },
+    ))
+}
+
+/// Parser para o comando UPDATE IDEA.
+fn update_idea(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("UPDATE"), space1, keyword("IDEA"), space1)).parse(input)?;
+    let (input, id) = identifier(input)?;
+    let (input, _) = space1(input)?;
+    let (input, text) = quoted_string(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::UpdateIdea {
+                id: id.to_string(),
+                text,
+            },
+        },
+    ))
+}
+
+/// Parser para o comando RETIRE ENTITY.
+fn retire_entity(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("RETIRE"), space1, keyword("ENTITY"), space1)).parse(input)?;
+    let (input, id) = identifier(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::RetireEntity { id: id.to_string() },
+        },
+    ))
+}
+
+/// Parser para o comando REVERT.
+fn revert(input: &str) -> PResult<'_, Imperative> {
+    let (input, _) = tuple((keyword("REVERT"), space1)).parse(input)?;
+    let (input, event_id) = identifier(input)?;
+
+    Ok((
+        input,
+        Imperative {
+            kind: ImperativeKind::Revert { event_id: event_id.to_string() },
+        },
+    ))
+}
+
+/// Parser para o valor de um filtro: `= valor`, entre aspas ou não.
+fn filter_value(input: &str) -> PResult<'_, String> {
+    let (input, _) = tuple((space0, char('='), space0)).parse(input)?;
+    text_value(input)
+}
+
+/// Parser para um texto entre aspas ou um identificador.
+fn text_value(input: &str) -> PResult<'_, String> {
+    alt((quoted_string, map(identifier, String::from))).parse(input)
+}
+
+/// Parser para uma quantidade inteira não negativa.
+fn count(input: &str) -> PResult<'_, usize> {
+    map_res(digit1, |s: &str| s.parse::<usize>()).parse(input)
+}
+
 /// Parser para uma string entre aspas duplas.
-fn quoted_string(input: &str) -> IResult<&str, &str> {
-    delimited(
-        char('"'),
-        take_till(|c| c == '"'),
-        char('"'),
-    )
+///
+/// Aceita as sequências de escape `\\`, `\"`, `\n`, `\r` e `\t`.
+pub(crate) fn quoted_string(input: &str) -> PResult<'_, String> {
+    let (mut rest, _) = char('"')(input)?;
+    let mut text = String::new();
+
+    loop {
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some('"') => return Ok((&rest[1..], text)),
+            Some('\\') => match chars.next() {
+                Some(escaped) => {
+                    text.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        other => other,
+                    });
+                    rest = &rest[1 + escaped.len_utf8()..];
+                }
+                None => return Err(Err::Error(Failure::from_char(&rest[1..], '"'))),
+            },
+            Some(c) => {
+                text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+            None => return Err(Err::Error(Failure::from_char(rest, '"'))),
+        }
+    }
+}
+
+/// Parser para uma cláusula de contrato: uma string entre aspas ou um
+/// trecho sem aspas. Cláusulas sem aspas vazias são descartadas.
+fn clause(input: &str) -> PResult<'_, Option<String>> {
+    alt((
+        map(quoted_string, Some),
+        map(bare_clause, |c: &str| Some(c.trim().to_string()).filter(|c| !c.is_empty())),
+    ))
     .parse(input)
 }
 
-/// Parser para uma cláusula de contrato.
-fn clause(input: &str) -> IResult<&str, &str> {
-    take_while(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == ' ' || c == '.' || c == ':'
-        || c == '(' || c == ')' || c == '[' || c == ']' || c == '$' || c == '%' || c == '@')(input)
+/// Parser para uma cláusula de contrato sem aspas.
+fn bare_clause(input: &str) -> PResult<'_, &str> {
+    recognize(many0(alt((
+        interpolation,
+        take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == ' ' || c == '.' || c == ':'
+            || c == '(' || c == ')' || c == '[' || c == ']' || c == '%' || c == '@'),
+        terminated(tag("$"), not(char('{'))),
+    ))))(input)
+}
+
+/// Parser para um identificador, que pode conter interpolações `${nome}`.
+fn identifier(input: &str) -> PResult<'_, &str> {
+    expect(
+        "identificador",
+        recognize(many1(alt((
+            take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '@'),
+            interpolation,
+        )))),
+    )(input)
+}
+
+/// Parser para uma interpolação de variável: `${nome}`.
+fn interpolation(input: &str) -> PResult<'_, &str> {
+    recognize(tuple((tag("${"), variable_name, char('}')))).parse(input)
 }
 
-/// Parser para um identificador.
-fn identifier(input: &str) -> IResult<&str, &str> {
-    take_while(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '@')(input)
+/// Parser para um nome de variável: letra ou `_`, seguido de letras,
+/// dígitos ou `_`.
+fn variable_name(input: &str) -> PResult<'_, &str> {
+    expect(
+        "nome de variável",
+        recognize(tuple((
+            take_while_m_n(1, 1, |c: char| c.is_ascii_alphabetic() || c == '_'),
+            take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+        ))),
+    )(input)
 }
\ No newline at end of file
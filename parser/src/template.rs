@@ -0,0 +1,69 @@
+// === File: parser/src/template.rs ===
+/*
+    Description: Análise de interpolações `${nome}` em textos e identificadores LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+/// Trecho de um texto com interpolação.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a> {
+    /// Texto literal.
+    Literal(&'a str),
+    /// Referência a uma variável (`${nome}`), sem os delimitadores.
+    Variable(&'a str),
+}
+
+/// Divide um texto nos seus trechos literais e nas referências `${nome}`.
+///
+/// `$${` é o escape de um `${` literal: `"$${nome}"` resulta no texto
+/// `${nome}`, sem interpolação.
+///
+/// # Retorno
+///
+/// Os trechos em ordem, ou uma mensagem de erro se houver uma interpolação
+/// sem `}` ou com nome de variável inválido.
+pub fn segments(text: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            segments.push(Segment::Literal(&rest[..start - 1]));
+            segments.push(Segment::Literal("${"));
+            rest = &rest[start + 2..];
+            continue;
+        }
+        if start > 0 {
+            segments.push(Segment::Literal(&rest[..start]));
+        }
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("Interpolação sem '}}' em '{}'", text))?;
+        let name = &after[..end];
+        if !is_variable_name(name) {
+            return Err(format!("Nome de variável inválido '{}' em '{}'", name, text));
+        }
+        segments.push(Segment::Variable(name));
+        rest = &after[end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+
+    Ok(segments)
+}
+
+/// Indica se `name` é um nome de variável válido: letra ou `_`, seguido de
+/// letras, dígitos ou `_`.
+pub fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,259 @@
+// === File: parser/tests/format_tests.rs ===
+/*
+    Description: Testes do formatador canônico e da propriedade de ida e volta do parser LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use parser::{parse, parse_program, parse_with_recovery, format_command, format_program, format_source, ast::{Command, CompareOp, Condition, Imperative, ImperativeKind, ItemKind, ListSource, Policy, Step, Value}};
+use proptest::prelude::*;
+
+fn identifier() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9_][a-zA-Z0-9_.@-]{0,15}(\\$\\{[a-z_][a-z0-9_]{0,8}\\})?"
+}
+
+fn imperative_kind() -> impl Strategy<Value = ImperativeKind> {
+    prop_oneof![
+        (identifier(), prop::collection::vec(any::<String>(), 0..4))
+            .prop_map(|(id, clauses)| ImperativeKind::DefineContract { id, clauses }),
+        (identifier(), any::<String>())
+            .prop_map(|(id, text)| ImperativeKind::DefineIdea { id, text }),
+        (identifier(), any::<usize>())
+            .prop_map(|(id, rounds)| ImperativeKind::SimulateEntity { id, rounds }),
+        identifier().prop_map(|mode| ImperativeKind::Orchestrate { mode, concurrency: None, steps: Vec::new() }),
+        (identifier(), identifier())
+            .prop_map(|(entity_id, ruleset_id)| ImperativeKind::InvokeRuleset { entity_id, ruleset_id }),
+        (identifier(), prop::collection::vec(any::<String>(), 0..4))
+            .prop_map(|(id, clauses)| ImperativeKind::AmendContract { id, clauses }),
+        (identifier(), prop::collection::vec(any::<String>(), 1..4))
+            .prop_map(|(id, tags)| ImperativeKind::TagIdea { id, tags }),
+        (identifier(), any::<String>())
+            .prop_map(|(id, text)| ImperativeKind::UpdateIdea { id, text }),
+        identifier().prop_map(|id| ImperativeKind::RetireEntity { id }),
+        identifier().prop_map(|event_id| ImperativeKind::Revert { event_id }),
+        identifier().prop_map(|id| ImperativeKind::ShowContract { id }),
+        prop::option::of(any::<String>()).prop_map(|tag| ImperativeKind::ListIdeas { tag }),
+        (prop::option::of(any::<String>()), prop::option::of(any::<usize>()))
+            .prop_map(|(kind, limit)| ImperativeKind::FindSpans { kind, limit }),
+        (identifier(), prop::option::of(any::<usize>()))
+            .prop_map(|(entity_id, last)| ImperativeKind::ShowSimulation { entity_id, last }),
+    ]
+}
+
+fn orchestrate() -> impl Strategy<Value = ImperativeKind> {
+    let step = (prop::option::of((identifier(), prop::collection::vec(identifier(), 0..3))), imperative_kind())
+        .prop_map(|(header, kind)| {
+            let (name, depends_on) = header.map_or((None, Vec::new()), |(name, deps)| (Some(name), deps));
+            Step { name, depends_on, imperative: Imperative { kind } }
+        });
+    (identifier(), prop::option::of(any::<usize>()), prop::collection::vec(step, 0..4))
+        .prop_map(|(mode, concurrency, steps)| ImperativeKind::Orchestrate { mode, concurrency, steps })
+}
+
+fn variable() -> impl Strategy<Value = String> {
+    "[a-zA-Z_][a-zA-Z0-9_]{0,8}"
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    prop_oneof![
+        any::<String>().prop_map(Value::Text),
+        identifier().prop_map(Value::Text),
+        (identifier(), identifier())
+            .prop_map(|(ruleset_id, entity_id)| Value::Verdict { ruleset_id, entity_id }),
+    ]
+}
+
+fn condition() -> impl Strategy<Value = Condition> {
+    let leaf = prop_oneof![
+        (value(), prop_oneof![Just(CompareOp::Eq), Just(CompareOp::Ne)], value())
+            .prop_map(|(left, op, right)| Condition::Compare { left, op, right }),
+        (prop_oneof![Just(ItemKind::Contract), Just(ItemKind::Idea), Just(ItemKind::Entity)], identifier())
+            .prop_map(|(kind, id)| Condition::Exists { kind, id }),
+    ];
+    leaf.prop_recursive(2, 4, 1, |inner| inner.prop_map(|c| Condition::Not(Box::new(c))))
+}
+
+fn list_source() -> impl Strategy<Value = ListSource> {
+    prop_oneof![
+        Just(ListSource::Contracts),
+        Just(ListSource::Ideas),
+        identifier().prop_map(|entity_type| ListSource::Entities { entity_type }),
+        prop::collection::vec(value(), 0..4).prop_map(ListSource::Items),
+    ]
+}
+
+fn policy() -> impl Strategy<Value = Policy> {
+    (prop::option::of(any::<u64>()), any::<usize>(), prop::option::of(any::<u64>()))
+        .prop_map(|(timeout_ms, retries, backoff_ms)| Policy { timeout_ms, retries, backoff_ms })
+}
+
+fn command() -> impl Strategy<Value = Command> {
+    let leaf = prop_oneof![
+        4 => imperative_kind().prop_map(|kind| Command::Imperative(Imperative { kind })),
+        1 => orchestrate().prop_map(|kind| Command::Imperative(Imperative { kind })),
+        1 => (variable(), any::<String>())
+            .prop_map(|(name, value)| Command::Let { name, value }),
+    ];
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        let block = prop::collection::vec(inner, 0..4);
+        prop_oneof![
+            (condition(), block.clone(), prop::option::of(block.clone()))
+                .prop_map(|(condition, then_branch, else_branch)| Command::If { condition, then_branch, else_branch }),
+            (variable(), list_source(), block.clone())
+                .prop_map(|(variable, source, body)| Command::ForEach { variable, source, body }),
+            (policy(), block.clone(), prop::option::of(block.clone()))
+                .prop_map(|(policy, body, on_failure)| Command::With { policy, body, on_failure }),
+            block.prop_map(|body| Command::Transaction { body }),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn prop_format_then_parse_is_identity(cmd in command()) {
+        let formatted = format_command(&cmd);
+        let parsed = parse(&formatted);
+        prop_assert!(parsed.is_ok(), "Forma canônica não pôde ser lida: {}", formatted);
+        prop_assert_eq!(parsed.unwrap(), cmd);
+    }
+
+    #[test]
+    fn prop_format_program_round_trips(cmds in prop::collection::vec(command(), 0..8)) {
+        let formatted = format_program(&cmds);
+        let parsed = parse_program(&formatted);
+        prop_assert!(parsed.is_ok(), "Programa formatado não pôde ser lido: {}", formatted);
+        prop_assert_eq!(parsed.unwrap(), cmds);
+    }
+
+    #[test]
+    fn prop_format_source_is_idempotent(cmds in prop::collection::vec(command(), 0..8)) {
+        let once = format_source(&format_program(&cmds)).unwrap();
+        let twice = format_source(&once).unwrap();
+        prop_assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn prop_recovery_keeps_valid_commands(
+        cmds in prop::collection::vec(command(), 1..6),
+        garbage in "\\?[a-z \"]{0,12}",
+        at in any::<prop::sample::Index>(),
+    ) {
+        // Uma linha inválida no meio do programa não afeta as demais
+        let mut lines: Vec<_> = cmds.iter().map(format_command).collect();
+        let position = at.index(lines.len() + 1);
+        lines.insert(position, garbage);
+        let recovered = parse_with_recovery(&lines.join("\n"));
+
+        prop_assert_eq!(recovered.diagnostics.len(), 1);
+        prop_assert_eq!(recovered.statements().map(|s| s.command.clone()).collect::<Vec<_>>(), cmds);
+    }
+}
+
+#[test]
+fn test_contract_clauses_with_commas_round_trip() {
+    let cmd = Command::Imperative(Imperative {
+        kind: ImperativeKind::DefineContract {
+            id: "contract-1".to_string(),
+            clauses: vec!["Pagamento em 30, 60 e 90 dias".to_string(), "Foro: \"Lisboa\"".to_string()],
+        },
+    });
+
+    let formatted = format_command(&cmd);
+    assert_eq!(
+        formatted,
+        "DEFINE CONTRACT contract-1 \"Pagamento em 30, 60 e 90 dias\", \"Foro: \\\"Lisboa\\\"\"",
+        "Forma canônica incorreta"
+    );
+    assert_eq!(parse(&formatted).unwrap(), cmd, "Ida e volta falhou");
+}
+
+#[test]
+fn test_format_source_keeps_comments_and_collapses_blank_lines() {
+    let input = "\n\n// Cabeçalho\n   DEFINE   CONTRACT c-1 a ,b   # cláusulas\n\n\n\nSIMULATE  ENTITY c-1   3\n\n";
+    let formatted = format_source(input).expect("Formatação falhou");
+
+    let expected = "// Cabeçalho\n\
+                    DEFINE CONTRACT c-1 \"a\", \"b\" # cláusulas\n\
+                    \n\
+                    SIMULATE ENTITY c-1 3\n";
+    assert_eq!(formatted, expected, "Script formatado incorreto");
+}
+
+#[test]
+fn test_format_source_reports_syntax_error() {
+    let result = format_source("ORCHESTRATE parallel\nDEFINE IDEA sem-texto\n");
+
+    assert!(result.is_err(), "Formatação deveria falhar para script inválido");
+    assert_eq!(result.unwrap_err().syntax().line, 2, "Erro deveria apontar a linha 2");
+}
+
+
+#[test]
+fn test_format_control_flow_blocks() {
+    let input = "FOR EACH id IN LIST CONTRACTS DO IF VERDICT(basic-check ON ${id})==Accepted THEN SIMULATE ENTITY ${id} 3";
+    let formatted = format_source(input).expect("Formatação falhou");
+
+    let expected = "FOR EACH id IN LIST CONTRACTS DO {\n\
+                    \x20   IF VERDICT(basic-check ON ${id}) == Accepted THEN {\n\
+                    \x20       SIMULATE ENTITY ${id} 3\n\
+                    \x20   }\n\
+                    }\n";
+    assert_eq!(formatted, expected, "Blocos formatados incorretamente");
+}
+
+#[test]
+fn test_format_source_keeps_comments_inside_blocks() {
+    let input = "IF EXISTS IDEA i-1 THEN {\n  // só se existir\n  SIMULATE ENTITY i-1 2\n}\n";
+    let formatted = format_source(input).expect("Formatação falhou");
+
+    assert_eq!(formatted, input, "Comentários dentro de blocos deveriam ser preservados");
+}
+
+#[test]
+fn test_format_orchestration_steps() {
+    let input = "IF EXISTS IDEA i THEN ORCHESTRATE dag 2 {\nSTEP a : DEFINE IDEA i2 \"x\"\n  STEP b DEPENDS ON a,c:SIMULATE ENTITY e 1\nSIMULATE ENTITY e 2 }";
+    let formatted = format_source(input).expect("Formatação falhou");
+
+    let expected = "IF EXISTS IDEA i THEN {\n\
+                    \x20   ORCHESTRATE dag 2 {\n\
+                    \x20       STEP a: DEFINE IDEA i2 \"x\"\n\
+                    \x20       STEP b DEPENDS ON a, c: SIMULATE ENTITY e 1\n\
+                    \x20       SIMULATE ENTITY e 2\n\
+                    \x20   }\n\
+                    }\n";
+    assert_eq!(formatted, expected, "Passos formatados incorretamente");
+}
+
+#[test]
+fn test_format_policy_durations() {
+    let input = "WITH  TIMEOUT 90000ms RETRY 0 BACKOFF 1500ms DO SIMULATE ENTITY e 1 ON FAILURE ORCHESTRATE x";
+    let formatted = format_source(input).expect("Formatação falhou");
+
+    let expected = "WITH TIMEOUT 90s BACKOFF 1500ms DO {\n\
+                    \x20   SIMULATE ENTITY e 1\n\
+                    } ON FAILURE {\n\
+                    \x20   ORCHESTRATE x\n\
+                    }\n";
+    assert_eq!(formatted, expected, "Política formatada incorretamente");
+}
+
+#[test]
+fn test_format_transaction() {
+    let input = "IF EXISTS IDEA i THEN {\nBEGIN\n  TAG IDEA i a\n\n   UPDATE IDEA i \"x\"\nCOMMIT\n}\nBEGIN\nCOMMIT";
+    let formatted = format_source(input).expect("Formatação falhou");
+
+    let expected = "IF EXISTS IDEA i THEN {\n\
+                    \x20   BEGIN\n\
+                    \x20       TAG IDEA i a\n\
+                    \x20       UPDATE IDEA i \"x\"\n\
+                    \x20   COMMIT\n\
+                    }\n\
+                    BEGIN\n\
+                    COMMIT\n";
+    assert_eq!(formatted, expected, "Transação formatada incorretamente");
+}
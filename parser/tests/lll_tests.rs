@@ -0,0 +1,130 @@
+// === File: parser/tests/lll_tests.rs ===
+/*
+    Description: Testes do parser de contratos .lll contra os arquivos do lllpack.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use parser::lll::{self, Member, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lê um arquivo do lllpack, relativo à raiz do pacote.
+fn pack_file(relative: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../lllpack").join(relative);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("Falha ao ler {}: {}", path.display(), e))
+}
+
+/// Lista recursivamente os arquivos `.lll` de um diretório.
+fn lll_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).expect("Falha ao listar diretório") {
+        let path = entry.expect("Entrada de diretório inválida").path();
+        if path.is_dir() {
+            lll_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "lll") {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn test_parse_every_lllpack_file() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../lllpack");
+    let mut files = Vec::new();
+    for dir in ["contracts", "agents", "spans", "triggers"] {
+        lll_files(&root.join(dir), &mut files);
+    }
+    assert!(files.len() >= 11, "Arquivos .lll do lllpack não encontrados");
+
+    for path in files {
+        let source = fs::read_to_string(&path).expect("Falha ao ler arquivo");
+        if let Err(e) = lll::parse(&source) {
+            panic!("{}", e.syntax().render(&path.display().to_string()));
+        }
+    }
+}
+
+#[test]
+fn test_contract_metadata_and_sections() {
+    let contract = lll::parse(&pack_file("contracts/regras_tenant.lll")).expect("Parsing falhou");
+
+    assert_eq!(contract.name, "RegrasTenant", "Nome do contrato incorreto");
+    assert_eq!(contract.version.as_deref(), Some("1.0.0"), "Versão incorreta");
+    assert!(!contract.tags.is_empty(), "Tags não foram lidas");
+
+    // Seções rotuladas como `clause 1.1 { ... }`
+    let article = contract.section("article").expect("Seção article não encontrada");
+    assert_eq!(article.name.as_deref(), Some("IdentidadeSoberania"), "Rótulo da seção incorreto");
+    match &article.body[0] {
+        Member::Section(clause) => {
+            assert_eq!(clause.kind, "clause", "Tipo da seção incorreto");
+            assert_eq!(clause.name.as_deref(), Some("1.1"), "Rótulo numérico incorreto");
+            assert!(clause.get("text").and_then(Value::as_str).is_some(), "Texto da cláusula ausente");
+        }
+        other => panic!("Membro inesperado: {:?}", other),
+    }
+}
+
+#[test]
+fn test_spans_dependencies_and_outcomes() {
+    let contract = lll::parse(&pack_file("spans/boot_sequence.lll")).expect("Parsing falhou");
+    let spans = contract.spans();
+
+    let boot = spans.iter().find(|s| s.name == "BootInstitution").expect("Span BootInstitution ausente");
+    assert_eq!(boot.span_type.as_deref(), Some("boot_institutional"), "Tipo do span incorreto");
+    assert_eq!(boot.children.len(), 6, "Número de filhos incorreto");
+    assert_eq!(boot.fields[0].name, "lllpack_name", "Nome do campo incorreto");
+
+    let load = spans.iter().find(|s| s.name == "LoadContracts").expect("Span LoadContracts ausente");
+    assert_eq!(load.depends_on, vec!["InitializeTimeline".to_string()], "Dependências incorretas");
+    assert_eq!(
+        load.on_failure.as_ref().and_then(|o| o.span.as_deref()),
+        Some("ContractLoadFailure"),
+        "on_failure deveria apontar para o span de recuperação"
+    );
+    assert_eq!(
+        load.on_success.as_ref().map(|o| o.fields.len()),
+        Some(2),
+        "on_success deveria ter 2 campos"
+    );
+    assert!(load.attribute("timeouts").is_some(), "Atributo timeouts ausente");
+}
+
+#[test]
+fn test_nested_spans_and_trigger_config() {
+    // Spans dentro de seções também são listados
+    let constitution = lll::parse(&pack_file("contracts/constituicao.lll")).expect("Parsing falhou");
+    let names: Vec<_> = constitution.spans().iter().map(|s| s.name.clone()).collect();
+    assert_eq!(names, vec!["ConstitutionalSignature".to_string()], "Span aninhado não encontrado");
+
+    let cron = lll::parse(&pack_file("triggers/cron.daily.lll")).expect("Parsing falhou");
+    let config = cron.section("trigger_config").expect("Seção trigger_config ausente");
+    assert_eq!(config.get("schedule").and_then(Value::as_str), Some("0 0 * * *"), "Agendamento incorreto");
+    assert_eq!(config.get("max_retries"), Some(&Value::Number(3.0)), "max_retries incorreto");
+}
+
+#[test]
+fn test_text_block_is_dedented() {
+    let source = "contract C {\n  prompt {\n    text: \"\"\"\n      linha 1\n        linha 2\n      \"\"\"\n  }\n}\n";
+    let contract = lll::parse(source).expect("Parsing falhou");
+
+    let text = contract.section("prompt").and_then(|s| s.get("text")).and_then(Value::as_str);
+    assert_eq!(text, Some("linha 1\n  linha 2"), "Indentação comum deveria ser removida");
+}
+
+#[test]
+fn test_parse_error_location() {
+    let source = "contract C {\n  span S {\n    depends_on: \"A\"\n  }\n}\n";
+    let err = lll::parse(source).expect_err("depends_on deveria exigir uma lista");
+    assert_eq!((err.syntax().line, err.syntax().column), (3, 5), "Posição do erro incorreta");
+    assert_eq!(err.syntax().expected, vec!["lista de strings".to_string()], "Token esperado incorreto");
+
+    let err = lll::parse("contract C {\n  title: \"x\"\n  secao {\n    chave:\n  }\n}").expect_err("Valor ausente");
+    assert_eq!(err.syntax().line, 5, "Erro deveria apontar para a linha 5");
+    assert!(err.syntax().expected.contains(&"valor".to_string()), "Deveria esperar um valor");
+}
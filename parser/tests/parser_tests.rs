@@ -10,7 +10,7 @@
     Contact: dan@danvoulez.com
 */
 
-use parser::{parse, ast::{Command, Imperative, ImperativeKind}};
+use parser::{parse, parse_program, parse_statements, parse_with_recovery, ast::{Command, CompareOp, Condition, Imperative, ImperativeKind, ItemKind, ListSource, Node, Policy, Step, Value}};
 
 #[test]
 fn test_parse_define_contract() {
@@ -66,13 +66,42 @@ fn test_parse_orchestrate() {
     
     assert!(result.is_ok(), "Parsing falhou para input válido");
     
-    if let Ok(Command::Imperative(Imperative { kind: ImperativeKind::Orchestrate { mode } })) = result {
+    if let Ok(Command::Imperative(Imperative { kind: ImperativeKind::Orchestrate { mode, concurrency, steps } })) = result {
         assert_eq!(mode, "parallel", "Modo de orquestração incorreto");
+        assert_eq!(concurrency, None, "Concorrência não informada");
+        assert!(steps.is_empty(), "Orquestração sem bloco não tem passos");
     } else {
         panic!("Tipo de comando incorreto após parsing");
     }
 }
 
+#[test]
+fn test_parse_orchestrate_steps() {
+    let input = "ORCHESTRATE dag 2 {\n    STEP init: DEFINE IDEA i \"x\"\n    STEP check DEPENDS ON init, other: INVOKE RULESET r ON i\n    SIMULATE ENTITY i 1 // sem nome\n}";
+    let result = parse(input).expect("Parsing falhou para orquestração com passos");
+
+    let Command::Imperative(Imperative { kind: ImperativeKind::Orchestrate { mode, concurrency, steps } }) = result else {
+        panic!("Tipo de comando incorreto após parsing");
+    };
+    assert_eq!(mode, "dag", "Modo de orquestração incorreto");
+    assert_eq!(concurrency, Some(2), "Concorrência incorreta");
+    assert_eq!(steps.len(), 3, "Número de passos incorreto");
+    assert_eq!(steps[0].name.as_deref(), Some("init"), "Nome do passo incorreto");
+    assert_eq!(steps[1].depends_on, vec!["init".to_string(), "other".to_string()], "Dependências incorretas");
+    assert_eq!(
+        steps[2],
+        Step {
+            name: None,
+            depends_on: Vec::new(),
+            imperative: Imperative { kind: ImperativeKind::SimulateEntity { id: "i".to_string(), rounds: 1 } },
+        },
+        "Passo sem nome incorreto"
+    );
+
+    assert!(parse("ORCHESTRATE dag {\n    STEP a DEPENDS ON: SIMULATE ENTITY e 1\n}").is_err(), "Dependências vazias deveriam falhar");
+    assert!(parse("ORCHESTRATE dag {\n    SIMULATE ENTITY e 1").is_err(), "Bloco sem '}}' deveria falhar");
+}
+
 #[test]
 fn test_parse_invoke_ruleset() {
     let input = "INVOKE RULESET basic-check ON entity-123";
@@ -109,4 +138,366 @@ fn test_parse_with_whitespace() {
     } else {
         panic!("Tipo de comando incorreto após parsing");
     }
+}
+
+#[test]
+fn test_parse_program_with_comments_and_blank_lines() {
+    let input = "// Script de teste\n\
+                 DEFINE IDEA idea-1 \"Primeira ideia\"\n\
+                 \n\
+                 # Simula a entidade\n\
+                 SIMULATE ENTITY idea-1 3 // três rodadas\n\
+                 INVOKE RULESET basic-check ON idea-1\n";
+    let result = parse_program(input);
+
+    assert!(result.is_ok(), "Parsing do programa falhou: {:?}", result.err());
+
+    let commands = result.unwrap();
+    assert_eq!(commands.len(), 3, "Número incorreto de comandos");
+    assert!(matches!(
+        &commands[1],
+        Command::Imperative(Imperative { kind: ImperativeKind::SimulateEntity { rounds: 3, .. } })
+    ), "Segundo comando deveria ser SIMULATE ENTITY");
+}
+
+#[test]
+fn test_parse_statements_records_lines() {
+    let input = "\n# cabeçalho\nORCHESTRATE sequential\n\nDEFINE CONTRACT c-1 a, b // cláusulas\n";
+    let statements = parse_statements(input).expect("Parsing do programa falhou");
+
+    assert_eq!(statements.len(), 2, "Número incorreto de instruções");
+    assert_eq!(statements[0].line, 3, "Linha da primeira instrução incorreta");
+    assert_eq!(statements[1].line, 5, "Linha da segunda instrução incorreta");
+
+    if let Command::Imperative(Imperative { kind: ImperativeKind::DefineContract { clauses, .. } }) = &statements[1].command {
+        assert_eq!(clauses, &vec!["a".to_string(), "b".to_string()], "Cláusulas incorretas");
+    } else {
+        panic!("Tipo de comando incorreto após parsing");
+    }
+}
+
+#[test]
+fn test_parse_program_error_points_to_line() {
+    let input = "ORCHESTRATE parallel\n\nSIMULATE ENTITY e-1 muitas\n";
+    let result = parse_program(input);
+
+    match result {
+        Err(parser::Error::Syntax(e)) => assert_eq!(e.line, 3, "Erro deveria apontar a linha 3: {}", e.message),
+        Ok(_) => panic!("Parsing deveria falhar para programa inválido"),
+    }
+}
+
+#[test]
+fn test_syntax_error_location_and_expected_tokens() {
+    let input = "ORCHESTRATE parallel\nSIMULATE ENTITY e-1 muitas";
+    let err = parse_program(input).expect_err("Parsing deveria falhar");
+    let syntax = err.syntax();
+
+    assert_eq!(syntax.line, 2, "Linha incorreta");
+    assert_eq!(syntax.column, 21, "Coluna incorreta");
+    assert_eq!(syntax.offset, input.find("muitas").unwrap(), "Deslocamento incorreto");
+    assert_eq!(syntax.expected, vec!["número".to_string()], "Tokens esperados incorretos");
+    assert_eq!(syntax.found, "muitas", "Trecho encontrado incorreto");
+}
+
+#[test]
+fn test_syntax_error_lists_alternative_keywords() {
+    let err = parse("REMOVE tudo").expect_err("Parsing deveria falhar");
+    let syntax = err.syntax();
+
+    assert_eq!((syntax.line, syntax.column, syntax.offset), (1, 1, 0), "Posição incorreta");
+    for keyword in ["DEFINE", "SIMULATE", "ORCHESTRATE", "INVOKE"] {
+        assert!(syntax.expected.contains(&keyword.to_string()), "Deveria esperar {}", keyword);
+    }
+}
+
+#[test]
+fn test_syntax_error_render_points_with_caret() {
+    let err = parse("INVOKE RULESET basic-check IN e-1").expect_err("Parsing deveria falhar");
+    let rendered = err.syntax().render("dsl");
+
+    let expected = "erro: esperado ON; encontrado 'IN'\n\
+                    \x20--> dsl:1:28\n\
+                    \x20 |\n\
+                    1 | INVOKE RULESET basic-check IN e-1\n\
+                    \x20 |                            ^^\n";
+    assert_eq!(rendered, expected, "Diagnóstico renderizado incorreto");
+}
+
+#[test]
+fn test_parse_let_binding() {
+    let result = parse("LET prefixo = \"boot-${timestamp}\"");
+
+    assert!(result.is_ok(), "Parsing falhou para LET válido");
+    assert_eq!(
+        result.unwrap(),
+        Command::Let { name: "prefixo".to_string(), value: "boot-${timestamp}".to_string() },
+        "Comando LET incorreto"
+    );
+}
+
+#[test]
+fn test_parse_interpolation_in_identifiers() {
+    let input = "LET alvo = entidade-1\nSIMULATE ENTITY ${alvo}-${random_id} 2\nDEFINE CONTRACT c-${timestamp} prazo ${dias} dias";
+    let commands = parse_program(input).expect("Parsing do programa falhou");
+
+    assert_eq!(commands.len(), 3, "Número incorreto de comandos");
+    if let Command::Imperative(Imperative { kind: ImperativeKind::SimulateEntity { id, .. } }) = &commands[1] {
+        assert_eq!(id, "${alvo}-${random_id}", "Identificador interpolado incorreto");
+    } else {
+        panic!("Tipo de comando incorreto após parsing");
+    }
+    if let Command::Imperative(Imperative { kind: ImperativeKind::DefineContract { id, clauses } }) = &commands[2] {
+        assert_eq!(id, "c-${timestamp}", "ID do contrato incorreto");
+        assert_eq!(clauses, &vec!["prazo ${dias} dias".to_string()], "Cláusula interpolada incorreta");
+    } else {
+        panic!("Tipo de comando incorreto após parsing");
+    }
+}
+
+#[test]
+fn test_parse_error_for_invalid_variable_name() {
+    assert!(parse("LET 1x = valor").is_err(), "Nome de variável inválido deveria falhar");
+    assert!(parse("SIMULATE ENTITY ${} 3").is_err(), "Interpolação vazia deveria falhar");
+}
+
+#[test]
+fn test_parse_inline_if() {
+    let input = "IF VERDICT(basic-check ON idea-1) == Accepted THEN SIMULATE ENTITY idea-1 10";
+    let result = parse(input);
+
+    assert!(result.is_ok(), "Parsing falhou para IF válido: {:?}", result.err());
+    assert_eq!(
+        result.unwrap(),
+        Command::If {
+            condition: Condition::Compare {
+                left: Value::Verdict { ruleset_id: "basic-check".to_string(), entity_id: "idea-1".to_string() },
+                op: CompareOp::Eq,
+                right: Value::Text("Accepted".to_string()),
+            },
+            then_branch: vec![Command::Imperative(Imperative {
+                kind: ImperativeKind::SimulateEntity { id: "idea-1".to_string(), rounds: 10 },
+            })],
+            else_branch: None,
+        },
+        "Comando IF incorreto"
+    );
+}
+
+#[test]
+fn test_parse_if_else_blocks() {
+    let input = "IF NOT EXISTS CONTRACT c-1 THEN {\n\
+                 \x20   DEFINE CONTRACT c-1 \"Termos\"\n\
+                 \x20   ORCHESTRATE sequential // comentário\n\
+                 }\n\
+                 ELSE { INVOKE RULESET always-accept ON c-1 }\n\
+                 ORCHESTRATE parallel";
+    let commands = parse_program(input).expect("Parsing do programa falhou");
+
+    assert_eq!(commands.len(), 2, "Número incorreto de comandos");
+    if let Command::If { condition, then_branch, else_branch } = &commands[0] {
+        assert_eq!(
+            condition,
+            &Condition::Not(Box::new(Condition::Exists { kind: ItemKind::Contract, id: "c-1".to_string() })),
+            "Condição incorreta"
+        );
+        assert_eq!(then_branch.len(), 2, "Bloco THEN deveria ter 2 comandos");
+        assert_eq!(else_branch.as_ref().map(Vec::len), Some(1), "Bloco ELSE deveria ter 1 comando");
+    } else {
+        panic!("Tipo de comando incorreto após parsing");
+    }
+}
+
+#[test]
+fn test_parse_for_each() {
+    let input = "FOR EACH id IN LIST CONTRACTS DO {\n    INVOKE RULESET basic-check ON ${id}\n}";
+    let result = parse(input);
+
+    if let Ok(Command::ForEach { variable, source, body }) = result {
+        assert_eq!(variable, "id", "Variável do laço incorreta");
+        assert_eq!(source, ListSource::Contracts, "Origem do laço incorreta");
+        assert_eq!(body.len(), 1, "Corpo do laço deveria ter 1 comando");
+    } else {
+        panic!("Tipo de comando incorreto após parsing: {:?}", result);
+    }
+
+    let result = parse("FOR EACH x IN [a, \"b c\", VERDICT(r ON e)] DO ORCHESTRATE ${x}");
+    if let Ok(Command::ForEach { source: ListSource::Items(items), .. }) = result {
+        assert_eq!(items.len(), 3, "Lista literal deveria ter 3 itens");
+    } else {
+        panic!("Tipo de comando incorreto após parsing: {:?}", result);
+    }
+}
+
+#[test]
+fn test_parse_with_policy() {
+    let input = "WITH TIMEOUT 30s RETRY 3 BACKOFF 250ms DO {\n    SIMULATE ENTITY e-1 5\n}\nON FAILURE DEFINE IDEA falha \"Simulação falhou\"";
+    let commands = parse_program(input).expect("Parsing do programa falhou");
+
+    assert_eq!(commands.len(), 1, "ON FAILURE deveria fazer parte do WITH");
+    if let Command::With { policy, body, on_failure } = &commands[0] {
+        assert_eq!(
+            policy,
+            &Policy { timeout_ms: Some(30_000), retries: 3, backoff_ms: Some(250) },
+            "Política incorreta"
+        );
+        assert_eq!(body.len(), 1, "Bloco DO deveria ter 1 comando");
+        assert_eq!(on_failure.as_ref().map(Vec::len), Some(1), "Bloco ON FAILURE deveria ter 1 comando");
+    } else {
+        panic!("Tipo de comando incorreto após parsing: {:?}", commands[0]);
+    }
+
+    let result = parse("WITH RETRY 2 DO SIMULATE ENTITY e-1 5");
+    assert!(
+        matches!(&result, Ok(Command::With { policy: Policy { timeout_ms: None, retries: 2, backoff_ms: None }, on_failure: None, .. })),
+        "Política só com RETRY incorreta: {:?}",
+        result
+    );
+    assert!(parse("WITH TIMEOUT 30 DO ORCHESTRATE x").is_err(), "Duração sem unidade deveria falhar");
+    assert!(parse("WITH RETRY 2 TIMEOUT 1s DO ORCHESTRATE x").is_err(), "Opções fora de ordem deveriam falhar");
+}
+
+#[test]
+fn test_parse_transaction() {
+    let input = "BEGIN // lote\n  DEFINE CONTRACT c-1 \"A\"\n\n  # comentário\n  TAG IDEA i-1 urgente\n  IF EXISTS IDEA i-1 THEN RETIRE ENTITY e-1\nCOMMIT\nLIST IDEAS";
+    let statements = parse_statements(input).expect("Parsing do programa falhou");
+
+    assert_eq!(statements.len(), 2, "COMMIT deveria encerrar a transação");
+    assert_eq!((statements[0].line, statements[1].line), (1, 8), "Linhas incorretas");
+    if let Command::Transaction { body } = &statements[0].command {
+        assert_eq!(body.len(), 3, "Transação deveria ter 3 comandos");
+        assert!(matches!(&body[2], Command::If { .. }), "Comandos de controle também podem fazer parte da transação");
+    } else {
+        panic!("Tipo de comando incorreto após parsing: {:?}", statements[0]);
+    }
+
+    assert_eq!(parse("BEGIN\nCOMMIT"), Ok(Command::Transaction { body: vec![] }));
+    assert!(parse("BEGIN DEFINE IDEA i \"x\"\nCOMMIT").is_err(), "Comandos deveriam começar na linha seguinte ao BEGIN");
+    assert!(parse("BEGIN\nDEFINE IDEA i \"x\" COMMIT").is_err(), "COMMIT deveria ficar em linha própria");
+    let err = parse_program("BEGIN\n  DEFINE IDEA i \"x\"\n").expect_err("Transação sem COMMIT deveria falhar");
+    assert!(err.syntax().expected.contains(&"COMMIT".to_string()), "Deveria esperar COMMIT: {:?}", err.syntax().expected);
+}
+
+#[test]
+fn test_parse_error_for_unclosed_block() {
+    let err = parse_program("IF EXISTS IDEA i THEN {\n  ORCHESTRATE seq\n").expect_err("Bloco sem '}' deveria falhar");
+
+    assert!(err.syntax().expected.contains(&"'}'".to_string()), "Deveria esperar '}}': {:?}", err.syntax().expected);
+}
+
+#[test]
+fn test_parse_query_verbs() {
+    let cases = [
+        ("SHOW CONTRACT c-1", ImperativeKind::ShowContract { id: "c-1".to_string() }),
+        ("LIST IDEAS", ImperativeKind::ListIdeas { tag: None }),
+        ("LIST IDEAS WHERE TAG = urgente", ImperativeKind::ListIdeas { tag: Some("urgente".to_string()) }),
+        ("LIST IDEAS WHERE TAG=\"com espaço\"", ImperativeKind::ListIdeas { tag: Some("com espaço".to_string()) }),
+        ("FIND SPANS", ImperativeKind::FindSpans { kind: None, limit: None }),
+        ("FIND SPANS KIND = boot LIMIT 5", ImperativeKind::FindSpans { kind: Some("boot".to_string()), limit: Some(5) }),
+        ("FIND SPANS LIMIT 2", ImperativeKind::FindSpans { kind: None, limit: Some(2) }),
+        ("SHOW SIMULATION e-1 LAST 3", ImperativeKind::ShowSimulation { entity_id: "e-1".to_string(), last: Some(3) }),
+    ];
+
+    for (input, kind) in cases {
+        let result = parse(input);
+        assert_eq!(result, Ok(Command::Imperative(Imperative { kind: kind.clone() })), "Consulta incorreta: {}", input);
+        assert!(kind.is_query(), "Deveria ser uma consulta: {}", input);
+    }
+
+    let err = parse("FIND SPANS LIMIT x").expect_err("LIMIT exige um número");
+    assert_eq!(err.syntax().column, 18, "Erro deveria apontar para o valor do LIMIT");
+}
+
+#[test]
+fn test_parse_mutation_verbs() {
+    let cases = [
+        (
+            "AMEND CONTRACT c-1 \"Nova cláusula\", outra",
+            ImperativeKind::AmendContract { id: "c-1".to_string(), clauses: vec!["Nova cláusula".to_string(), "outra".to_string()] },
+        ),
+        (
+            "TAG IDEA i-1 urgente, \"longo prazo\"",
+            ImperativeKind::TagIdea { id: "i-1".to_string(), tags: vec!["urgente".to_string(), "longo prazo".to_string()] },
+        ),
+        ("UPDATE IDEA i-1 \"Texto revisado\"", ImperativeKind::UpdateIdea { id: "i-1".to_string(), text: "Texto revisado".to_string() }),
+        ("RETIRE ENTITY e-1", ImperativeKind::RetireEntity { id: "e-1".to_string() }),
+        (
+            "REVERT 6f1c2a9e-3b7d-4e58-9a0c-1d2e3f4a5b6c",
+            ImperativeKind::Revert { event_id: "6f1c2a9e-3b7d-4e58-9a0c-1d2e3f4a5b6c".to_string() },
+        ),
+    ];
+
+    for (input, kind) in cases {
+        let result = parse(input);
+        assert_eq!(result, Ok(Command::Imperative(Imperative { kind: kind.clone() })), "Comando incorreto: {}", input);
+        assert!(kind.is_mutation(), "Deveria ser uma alteração: {}", input);
+    }
+
+    assert!(parse("TAG IDEA i-1").is_err(), "TAG IDEA exige ao menos uma tag");
+    assert!(parse("UPDATE IDEA i-1 sem aspas").is_err(), "UPDATE IDEA exige texto entre aspas");
+}
+
+#[test]
+fn test_recovery_collects_every_error() {
+    let input = "DEFINE IDEA i-1 \"Ok\"\n\
+                 SIMULATE ENTITY e-1 muitas\n\
+                 ORCHESTRATE sequential\n\
+                 INVALIDO agora\n\
+                 INVOKE RULESET r ON e";
+    let recovered = parse_with_recovery(input);
+
+    let lines: Vec<_> = recovered.statements().map(|s| s.line).collect();
+    assert_eq!(lines, vec![1, 3, 5], "Instruções válidas deveriam ser preservadas");
+    assert_eq!(recovered.diagnostics.len(), 2, "Deveria haver um diagnóstico por instrução inválida");
+    assert_eq!((recovered.diagnostics[0].line, recovered.diagnostics[0].column), (2, 21), "Posição do primeiro erro incorreta");
+    assert_eq!(recovered.diagnostics[1].line, 4, "Linha do segundo erro incorreta");
+    assert_eq!(
+        recovered.nodes[1],
+        Node::Error { line: 2, text: "SIMULATE ENTITY e-1 muitas".to_string() },
+        "Nó de erro incorreto"
+    );
+}
+
+#[test]
+fn test_recovery_records_missing_elements() {
+    let recovered = parse_with_recovery("SIMULATE ENTITY e-1\nORCHESTRATE x\nIF EXISTS IDEA i THEN {\n  ORCHESTRATE y\n");
+
+    match &recovered.nodes[0] {
+        Node::Missing { line, text, expected } => {
+            assert_eq!(*line, 1, "Linha incorreta");
+            assert_eq!(text, "SIMULATE ENTITY e-1", "Texto incorreto");
+            assert!(expected.contains(&"espaço".to_string()), "Deveria faltar o número de rodadas: {:?}", expected);
+        }
+        other => panic!("Nó deveria ser Missing: {:?}", other),
+    }
+    assert!(matches!(recovered.nodes[1], Node::Statement(_)), "ORCHESTRATE deveria ser válido");
+    // O bloco sem `}` vai até o fim da entrada
+    match &recovered.nodes[2] {
+        Node::Missing { line, expected, .. } => {
+            assert_eq!(*line, 3, "Linha incorreta");
+            assert!(expected.contains(&"'}'".to_string()), "Deveria faltar '}}': {:?}", expected);
+        }
+        other => panic!("Nó deveria ser Missing: {:?}", other),
+    }
+}
+
+#[test]
+fn test_recovery_skips_whole_block() {
+    let input = "IF EXISTS IDEA i THEN {\n    ORCHESTRATE ok\n    SIMULATE ENTITY e x\n    ORCHESTRATE \"}\"\n}\nORCHESTRATE depois";
+    let recovered = parse_with_recovery(input);
+
+    assert_eq!(recovered.nodes.len(), 2, "O bloco inválido deveria virar um único nó: {:?}", recovered.nodes);
+    assert!(matches!(&recovered.nodes[0], Node::Error { line: 1, text } if text.ends_with('}')), "Bloco deveria ser descartado por inteiro");
+    assert_eq!(recovered.statements().next().map(|s| s.line), Some(6), "Instrução após o bloco deveria ser recuperada");
+
+    // Transações também são descartadas até o COMMIT
+    let recovered = parse_with_recovery("BEGIN\n    SIMULATE ENTITY e x\n    ORCHESTRATE ok\nCOMMIT\nORCHESTRATE depois");
+    assert_eq!(recovered.nodes.len(), 2, "A transação inválida deveria virar um único nó: {:?}", recovered.nodes);
+    assert_eq!(recovered.statements().next().map(|s| s.line), Some(5), "Instrução após a transação deveria ser recuperada");
+
+    // Sem erros, o resultado é o mesmo de parse_statements
+    let valid = "ORCHESTRATE a\n// comentário\nIF EXISTS IDEA i THEN {\n    ORCHESTRATE b\n}";
+    let recovered = parse_with_recovery(valid);
+    assert!(recovered.diagnostics.is_empty(), "Programa válido não deveria ter diagnósticos");
+    assert_eq!(recovered.statements().cloned().collect::<Vec<_>>(), parse_statements(valid).unwrap(), "AST diferente de parse_statements");
 }
\ No newline at end of file
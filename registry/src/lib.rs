@@ -1,6 +1,6 @@
 // === File: registry/src/lib.rs ===
 /*
-    Description: Registro central de entidades com suporte a diferentes tipos de dados para o LogLine.
+    Description: Registro central de entidades com suporte a diferentes tipos de dados e armazenamentos para o LogLine.
     LogLine Motor
     Author: @danvoulez
     License: Apache-2.0
@@ -10,101 +10,194 @@
     Contact: dan@danvoulez.com
 */
 
-use std::collections::HashMap;
-use once_cell::sync::Lazy;
-use tokio::sync::RwLock;
-use chrono::{DateTime, Utc};
-use std::error::Error;
-
-/// Estrutura de dados para as entidades registradas
-struct Entity {
-    /// Identificador lógico da entidade (informado pelo usuário)
-    logical_id: String,
-    /// Tipo da entidade (CONTRACT, IDEA, etc.)
-    entity_type: String,
-    /// Quando a entidade foi registrada
-    created_at: DateTime<Utc>,
-}
+pub mod store;
+
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use chrono::Utc;
+use serde_json::Value;
+use thiserror::Error;
+
+pub use logline_core::types::{Entity, EntityType};
+pub use store::{MemoryStore, RedbStore, RegistryStore};
+
+/// Erros das operações tipadas do registro
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RegistryError {
+    #[error("Entidade não encontrada: {0}")]
+    NotFound(String),
+
+    #[error("Entidade já existe: {0}")]
+    AlreadyExists(String),
 
-/// Armazena entidades em memória.
-/// Em uma implementação de produção, esse estado estaria em um banco de dados.
-static REGISTRY: Lazy<RwLock<HashMap<String, Entity>>> = Lazy::new(|| {
-    RwLock::new(HashMap::new())
-});
-
-/// Registra uma nova entidade.
-/// 
-/// # Parâmetros
-/// 
-/// * `logical_id` - ID lógico da entidade (informado pelo usuário)
-/// * `entity_type` - Tipo da entidade (ex: CONTRACT, IDEA)
-/// 
-/// # Retorna
-/// 
-/// O ID lógico da entidade se o registro for bem-sucedido, ou um erro se falhar.
-pub async fn register_entity(logical_id: &str, entity_type: &str) -> Result<String, Box<dyn Error>> {
-    let entity = Entity {
-        logical_id: logical_id.to_string(),
-        entity_type: entity_type.to_string(),
-        created_at: Utc::now(),
-    };
-    
-    let mut registry = REGISTRY.write().await;
-    registry.insert(logical_id.to_string(), entity);
-    
-    Ok(logical_id.to_string())
+    #[error("Conflito de versão na entidade {id}: esperada {expected}, atual {found}")]
+    VersionConflict { id: String, expected: u64, found: u64 },
+
+    #[error("Erro no armazenamento do registro: {0}")]
+    Storage(String),
 }
 
-/// Busca uma entidade pelo seu ID.
-/// 
-/// # Parâmetros
-/// 
-/// * `id` - ID lógico da entidade a buscar
-/// 
-/// # Retorna
-/// 
-/// Um tuple (logical_id, entity_type) se a entidade for encontrada, ou um erro se não for.
-pub async fn fetch_entity(id: &str) -> Result<(String, String), Box<dyn Error>> {
-    let registry = REGISTRY.read().await;
-    
-    match registry.get(id) {
-        Some(entity) => Ok((entity.logical_id.clone(), entity.entity_type.clone())),
-        None => Err(format!("Entidade não encontrada: {}", id).into()),
-    }
+/// Alterações de uma entidade (ver [`Registry::update_entity`]); os campos
+/// `None` ficam como estão.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntityUpdate {
+    /// Novo tipo da entidade
+    pub entity_type: Option<EntityType>,
+    /// Novo dono ou tenant
+    pub owner: Option<String>,
+    /// Novos metadados, que substituem os anteriores
+    pub metadata: Option<Value>,
 }
 
-/// Lista todas as entidades do tipo especificado.
-/// 
-/// # Parâmetros
-/// 
-/// * `entity_type` - Tipo de entidade a filtrar
-/// 
-/// # Retorna
-/// 
-/// Um vetor de IDs lógicos das entidades do tipo especificado.
-pub async fn list_entities_by_type(entity_type: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let registry = REGISTRY.read().await;
-    
-    let ids: Vec<String> = registry
-        .iter()
-        .filter(|(_, entity)| entity.entity_type == entity_type)
-        .map(|(id, _)| id.clone())
-        .collect();
-    
-    Ok(ids)
+/// Registro de entidades sobre um armazenamento (ver [`RegistryStore`]).
+///
+/// Cópias de um `Registry` compartilham o armazenamento; registros criados
+/// separadamente, como os de testes diferentes, não compartilham nada.
+#[derive(Clone)]
+pub struct Registry {
+    store: Arc<dyn RegistryStore>,
+    /// Serializa as gravações que dependem do estado atual. Nunca é mantido
+    /// durante um `await`, de modo que também serve a [`Registry::restore`].
+    writes: Arc<Mutex<()>>,
 }
 
-/// Remove uma entidade do registro.
-/// 
-/// # Parâmetros
-/// 
-/// * `id` - ID lógico da entidade a remover
-/// 
-/// # Retorna
-/// 
-/// `true` se a entidade foi removida, `false` se não foi encontrada.
-pub async fn remove_entity(id: &str) -> Result<bool, Box<dyn Error>> {
-    let mut registry = REGISTRY.write().await;
-    
-    Ok(registry.remove(id).is_some())
-}
\ No newline at end of file
+impl Registry {
+    /// Cria um registro sobre o armazenamento dado.
+    pub fn new(store: impl RegistryStore + 'static) -> Self {
+        Self { store: Arc::new(store), writes: Arc::new(Mutex::new(())) }
+    }
+
+    /// Cria um registro vazio em memória.
+    pub fn in_memory() -> Self {
+        Self::new(MemoryStore::new())
+    }
+
+    /// Abre um registro persistido no arquivo dado (ver [`RedbStore`]).
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        Ok(Self::new(RedbStore::open(path)?))
+    }
+
+    /// Se as entidades sobrevivem ao fim do processo (ver
+    /// [`RegistryStore::is_durable`]).
+    pub fn is_durable(&self) -> bool {
+        self.store.is_durable()
+    }
+
+    /// Registra uma entidade com o tipo dado, sem dono nem metadados.
+    ///
+    /// Registrar de novo uma entidade com o mesmo tipo não a altera, de
+    /// modo que reaplicar um registro é inofensivo. Se a entidade existir
+    /// com outro tipo, apenas o tipo muda, como numa alteração: dono e
+    /// metadados são mantidos e a versão passa à seguinte.
+    pub async fn register_entity(&self, logical_id: &str, entity_type: &str) -> Result<Entity, RegistryError> {
+        let entity_type = EntityType::from(entity_type);
+        let _write = self.write();
+        let entity = match self.store.get(logical_id)? {
+            Some(existing) if existing.entity_type == entity_type => return Ok(existing),
+            Some(mut existing) => {
+                existing.entity_type = entity_type;
+                existing.version += 1;
+                existing.updated_at = Utc::now();
+                existing
+            }
+            None => Entity::new(logical_id, entity_type),
+        };
+        self.store.put(&entity)?;
+        Ok(entity)
+    }
+
+    /// Cria uma entidade com tipo, dono e metadados.
+    ///
+    /// A entidade é registrada na versão 1, com as datas de criação e
+    /// alteração atuais, quaisquer que sejam as informadas. Falha com
+    /// [`RegistryError::AlreadyExists`] se o ID já estiver em uso.
+    pub async fn create_entity(&self, entity: Entity) -> Result<Entity, RegistryError> {
+        let _write = self.write();
+        if self.store.get(&entity.id)?.is_some() {
+            return Err(RegistryError::AlreadyExists(entity.id));
+        }
+
+        let now = Utc::now();
+        let entity = Entity { version: 1, created_at: now, updated_at: now, ..entity };
+        self.store.put(&entity)?;
+        Ok(entity)
+    }
+
+    /// Busca a entidade completa pelo seu ID.
+    pub async fn get_entity(&self, id: &str) -> Result<Entity, RegistryError> {
+        self.store.get(id)?.ok_or_else(|| RegistryError::NotFound(id.to_string()))
+    }
+
+    /// Altera uma entidade com controle de concorrência otimista.
+    ///
+    /// A alteração só é aplicada se a entidade ainda estiver na versão
+    /// `expected_version`; a versão passa então à seguinte e a data de
+    /// alteração é atualizada. Quem recebe
+    /// [`RegistryError::VersionConflict`] deve buscar a entidade de novo e
+    /// refazer a alteração sobre a versão atual.
+    pub async fn update_entity(&self, id: &str, expected_version: u64, update: EntityUpdate) -> Result<Entity, RegistryError> {
+        let _write = self.write();
+        let mut entity = self.store.get(id)?.ok_or_else(|| RegistryError::NotFound(id.to_string()))?;
+        if entity.version != expected_version {
+            return Err(RegistryError::VersionConflict {
+                id: id.to_string(),
+                expected: expected_version,
+                found: entity.version,
+            });
+        }
+
+        if let Some(entity_type) = update.entity_type {
+            entity.entity_type = entity_type;
+        }
+        if let Some(owner) = update.owner {
+            entity.owner = Some(owner);
+        }
+        if let Some(metadata) = update.metadata {
+            entity.metadata = metadata;
+        }
+        entity.version += 1;
+        entity.updated_at = Utc::now();
+        self.store.put(&entity)?;
+        Ok(entity)
+    }
+
+    /// Grava a entidade como está, sem verificar o ID nem a versão.
+    ///
+    /// Serve a quem mantém o registro como projeção de outro registro de
+    /// eventos, como o runtime, que o reconstrói e desfaz alterações.
+    pub async fn put_entity(&self, entity: Entity) -> Result<(), RegistryError> {
+        let _write = self.write();
+        self.store.put(&entity)
+    }
+
+    /// Grava a entidade dada, ou remove a do ID `id` se for `None`, sem
+    /// aguardar.
+    ///
+    /// Como [`Registry::put_entity`], serve a quem mantém o registro como
+    /// projeção, para desfazer alterações onde não é possível aguardar,
+    /// como no `Drop`.
+    pub fn restore(&self, id: &str, entity: Option<Entity>) -> Result<(), RegistryError> {
+        let _write = self.write();
+        match entity {
+            Some(entity) => self.store.put(&entity),
+            None => self.store.remove(id).map(|_| ()),
+        }
+    }
+
+    /// Lista as entidades que satisfazem o filtro.
+    pub async fn list_entities(&self, filter: impl Fn(&Entity) -> bool) -> Result<Vec<Entity>, RegistryError> {
+        Ok(self.store.list()?.into_iter().filter(|entity| filter(entity)).collect())
+    }
+
+    /// Remove uma entidade. Devolve `false` se ela não existia.
+    pub async fn remove_entity(&self, id: &str) -> Result<bool, RegistryError> {
+        let _write = self.write();
+        self.store.remove(id)
+    }
+
+    /// Lock das gravações. Uma gravação interrompida por pânico não deixa o
+    /// armazenamento pela metade, então o lock envenenado é reaproveitado.
+    fn write(&self) -> MutexGuard<'_, ()> {
+        self.writes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,100 @@
+// === File: registry/src/store/disk.rs ===
+/*
+    Description: Armazenamento do registro em disco, num banco redb embutido.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fs;
+use std::path::Path;
+use redb::{Database, ReadableTable, TableDefinition};
+use super::RegistryStore;
+use crate::{Entity, RegistryError};
+
+/// Tabela das entidades: ID → entidade em JSON.
+const ENTITIES: TableDefinition<&str, &[u8]> = TableDefinition::new("entities");
+
+/// Entidades gravadas em JSON num arquivo redb, uma chave por ID.
+///
+/// Cada gravação é uma transação confirmada no disco antes de retornar, de
+/// modo que o registro sobrevive a um reinício do processo. O arquivo só
+/// pode ser aberto por um `RedbStore` de cada vez.
+pub struct RedbStore {
+    db: Database,
+}
+
+impl RedbStore {
+    /// Abre o banco no arquivo dado, criando-o se não existir.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RegistryError> {
+        let path = path.as_ref();
+        let opening = |e: String| RegistryError::Storage(format!("Falha ao abrir {}: {}", path.display(), e));
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| opening(e.to_string()))?;
+        }
+        let db = Database::create(path).map_err(|e| opening(e.to_string()))?;
+
+        // Cria a tabela, para que as leituras não precisem tratar a ausência dela
+        let txn = db.begin_write().map_err(storage)?;
+        txn.open_table(ENTITIES).map_err(storage)?;
+        txn.commit().map_err(storage)?;
+        Ok(Self { db })
+    }
+}
+
+impl RegistryStore for RedbStore {
+    fn get(&self, id: &str) -> Result<Option<Entity>, RegistryError> {
+        let txn = self.db.begin_read().map_err(storage)?;
+        let table = txn.open_table(ENTITIES).map_err(storage)?;
+        let value = table.get(id).map_err(storage)?;
+        value.map(|bytes| decode(bytes.value())).transpose()
+    }
+
+    fn put(&self, entity: &Entity) -> Result<(), RegistryError> {
+        let bytes = serde_json::to_vec(entity).map_err(|e| RegistryError::Storage(e.to_string()))?;
+        let txn = self.db.begin_write().map_err(storage)?;
+        {
+            let mut table = txn.open_table(ENTITIES).map_err(storage)?;
+            table.insert(entity.id.as_str(), bytes.as_slice()).map_err(storage)?;
+        }
+        txn.commit().map_err(storage)
+    }
+
+    fn remove(&self, id: &str) -> Result<bool, RegistryError> {
+        let txn = self.db.begin_write().map_err(storage)?;
+        let mut table = txn.open_table(ENTITIES).map_err(storage)?;
+        let removed = table.remove(id).map_err(storage)?.is_some();
+        // A tabela empresta a transação, que só pode ser confirmada sem ela
+        drop(table);
+        txn.commit().map_err(storage)?;
+        Ok(removed)
+    }
+
+    fn list(&self) -> Result<Vec<Entity>, RegistryError> {
+        let txn = self.db.begin_read().map_err(storage)?;
+        let table = txn.open_table(ENTITIES).map_err(storage)?;
+        let entries = table.iter().map_err(storage)?;
+        entries
+            .map(|entry| {
+                let (_, bytes) = entry.map_err(storage)?;
+                decode(bytes.value())
+            })
+            .collect()
+    }
+
+    fn is_durable(&self) -> bool {
+        true
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<Entity, RegistryError> {
+    serde_json::from_slice(bytes).map_err(|e| RegistryError::Storage(format!("Entidade inválida no banco: {}", e)))
+}
+
+fn storage(error: impl Into<redb::Error>) -> RegistryError {
+    RegistryError::Storage(error.into().to_string())
+}
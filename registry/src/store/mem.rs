@@ -0,0 +1,52 @@
+// === File: registry/src/store/mem.rs ===
+/*
+    Description: Armazenamento do registro em memória.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use super::RegistryStore;
+use crate::{Entity, RegistryError};
+
+/// Entidades em memória, perdidas quando o processo termina.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entities: RwLock<HashMap<String, Entity>>,
+}
+
+impl MemoryStore {
+    /// Cria um armazenamento vazio.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RegistryStore for MemoryStore {
+    fn get(&self, id: &str) -> Result<Option<Entity>, RegistryError> {
+        let entities = self.entities.read().map_err(|e| RegistryError::Storage(e.to_string()))?;
+        Ok(entities.get(id).cloned())
+    }
+
+    fn put(&self, entity: &Entity) -> Result<(), RegistryError> {
+        let mut entities = self.entities.write().map_err(|e| RegistryError::Storage(e.to_string()))?;
+        entities.insert(entity.id.clone(), entity.clone());
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) -> Result<bool, RegistryError> {
+        let mut entities = self.entities.write().map_err(|e| RegistryError::Storage(e.to_string()))?;
+        Ok(entities.remove(id).is_some())
+    }
+
+    fn list(&self) -> Result<Vec<Entity>, RegistryError> {
+        let entities = self.entities.read().map_err(|e| RegistryError::Storage(e.to_string()))?;
+        Ok(entities.values().cloned().collect())
+    }
+}
@@ -0,0 +1,43 @@
+// === File: registry/src/store/mod.rs ===
+/*
+    Description: Interface dos armazenamentos do registro, com backends em memória e em disco.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+mod disk;
+mod mem;
+
+pub use disk::RedbStore;
+pub use mem::MemoryStore;
+
+use crate::{Entity, RegistryError};
+
+/// Armazenamento das entidades do registro.
+///
+/// As operações são simples leituras e gravações por ID: as verificações
+/// que dependem do estado atual (ID em uso, versão esperada) são feitas por
+/// [`crate::Registry`], que serializa as gravações.
+pub trait RegistryStore: Send + Sync {
+    /// Busca uma entidade pelo ID.
+    fn get(&self, id: &str) -> Result<Option<Entity>, RegistryError>;
+
+    /// Grava uma entidade, substituindo a de mesmo ID.
+    fn put(&self, entity: &Entity) -> Result<(), RegistryError>;
+
+    /// Remove uma entidade. Devolve `false` se ela não existia.
+    fn remove(&self, id: &str) -> Result<bool, RegistryError>;
+
+    /// Lista todas as entidades.
+    fn list(&self) -> Result<Vec<Entity>, RegistryError>;
+
+    /// Se as entidades sobrevivem ao fim do processo.
+    fn is_durable(&self) -> bool {
+        false
+    }
+}
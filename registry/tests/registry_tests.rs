@@ -10,36 +10,45 @@
     Contact: dan@danvoulez.com
 */
 
-use registry::{register_entity, fetch_entity, list_entities_by_type, remove_entity};
+use registry::{Entity, EntityType, EntityUpdate, Registry, RegistryError};
+use serde_json::json;
+
+/// IDs das entidades do tipo dado.
+async fn ids_of_type(registry: &Registry, entity_type: &str) -> Vec<String> {
+    let entities = registry.list_entities(|entity| entity.entity_type.as_str() == entity_type).await.unwrap();
+    entities.into_iter().map(|entity| entity.id).collect()
+}
 
 #[tokio::test]
 async fn test_register_and_fetch_entity() {
     // Registra uma entidade de teste
     let id = "test-entity-1";
     let entity_type = "TEST";
+    let registry = Registry::in_memory();
     
-    let result = register_entity(id, entity_type).await;
+    let result = registry.register_entity(id, entity_type).await;
     assert!(result.is_ok());
-    assert_eq!(result.unwrap(), id);
+    assert_eq!(result.unwrap().id, id);
     
     // Busca a entidade registrada
-    let entity = fetch_entity(id).await;
+    let entity = registry.get_entity(id).await;
     assert!(entity.is_ok());
     
-    let (fetched_id, fetched_type) = entity.unwrap();
-    assert_eq!(fetched_id, id);
-    assert_eq!(fetched_type, entity_type);
+    let fetched = entity.unwrap();
+    assert_eq!(fetched.id, id);
+    assert_eq!(fetched.entity_type.as_str(), entity_type);
 }
 
 #[tokio::test]
 async fn test_list_entities_by_type() {
     // Registra múltiplas entidades de diferentes tipos
-    register_entity("list-test-1", "TYPE_A").await.unwrap();
-    register_entity("list-test-2", "TYPE_B").await.unwrap();
-    register_entity("list-test-3", "TYPE_A").await.unwrap();
+    let registry = Registry::in_memory();
+    registry.register_entity("list-test-1", "TYPE_A").await.unwrap();
+    registry.register_entity("list-test-2", "TYPE_B").await.unwrap();
+    registry.register_entity("list-test-3", "TYPE_A").await.unwrap();
     
     // Lista entidades do TYPE_A
-    let type_a_entities = list_entities_by_type("TYPE_A").await.unwrap();
+    let type_a_entities = ids_of_type(&registry, "TYPE_A").await;
     
     // Verifica se todas as entidades TYPE_A foram encontradas
     assert!(type_a_entities.contains(&"list-test-1".to_string()));
@@ -47,7 +56,7 @@ async fn test_list_entities_by_type() {
     assert_eq!(type_a_entities.len(), 2);
     
     // Lista entidades do TYPE_B
-    let type_b_entities = list_entities_by_type("TYPE_B").await.unwrap();
+    let type_b_entities = ids_of_type(&registry, "TYPE_B").await;
     
     // Verifica se todas as entidades TYPE_B foram encontradas
     assert!(type_b_entities.contains(&"list-test-2".to_string()));
@@ -58,21 +67,79 @@ async fn test_list_entities_by_type() {
 async fn test_remove_entity() {
     // Registra uma entidade para remoção
     let id = "remove-test";
-    register_entity(id, "TEMPORARY").await.unwrap();
+    let registry = Registry::in_memory();
+    registry.register_entity(id, "TEMPORARY").await.unwrap();
     
     // Verifica se a entidade existe
-    let exists = fetch_entity(id).await.is_ok();
+    let exists = registry.get_entity(id).await.is_ok();
     assert!(exists, "A entidade deveria existir antes da remoção");
     
     // Remove a entidade
-    let removed = remove_entity(id).await.unwrap();
+    let removed = registry.remove_entity(id).await.unwrap();
     assert!(removed, "A remoção deveria ser bem-sucedida");
     
     // Verifica se a entidade foi removida
-    let not_exists = fetch_entity(id).await.is_err();
+    let not_exists = registry.get_entity(id).await.is_err();
     assert!(not_exists, "A entidade não deveria existir após a remoção");
     
     // Tenta remover novamente (deveria falhar)
-    let not_removed = !remove_entity(id).await.unwrap();
+    let not_removed = !registry.remove_entity(id).await.unwrap();
     assert!(not_removed, "A segunda tentativa de remoção deveria falhar");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_create_typed_entity() {
+    // Cria uma entidade com dono e metadados
+    let registry = Registry::in_memory();
+    let entity = Entity::new("typed-test", EntityType::Contract)
+        .with_owner("tenant-a")
+        .with_metadata(json!({ "origem": "teste" }));
+    let created = registry.create_entity(entity.clone()).await.unwrap();
+    assert_eq!(created.version, 1);
+    assert_eq!(created.owner.as_deref(), Some("tenant-a"));
+
+    // A entidade é encontrada pelo ID, pelo tipo e pelo dono
+    let fetched = registry.get_entity("typed-test").await.unwrap();
+    assert_eq!(fetched, created);
+    assert_eq!(fetched.entity_type.as_str(), "CONTRACT");
+    assert_eq!(ids_of_type(&registry, "CONTRACT").await, vec!["typed-test".to_string()]);
+    let owned = registry.list_entities(|entity| entity.owner.as_deref() == Some("tenant-a")).await;
+    assert_eq!(owned, Ok(vec![created]));
+
+    // Um ID em uso não pode ser criado de novo
+    let duplicate = registry.create_entity(entity).await;
+    assert_eq!(duplicate, Err(RegistryError::AlreadyExists("typed-test".to_string())));
+
+    // Tipos desconhecidos são mantidos como escritos
+    registry.register_entity("typed-custom", "SENSOR").await.unwrap();
+    let custom = registry.get_entity("typed-custom").await.unwrap();
+    assert_eq!(custom.entity_type, EntityType::Other("SENSOR".to_string()));
+    assert_eq!(serde_json::to_value(&custom).unwrap()["entity_type"], json!("SENSOR"));
+}
+
+#[tokio::test]
+async fn test_update_entity_with_expected_version() {
+    let registry = Registry::in_memory();
+    registry.register_entity("versioned-test", "IDEA").await.unwrap();
+
+    // Alteração sobre a versão atual
+    let update = EntityUpdate { metadata: Some(json!({ "prioridade": 1 })), ..EntityUpdate::default() };
+    let updated = registry.update_entity("versioned-test", 1, update).await.unwrap();
+    assert_eq!(updated.version, 2);
+    assert_eq!(updated.metadata, json!({ "prioridade": 1 }));
+    assert_eq!(updated.entity_type, EntityType::Idea);
+    assert!(updated.updated_at >= updated.created_at);
+
+    // Outra alteração preparada sobre a versão 1 é recusada
+    let stale = EntityUpdate { owner: Some("tenant-b".to_string()), ..EntityUpdate::default() };
+    let conflict = registry.update_entity("versioned-test", 1, stale).await;
+    assert_eq!(
+        conflict,
+        Err(RegistryError::VersionConflict { id: "versioned-test".to_string(), expected: 1, found: 2 })
+    );
+    let current = registry.get_entity("versioned-test").await.unwrap();
+    assert_eq!(current, updated, "A alteração recusada não deveria ter efeito");
+
+    let missing = registry.update_entity("versioned-missing", 1, EntityUpdate::default()).await;
+    assert_eq!(missing, Err(RegistryError::NotFound("versioned-missing".to_string())));
+}
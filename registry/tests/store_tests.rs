@@ -0,0 +1,81 @@
+// === File: registry/tests/store_tests.rs ===
+/*
+    Description: Testes dos armazenamentos do registro: registros isolados em memória e persistência em arquivo.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::path::PathBuf;
+use registry::{Entity, EntityType, EntityUpdate, Registry, RegistryError};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Caminho de um arquivo de registro temporário, único por teste.
+fn temp_registry() -> PathBuf {
+    std::env::temp_dir().join(format!("logline-{}", Uuid::new_v4())).join("registry.redb")
+}
+
+#[tokio::test]
+async fn test_in_memory_registries_are_isolated() {
+    let first = Registry::in_memory();
+    let second = Registry::in_memory();
+    first.register_entity("isolated", "IDEA").await.unwrap();
+
+    assert!(first.get_entity("isolated").await.is_ok());
+    assert_eq!(second.get_entity("isolated").await, Err(RegistryError::NotFound("isolated".to_string())));
+
+    // Cópias compartilham o armazenamento
+    let copy = first.clone();
+    copy.remove_entity("isolated").await.unwrap();
+    assert!(first.get_entity("isolated").await.is_err());
+}
+
+#[tokio::test]
+async fn test_register_keeps_entity_of_same_type() {
+    let registry = Registry::in_memory();
+    registry.register_entity("repeated", "CONTRACT").await.unwrap();
+    let update = EntityUpdate { owner: Some("tenant-a".to_string()), ..EntityUpdate::default() };
+    let updated = registry.update_entity("repeated", 1, update).await.unwrap();
+
+    // Reaplicar o registro não desfaz a alteração
+    assert_eq!(registry.register_entity("repeated", "CONTRACT").await.unwrap(), updated);
+
+    // Outro tipo altera apenas o tipo, na versão seguinte
+    let retyped = registry.register_entity("repeated", "IDEA").await.unwrap();
+    assert_eq!(retyped.entity_type, EntityType::Idea);
+    assert_eq!((retyped.version, retyped.owner), (3, Some("tenant-a".to_string())));
+    assert_eq!(retyped.created_at, updated.created_at);
+}
+
+#[tokio::test]
+async fn test_redb_store_survives_reopen() {
+    let path = temp_registry();
+    let created = {
+        let registry = Registry::open(&path).unwrap();
+        let entity = Entity::new("persisted", EntityType::Agent).with_owner("tenant-a");
+        registry.create_entity(entity).await.unwrap();
+        registry.register_entity("removed", "IDEA").await.unwrap();
+        assert!(registry.remove_entity("removed").await.unwrap());
+
+        let update = EntityUpdate { metadata: Some(json!({ "modelo": "v2" })), ..EntityUpdate::default() };
+        registry.update_entity("persisted", 1, update).await.unwrap()
+    };
+
+    let registry = Registry::open(&path).unwrap();
+    assert_eq!(registry.get_entity("persisted").await.unwrap(), created);
+    assert!(registry.get_entity("removed").await.is_err(), "Remoção deveria persistir");
+    let agents = registry.list_entities(|entity| entity.entity_type == EntityType::Agent).await.unwrap();
+    assert_eq!(agents, vec![created.clone()]);
+
+    // A versão persistida continua valendo para o controle de concorrência
+    let conflict = registry.update_entity("persisted", 1, EntityUpdate::default()).await;
+    assert!(matches!(conflict, Err(RegistryError::VersionConflict { found: 2, .. })), "{:?}", conflict);
+
+    drop(registry);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,463 @@
+// === File: runtime/src/boot.rs ===
+/*
+    Description: Boot de um lllpack: lê o manifesto, executa a boot_sequence e registra os spans de boot na timeline.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, bail, Context, Result};
+use parser::lll::{self, Contract, Field, Member, Span, Value};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use crate::events::EventKind;
+use crate::scheduler::Trigger;
+use crate::trace::{self, TraceContext};
+use crate::vars::Scope;
+use crate::{replay, timeline, view};
+
+/// Nome do manifesto na raiz do pacote.
+pub const MANIFEST_FILE: &str = "lllpack.yaml";
+
+/// Span que encerra o boot com o resumo (ver [`BootReport`]).
+pub const REPORT_SPAN: &str = "ReportBootStatus";
+
+/// Tipo das entidades registradas para os agentes do pacote.
+pub const AGENT_ENTITY_TYPE: &str = "AGENT";
+
+/// Manifesto `lllpack.yaml` de um pacote.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Manifest {
+    pub name: String,
+    pub version: String,
+    /// Arquivo `.lll` com os spans de boot, relativo ao pacote.
+    pub entrypoint: String,
+    #[serde(default)]
+    pub contracts: Vec<String>,
+    #[serde(default)]
+    pub agents: Vec<String>,
+    #[serde(default)]
+    pub boot_sequence: Vec<BootStep>,
+    /// Gatilhos executados pelo [`crate::scheduler`].
+    #[serde(default)]
+    pub triggers: Vec<Trigger>,
+    /// Arquivo da timeline. Não é aceito: a timeline é a do runtime (ver
+    /// [`crate::init`]), e um caminho declarado seria ignorado.
+    #[serde(default)]
+    timeline: Option<String>,
+}
+
+/// Passo da `boot_sequence`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BootStep {
+    /// Nome do span, declarado no entrypoint.
+    pub span: String,
+    #[serde(rename = "type")]
+    pub step_type: String,
+}
+
+impl Manifest {
+    /// Lê o manifesto de um pacote.
+    ///
+    /// Falha se o manifesto declarar `timeline`: o boot usa a timeline do
+    /// runtime, definida por `LOGLINE_TIMELINE_PATH` (ver [`crate::init`]).
+    pub fn load(pack_dir: impl AsRef<Path>) -> Result<Self> {
+        let path = pack_dir.as_ref().join(MANIFEST_FILE);
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Falha ao ler o manifesto {}", path.display()))?;
+        let manifest: Self =
+            serde_yaml::from_str(&source).with_context(|| format!("Manifesto {} inválido", path.display()))?;
+        if let Some(timeline) = &manifest.timeline {
+            bail!(
+                "Manifesto {} declara a timeline {}; a timeline do runtime é definida por {}",
+                path.display(),
+                timeline,
+                crate::TIMELINE_PATH_ENV
+            );
+        }
+        Ok(manifest)
+    }
+}
+
+/// Resumo do boot, produzido pelo span `ReportBootStatus`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BootReport {
+    pub pack: String,
+    pub version: String,
+    /// `boot_complete` se todos os passos tiveram sucesso; `boot_failed`
+    /// caso contrário.
+    pub status: String,
+    pub duration_ms: u64,
+    /// Contratos carregados, pelo nome declarado no `.lll`.
+    pub contracts: Vec<String>,
+    /// Agentes ativados, pelo nome declarado no `.lll`.
+    pub agents: Vec<String>,
+    /// Passos na ordem da `boot_sequence`.
+    pub steps: Vec<StepReport>,
+}
+
+/// Resultado de um passo do boot.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StepReport {
+    pub span: String,
+    /// `succeeded`, `failed` ou `skipped` (dependência sem sucesso).
+    pub status: String,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+    /// Campos do span registrado, já resolvidos.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Executa o boot do pacote no diretório indicado.
+///
+/// Os passos da `boot_sequence` são executados em ordem, cada um com o
+/// tempo limite de `timeouts.execution` do seu span no entrypoint:
+///
+/// * `InitializeTimeline` inicializa o runtime (ver [`crate::init`]), com a
+///   timeline de `LOGLINE_TIMELINE_PATH`;
+/// * `LoadContracts` registra os contratos do manifesto;
+/// * `ActivateAgents` registra os agentes como entidades `AGENT`;
+/// * `ConnectEmbeddings` valida o cabeçalho do índice de embeddings do span;
+/// * `RunDiagnostic` verifica a timeline e o que foi carregado.
+///
+/// Cada passo registra um `BootSpan` com os campos do span e, conforme o
+/// desfecho, os de `on_success` ou o span de `on_failure`. Um passo que
+/// depende (`depends_on`) de outro sem sucesso não é executado. Por fim,
+/// `ReportBootStatus` registra e devolve o resumo. Carregar de novo um
+/// pacote já carregado não altera o estado.
+///
+/// Falha apenas se o manifesto ou o entrypoint forem inválidos; falhas dos
+/// passos aparecem no resumo.
+pub async fn boot(pack_dir: impl AsRef<Path>) -> Result<BootReport> {
+    let pack_dir = pack_dir.as_ref();
+    let manifest = Manifest::load(pack_dir)?;
+    let entrypoint = load_lll(&pack_dir.join(&manifest.entrypoint))?;
+    let spans: HashMap<&str, &Span> = entrypoint.spans().into_iter().map(|span| (span.name.as_str(), span)).collect();
+    for step in manifest.boot_sequence.iter().map(|step| step.span.as_str()).chain([REPORT_SPAN]) {
+        if !spans.contains_key(step) {
+            bail!("Span de boot {} não declarado em {}", step, manifest.entrypoint);
+        }
+    }
+
+    let context = TraceContext::new(vec![trace::DEFAULT_ACTOR.to_string()]);
+    let mut boot = Boot {
+        pack_dir,
+        manifest: &manifest,
+        spans,
+        context,
+        scope: Scope::new(),
+        contracts: Vec::new(),
+        agents: Vec::new(),
+    };
+    let started = Instant::now();
+    let mut steps = Vec::with_capacity(manifest.boot_sequence.len());
+    let mut unsuccessful = HashSet::new();
+
+    for step in &manifest.boot_sequence {
+        let span = boot.spans[step.span.as_str()];
+        let report = match span.depends_on.iter().find(|d| unsuccessful.contains(d.as_str())) {
+            Some(dependency) => StepReport {
+                span: step.span.clone(),
+                status: "skipped".to_string(),
+                duration_ms: 0,
+                error: Some(format!("Dependência {} não concluída", dependency)),
+                fields: BTreeMap::new(),
+            },
+            None => boot.run_step(step).await?,
+        };
+        if report.status != "succeeded" {
+            unsuccessful.insert(step.span.as_str());
+        }
+        steps.push(report);
+    }
+
+    let status = if unsuccessful.is_empty() { "boot_complete" } else { "boot_failed" };
+    let duration_ms = started.elapsed().as_millis() as u64;
+    boot.scope.bind("boot_duration", &duration_ms.to_string());
+    boot.scope.bind("contracts_count", &boot.contracts.len().to_string());
+    boot.scope.bind("agents_count", &boot.agents.len().to_string());
+    let mut fields = boot.fields(REPORT_SPAN, true);
+    fields.insert("status".to_string(), status.to_string());
+    boot.record(REPORT_SPAN, "boot_report", fields).await?;
+
+    Ok(BootReport {
+        pack: manifest.name.clone(),
+        version: manifest.version.clone(),
+        status: status.to_string(),
+        duration_ms,
+        contracts: boot.contracts,
+        agents: boot.agents,
+        steps,
+    })
+}
+
+/// Estado de um boot em andamento.
+struct Boot<'a> {
+    pack_dir: &'a Path,
+    manifest: &'a Manifest,
+    spans: HashMap<&'a str, &'a Span>,
+    context: TraceContext,
+    /// Variáveis usadas nos campos dos spans (`${loaded_count}`...).
+    scope: Scope,
+    contracts: Vec<String>,
+    agents: Vec<String>,
+}
+
+impl Boot<'_> {
+    /// Executa um passo e registra o seu span.
+    async fn run_step(&mut self, step: &BootStep) -> Result<StepReport> {
+        let span = self.spans[step.span.as_str()];
+        let started = Instant::now();
+        let result = match execution_timeout(span) {
+            Some(limit) => tokio::time::timeout(limit, self.execute(&step.span))
+                .await
+                .unwrap_or_else(|_| Err(anyhow!("Tempo limite de {} ms excedido", limit.as_millis()))),
+            None => self.execute(&step.span).await,
+        };
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        let (status, error, fields) = match result {
+            Ok(()) => ("succeeded", None, self.fields(&step.span, true)),
+            Err(e) => {
+                let mut fields = self.fields(&step.span, false);
+                fields.insert("status".to_string(), "failed".to_string());
+                fields.insert("error".to_string(), e.to_string());
+                ("failed", Some(e.to_string()), fields)
+            }
+        };
+        self.record(&step.span, &step.step_type, fields.clone()).await?;
+
+        // O span de recuperação é registrado com os próprios campos
+        if let Some(recovery) = error.as_ref().and(span.on_failure.as_ref()).and_then(|o| o.span.as_deref()) {
+            match self.spans.get(recovery) {
+                Some(_) => {
+                    let fields = self.fields(recovery, false);
+                    self.record(recovery, "recovery", fields).await?;
+                }
+                None => warn!("Span de recuperação {} não declarado", recovery),
+            }
+        }
+
+        Ok(StepReport { span: step.span.clone(), status: status.to_string(), duration_ms, error, fields })
+    }
+
+    /// Ação de cada span de boot. Os resultados são associados às
+    /// variáveis usadas em `on_success`.
+    async fn execute(&mut self, span: &str) -> Result<()> {
+        match span {
+            "InitializeTimeline" => {
+                trace::scope(self.context.clone(), crate::init()).await?;
+                let (_, events) = timeline::history().await?;
+                self.scope.bind("timeline_events", &events.len().to_string());
+            }
+            "LoadContracts" => {
+                for path in &self.manifest.contracts {
+                    let contract = load_lll(&self.pack_dir.join(path))?;
+                    trace::scope(self.context.clone(), register_contract(&contract)).await?;
+                    self.contracts.push(contract.name);
+                }
+                self.scope.bind("loaded_count", &self.contracts.len().to_string());
+            }
+            "ActivateAgents" => {
+                for path in &self.manifest.agents {
+                    let agent = load_lll(&self.pack_dir.join(path))?;
+                    trace::scope(self.context.clone(), register_agent(&agent.name)).await?;
+                    self.agents.push(agent.name);
+                }
+                self.scope.bind("activated_count", &self.agents.len().to_string());
+            }
+            "ConnectEmbeddings" => {
+                let fields = self.fields(span, false);
+                let path = fields.get("embedding_path").ok_or_else(|| anyhow!("Span {} sem embedding_path", span))?;
+                let vectors = connect_embeddings(&self.pack_dir.join(path), fields.get("dimension"))?;
+                self.scope.bind("loaded_vectors", &vectors.to_string());
+            }
+            "RunDiagnostic" => {
+                let issues = self.diagnose().await?;
+                for issue in &issues {
+                    warn!("Diagnóstico do boot: {}", issue);
+                }
+                let health = if issues.is_empty() { "healthy" } else { "degraded" };
+                self.scope.bind("health_status", health);
+                self.scope.bind("issues_count", &issues.len().to_string());
+            }
+            other => bail!("Span de boot sem ação nativa: {}", other),
+        }
+        Ok(())
+    }
+
+    /// Problemas encontrados no que foi carregado. Uma timeline adulterada
+    /// é uma falha do diagnóstico.
+    async fn diagnose(&self) -> Result<Vec<String>> {
+        timeline::verify().await.map_err(|e| anyhow!("Timeline adulterada: {}", e))?;
+        let mut issues = Vec::new();
+        for name in &self.contracts {
+            if view::get_contract(name).await.is_err() {
+                issues.push(format!("Contrato {} não está registrado", name));
+            }
+        }
+        for name in &self.agents {
+            if view::fetch_entity(name).await.is_err() {
+                issues.push(format!("Agente {} não está registrado", name));
+            }
+        }
+        if self.contracts.len() < self.manifest.contracts.len() {
+            issues.push(format!("{} de {} contratos carregados", self.contracts.len(), self.manifest.contracts.len()));
+        }
+        if self.agents.len() < self.manifest.agents.len() {
+            issues.push(format!("{} de {} agentes ativados", self.agents.len(), self.manifest.agents.len()));
+        }
+        Ok(issues)
+    }
+
+    /// Campos do span resolvidos com as variáveis do boot, seguidos dos de
+    /// `on_success` quando `succeeded`. Campos com variáveis ainda não
+    /// definidas mantêm o texto declarado.
+    fn fields(&self, span: &str, succeeded: bool) -> BTreeMap<String, String> {
+        let span = self.spans[span];
+        let outcome = span.on_success.as_ref().filter(|_| succeeded).map(|o| o.fields.as_slice()).unwrap_or_default();
+        span.fields.iter().chain(outcome).map(|field| (field.name.clone(), self.resolve(field))).collect()
+    }
+
+    fn resolve(&self, field: &Field) -> String {
+        let text = text(&field.value);
+        self.scope.interpolate(&text).unwrap_or(text)
+    }
+
+    /// Registra o `BootSpan` de um span, em nome dos atores dele.
+    async fn record(&self, name: &str, step_type: &str, fields: BTreeMap<String, String>) -> Result<()> {
+        let span = self.spans[name];
+        let span_id = span.id.as_deref().map(|id| self.scope.interpolate(id).unwrap_or_else(|_| id.to_string()));
+        let status = fields.get("status").cloned().unwrap_or_else(|| "completed".to_string());
+        let kind = EventKind::BootSpan {
+            span: name.to_string(),
+            span_id: span_id.unwrap_or_else(|| name.to_string()),
+            span_type: span.span_type.clone().unwrap_or_else(|| step_type.to_string()),
+            status,
+            fields,
+        };
+        let context = if span.actors.is_empty() {
+            self.context.clone()
+        } else {
+            TraceContext { actors: span.actors.clone(), ..self.context.clone() }
+        };
+        trace::scope(context, timeline::append(kind)).await?;
+        Ok(())
+    }
+}
+
+/// Lê e analisa um arquivo `.lll`.
+pub(crate) fn load_lll(path: &Path) -> Result<Contract> {
+    let source = std::fs::read_to_string(path).with_context(|| format!("Falha ao ler {}", path.display()))?;
+    lll::parse(&source).map_err(|e| anyhow!("{}: {}", path.display(), e))
+}
+
+/// Registra um contrato `.lll`, emendando-o se as cláusulas mudaram.
+///
+/// As cláusulas são os textos (`texto` ou `text`) das seções do contrato,
+/// na ordem do arquivo.
+async fn register_contract(contract: &Contract) -> Result<()> {
+    let mut clauses = Vec::new();
+    collect_clauses(&contract.body, &mut clauses);
+    let id = contract.name.clone();
+    replay::exclusive(async {
+        match view::get_contract(&id).await {
+            Ok((_, current, _)) if current == clauses => {}
+            Ok(_) => {
+                replay::commit(EventKind::ContractAmended { id, clauses }).await?;
+            }
+            Err(_) => {
+                replay::commit(EventKind::ContractRegistered { id, clauses }).await?;
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+fn collect_clauses(members: &[Member], clauses: &mut Vec<String>) {
+    for member in members {
+        if let Member::Section(section) = member {
+            if let Some(text) = section.get("texto").or_else(|| section.get("text")).and_then(Value::as_str) {
+                clauses.push(text.to_string());
+            }
+            collect_clauses(&section.body, clauses);
+        }
+    }
+}
+
+/// Registra um agente como entidade, se ainda não estiver no registro.
+async fn register_agent(name: &str) -> Result<()> {
+    replay::exclusive(async {
+        if view::fetch_entity(name).await.is_err() {
+            let entity_type = AGENT_ENTITY_TYPE.to_string();
+            replay::commit(EventKind::EntityRegistered { id: name.to_string(), entity_type }).await?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Valida o cabeçalho do índice de embeddings e devolve o número de
+/// vetores.
+///
+/// A dimensão do índice deve ser a declarada no span, se houver. Apenas o
+/// cabeçalho (`index_metadata`) é lido: os vetores não são carregados no
+/// boot, e os do índice de exemplo do pacote vêm abreviados (`...`).
+fn connect_embeddings(path: &Path, dimension: Option<&String>) -> Result<u64> {
+    let source = std::fs::read_to_string(path).with_context(|| format!("Falha ao ler o índice {}", path.display()))?;
+    let metadata = index_metadata(&source).with_context(|| format!("Índice {} inválido", path.display()))?;
+    if let Some(expected) = dimension {
+        let found = metadata["dimension"].as_u64().map(|d| d.to_string()).unwrap_or_default();
+        if found != *expected {
+            bail!("Índice {} tem dimensão {:?}, esperada {}", path.display(), found, expected);
+        }
+    }
+    metadata["total_vectors"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("Índice {} sem total_vectors", path.display()))
+}
+
+/// Objeto `index_metadata` de um índice, lido sem analisar o resto do
+/// arquivo.
+fn index_metadata(source: &str) -> Result<serde_json::Value> {
+    const KEY: &str = "\"index_metadata\"";
+    let start = source.find(KEY).ok_or_else(|| anyhow!("Cabeçalho index_metadata ausente"))?;
+    let value = source[start + KEY.len()..]
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(|| anyhow!("Cabeçalho index_metadata sem valor"))?;
+    let metadata = serde_json::Deserializer::from_str(value)
+        .into_iter::<serde_json::Value>()
+        .next()
+        .ok_or_else(|| anyhow!("Cabeçalho index_metadata sem valor"))??;
+    Ok(metadata)
+}
+
+/// Tempo limite de `timeouts.execution`, em segundos.
+pub(crate) fn execution_timeout(span: &Span) -> Option<Duration> {
+    let seconds = match span.attribute("timeouts")?.get("execution")? {
+        Value::Number(n) => *n,
+        Value::Text(text) => text.trim().parse().ok()?,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok().filter(|limit| !limit.is_zero())
+}
+
+/// Forma textual de um valor `.lll`.
+fn text(value: &Value) -> String {
+    match value {
+        Value::Text(text) => text.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Null => String::new(),
+        other => format!("{:?}", other),
+    }
+}
@@ -0,0 +1,107 @@
+// === File: runtime/src/context.rs ===
+/*
+    Description: Instância do runtime, com o registro de entidades, a timeline e o lock de registros da tarefa atual.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::future::Future;
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use registry::Registry;
+use tokio::sync::{Mutex, RwLock};
+use tracing::info;
+use crate::timeline::Timeline;
+use crate::REGISTRY_PATH_ENV;
+
+/// Instância do runtime: o registro de entidades, a timeline da qual ele é
+/// uma projeção e o lock dos registros (ver [`crate::replay::exclusive`]).
+///
+/// As funções do runtime operam sobre a instância da tarefa atual, que deve
+/// ser dada com [`Runtime::scope`]: não há instância padrão, de modo que
+/// chamadas sem instância não compartilham estado por acaso. Cópias de um
+/// `Runtime` compartilham o registro e a timeline; instâncias criadas com
+/// [`Runtime::new`] não compartilham nada entre si.
+#[derive(Clone)]
+pub struct Runtime {
+    registry: Registry,
+    timeline: Arc<RwLock<Timeline>>,
+    commits: Arc<Mutex<()>>,
+}
+
+tokio::task_local! {
+    static RUNTIME: Runtime;
+}
+
+impl Runtime {
+    /// Cria uma instância com o registro dado e a timeline vazia, em
+    /// memória (ver [`crate::init_with_timeline`]).
+    pub fn new(registry: Registry) -> Self {
+        Self {
+            registry,
+            timeline: Arc::new(RwLock::new(Timeline::default())),
+            commits: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Cria uma instância com o registro e a timeline em memória.
+    pub fn in_memory() -> Self {
+        Self::new(Registry::in_memory())
+    }
+
+    /// Cria uma instância com o registro persistido em
+    /// `LOGLINE_REGISTRY_PATH`, se definida, ou um em memória.
+    ///
+    /// O registro é uma projeção da timeline e é reconstruído a partir dela
+    /// ao carregá-la: o persistido exige também `LOGLINE_TIMELINE_PATH`
+    /// (ver [`crate::init`]).
+    pub fn from_env() -> Result<Self> {
+        let registry = match std::env::var(REGISTRY_PATH_ENV) {
+            Ok(path) if !path.is_empty() => {
+                let registry = Registry::open(&path).map_err(|e| anyhow!("Falha ao abrir o registro em {}: {}", path, e))?;
+                info!("Registro de entidades persistido em {}", path);
+                registry
+            }
+            _ => Registry::in_memory(),
+        };
+        Ok(Self::new(registry))
+    }
+
+    /// Instância da tarefa atual.
+    ///
+    /// Como o contexto de [`crate::trace`], a instância vale apenas para a
+    /// tarefa atual: tarefas criadas com `tokio::spawn` devem recebê-la de
+    /// novo (ver [`Runtime::scope`]).
+    ///
+    /// # Panics
+    ///
+    /// Fora de [`Runtime::scope`].
+    pub fn current() -> Self {
+        RUNTIME
+            .try_with(Clone::clone)
+            .expect("Funções do runtime devem executar dentro de Runtime::scope")
+    }
+
+    /// Executa `future` sobre esta instância.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        RUNTIME.scope(self.clone(), future).await
+    }
+
+    /// Registro de entidades da instância.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    pub(crate) fn timeline(&self) -> Arc<RwLock<Timeline>> {
+        self.timeline.clone()
+    }
+
+    pub(crate) fn commits(&self) -> Arc<Mutex<()>> {
+        self.commits.clone()
+    }
+}
@@ -0,0 +1,90 @@
+// === File: runtime/src/control.rs ===
+/*
+    Description: Avaliação de condições e listas dos comandos IF e FOR EACH contra o estado do LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use parser::ast::{CompareOp, Condition, ItemKind, ListSource, Value};
+use crate::vars::Scope;
+use crate::view;
+
+/// Avalia a condição de um `IF`.
+///
+/// `EXISTS` consulta o registro, os contratos ou as ideias; `VERDICT(...)`
+/// aplica o ruleset indicado à entidade. Interpolações são resolvidas com
+/// o escopo atual.
+pub async fn evaluate(condition: &Condition, scope: &Scope) -> Result<bool> {
+    match condition {
+        Condition::Compare { left, op, right } => {
+            let left = evaluate_value(left, scope).await?;
+            let right = evaluate_value(right, scope).await?;
+            Ok(match op {
+                CompareOp::Eq => left == right,
+                CompareOp::Ne => left != right,
+            })
+        }
+        Condition::Exists { kind, id } => {
+            let id = scope.interpolate(id)?;
+            Ok(match kind {
+                ItemKind::Contract => view::get_contract(&id).await.is_ok(),
+                ItemKind::Idea => view::get_idea(&id).await.is_ok(),
+                ItemKind::Entity => view::fetch_entity(&id).await.is_ok(),
+            })
+        }
+        Condition::Not(inner) => Ok(!evaluate_boxed(inner, scope).await?),
+    }
+}
+
+/// [`evaluate`] numa `BoxFuture`, para a recursão do `NOT`.
+fn evaluate_boxed<'a>(condition: &'a Condition, scope: &'a Scope) -> BoxFuture<'a, Result<bool>> {
+    Box::pin(evaluate(condition, scope))
+}
+
+/// Avalia um valor, devolvendo sua forma textual.
+///
+/// Veredictos são representados pelo nome da variante (`Accepted` ou
+/// `Rejected`).
+pub async fn evaluate_value(value: &Value, scope: &Scope) -> Result<String> {
+    match value {
+        Value::Text(text) => Ok(scope.interpolate(text)?),
+        Value::Verdict { ruleset_id, entity_id } => {
+            let ruleset_id = scope.interpolate(ruleset_id)?;
+            let entity_id = scope.interpolate(entity_id)?;
+            let verdict = ruleset::apply_ruleset(&ruleset_id, &entity_id)
+                .await
+                .map_err(|e| anyhow!("Falha ao aplicar o ruleset '{}': {}", ruleset_id, e))?;
+            Ok(format!("{:?}", verdict))
+        }
+    }
+}
+
+/// Lista os itens sobre os quais um `FOR EACH` itera, em ordem estável.
+pub async fn list_items(source: &ListSource, scope: &Scope) -> Result<Vec<String>> {
+    let mut items = match source {
+        ListSource::Contracts => view::list_contracts().await,
+        ListSource::Ideas => view::list_ideas().await,
+        ListSource::Entities { entity_type } => {
+            let entity_type = scope.interpolate(entity_type)?;
+            view::list_entities_by_type(&entity_type).await
+        }
+        ListSource::Items(values) => {
+            // A ordem da lista literal é a escrita no script.
+            let mut items = Vec::with_capacity(values.len());
+            for value in values {
+                items.push(evaluate_value(value, scope).await?);
+            }
+            return Ok(items);
+        }
+    };
+
+    items.sort();
+    Ok(items)
+}
@@ -0,0 +1,166 @@
+// === File: runtime/src/cron.rs ===
+/*
+    Description: Expressões cron de cinco campos e cálculo das próximas ocorrências, em UTC.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fmt;
+use std::str::FromStr;
+use anyhow::{anyhow, bail, Error, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+
+/// Maior intervalo procurado por uma próxima ocorrência. Cobre agendas como
+/// `0 0 29 2 1`, que só ocorrem em anos bissextos.
+const SEARCH_LIMIT_DAYS: i64 = 366 * 8;
+
+/// Agenda cron de cinco campos: minuto, hora, dia do mês, mês e dia da
+/// semana.
+///
+/// Cada campo aceita `*`, valores, intervalos (`1-5`), listas (`1,15`) e
+/// passos (`*/15`, `0-30/10`, `5/20`). Os dias da semana vão de 0 (domingo)
+/// a 6, e 7 também é domingo. Como no cron tradicional, se tanto o dia do
+/// mês quanto o da semana forem restritos, basta um dos dois coincidir.
+/// Também são aceitos `@hourly`, `@daily`, `@weekly`, `@monthly` e
+/// `@yearly`. Os horários são sempre em UTC.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cron {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl Cron {
+    /// Primeira ocorrência estritamente posterior a `after`, ou `None` se a
+    /// agenda nunca ocorre (ex: `0 0 31 2 *`).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after + Duration::days(SEARCH_LIMIT_DAYS);
+        let mut at = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        while at <= limit {
+            let date = at.date_naive();
+            if !contains(self.months, date.month()) {
+                let (year, month) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+                at = midnight(NaiveDate::from_ymd_opt(year, month, 1)?);
+            } else if !self.day_matches(date) {
+                at = midnight(date.succ_opt()?);
+            } else if !contains(self.hours, at.hour()) {
+                at = at.with_minute(0)? + Duration::hours(1);
+            } else if !contains(self.minutes, at.minute()) {
+                at += Duration::minutes(1);
+            } else {
+                return Some(at);
+            }
+        }
+        None
+    }
+
+    /// Ocorrências posteriores a `after`, em ordem.
+    pub fn occurrences(&self, after: DateTime<Utc>) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        std::iter::successors(self.next_after(after), move |at| self.next_after(*at))
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = contains(self.days, date.day());
+        let weekday = contains(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+impl FromStr for Cron {
+    type Err = Error;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let expression = match source.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let parts: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = parts[..] else {
+            bail!("Expressão cron '{}' deveria ter 5 campos, tem {}", source, parts.len());
+        };
+
+        let mut weekdays = field(weekday, "dia da semana", 0, 7)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            minutes: field(minute, "minuto", 0, 59)?,
+            hours: field(hour, "hora", 0, 23)?,
+            days: field(day, "dia do mês", 1, 31)?,
+            months: field(month, "mês", 1, 12)?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Valores aceitos por um campo, como máscara de bits.
+fn field(text: &str, name: &str, min: u32, max: u32) -> Result<u64> {
+    let mut mask = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step.parse::<u32>().ok().filter(|step| *step > 0);
+                (range, step.ok_or_else(|| anyhow!("Passo inválido no campo {}: '{}'", name, part))?)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (value(start, name, min, max)?, value(end, name, min, max)?),
+            // `5/20` vai de 5 até o fim do campo
+            None if step > 1 => (value(range, name, min, max)?, max),
+            None => {
+                let value = value(range, name, min, max)?;
+                (value, value)
+            }
+        };
+        if start > end {
+            bail!("Intervalo invertido no campo {}: '{}'", name, part);
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+fn value(text: &str, name: &str, min: u32, max: u32) -> Result<u32> {
+    text.parse()
+        .ok()
+        .filter(|value| (min..=max).contains(value))
+        .ok_or_else(|| anyhow!("Valor inválido no campo {}: '{}' (esperado de {} a {})", name, text, min, max))
+}
+
+fn contains(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
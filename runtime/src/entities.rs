@@ -0,0 +1,79 @@
+// === File: runtime/src/entities.rs ===
+/*
+    Description: Criação e alteração de entidades com dono, metadados e versão, registradas na timeline.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{anyhow, Result};
+use registry::{Entity, EntityUpdate, RegistryError};
+use crate::events::EventKind;
+use crate::{replay, view};
+
+/// Cria uma entidade com tipo, dono e metadados.
+///
+/// Registra um `EntityCreated` na timeline (ver [`replay::commit`]), de
+/// modo que a entidade sobrevive a um replay ou a um snapshot. Ela entra
+/// na versão 1, com a data do evento. Falha com
+/// [`RegistryError::AlreadyExists`] se o ID já estiver em uso.
+pub async fn create_entity(entity: Entity) -> Result<Entity> {
+    replay::exclusive(async {
+        if view::get_entity(&entity.id).await.is_some() {
+            return Err(RegistryError::AlreadyExists(entity.id).into());
+        }
+        let id = entity.id.clone();
+        replay::commit(EventKind::EntityCreated {
+            id: entity.id,
+            entity_type: entity.entity_type.to_string(),
+            owner: entity.owner,
+            metadata: entity.metadata,
+            version: 1,
+        })
+        .await?;
+        current(&id).await
+    })
+    .await
+}
+
+/// Altera uma entidade com controle de concorrência otimista.
+///
+/// A alteração só é aplicada se a entidade ainda estiver na versão
+/// `expected_version`; um `EntityUpdated` com os campos resultantes e a
+/// versão seguinte é então registrado na timeline. Os campos `None` de
+/// `update` ficam como estão. Falha com [`RegistryError::NotFound`] ou
+/// [`RegistryError::VersionConflict`]; neste caso, busque a entidade de
+/// novo e refaça a alteração sobre a versão atual.
+pub async fn update_entity(id: &str, expected_version: u64, update: EntityUpdate) -> Result<Entity> {
+    replay::exclusive(async {
+        let entity = view::get_entity(id).await.ok_or_else(|| RegistryError::NotFound(id.to_string()))?;
+        if entity.version != expected_version {
+            return Err(RegistryError::VersionConflict {
+                id: id.to_string(),
+                expected: expected_version,
+                found: entity.version,
+            }
+            .into());
+        }
+        replay::commit(EventKind::EntityUpdated {
+            id: id.to_string(),
+            entity_type: update.entity_type.map_or(entity.entity_type, |entity_type| entity_type.to_string()),
+            owner: update.owner.or(entity.owner),
+            metadata: update.metadata.unwrap_or(entity.metadata),
+            version: entity.version + 1,
+        })
+        .await?;
+        current(id).await
+    })
+    .await
+}
+
+/// Entidade como ficou depois do registro.
+async fn current(id: &str) -> Result<Entity> {
+    let entity = view::get_entity(id).await.ok_or_else(|| anyhow!("Entidade não encontrada: {}", id))?;
+    Ok(entity.to_entity())
+}
@@ -10,10 +10,13 @@
     Contact: dan@danvoulez.com
 */
 
+use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use ruleset::rules::Verdict;
+use sha2::{Digest, Sha256};
+use crate::trace::TraceContext;
 
 /// Tipo de evento produzido pelo runtime
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -29,10 +32,89 @@ pub enum EventKind {
     /// Ideia registrada
     IdeaRegistered {
         id: String,
+        #[serde(default)]
+        text: String,
     },
     /// Contrato registrado
     ContractRegistered {
         id: String,
+        #[serde(default)]
+        clauses: Vec<String>,
+    },
+    /// Cláusulas de um contrato substituídas
+    ContractAmended {
+        id: String,
+        clauses: Vec<String>,
+    },
+    /// Tags adicionadas a uma ideia
+    IdeaTagged {
+        id: String,
+        tags: Vec<String>,
+    },
+    /// Tags removidas de uma ideia
+    IdeaUntagged {
+        id: String,
+        tags: Vec<String>,
+    },
+    /// Texto de uma ideia atualizado
+    IdeaUpdated {
+        id: String,
+        text: String,
+    },
+    /// Entidade removida do registro
+    EntityRetired {
+        id: String,
+    },
+    /// Entidade incluída no registro sem um contrato ou ideia associado
+    EntityRegistered {
+        id: String,
+        entity_type: String,
+    },
+    /// Entidade criada com dono e metadados (ver
+    /// [`crate::entities::create_entity`])
+    EntityCreated {
+        id: String,
+        entity_type: String,
+        #[serde(default)]
+        owner: Option<String>,
+        #[serde(default)]
+        metadata: serde_json::Value,
+        /// 1, ou a seguinte à da entidade removida, quando a remoção é
+        /// revertida
+        version: u64,
+    },
+    /// Entidade alterada (ver [`crate::entities::update_entity`]): os campos
+    /// como ficaram e a nova versão
+    EntityUpdated {
+        id: String,
+        entity_type: String,
+        #[serde(default)]
+        owner: Option<String>,
+        #[serde(default)]
+        metadata: serde_json::Value,
+        version: u64,
+    },
+    /// Contrato removido
+    ContractDeleted {
+        id: String,
+    },
+    /// Ideia removida
+    IdeaDeleted {
+        id: String,
+    },
+    /// Evento revertido por eventos de compensação
+    EventReverted {
+        /// Evento revertido
+        event_id: Uuid,
+        /// Hash do evento revertido
+        event_hash: String,
+        /// Eventos de compensação, na ordem em que foram registrados
+        compensations: Vec<Uuid>,
+    },
+    /// Eventos anteriores substituídos por um snapshot do estado
+    TimelineCompacted {
+        events_collapsed: usize,
+        snapshot_hash: String,
     },
     /// Veredicto de uma regra do ruleset
     RuleVerdict {
@@ -50,6 +132,60 @@ pub enum EventKind {
         concurrency: usize,
         duration_ms: u64,
     },
+    /// Tentativa de execução de um bloco sob uma política (ver
+    /// [`crate::policy`])
+    ExecutionAttempt {
+        /// Número da tentativa, a partir de 1
+        attempt: usize,
+        max_attempts: usize,
+        /// `succeeded`, `failed` ou `timed_out`
+        status: String,
+        duration_ms: u64,
+        #[serde(default)]
+        error: Option<String>,
+    },
+    /// Span da sequência de boot de um lllpack (ver [`crate::boot`])
+    BootSpan {
+        /// Nome do span no entrypoint
+        span: String,
+        /// Identificador declarado no span, já resolvido
+        span_id: String,
+        span_type: String,
+        status: String,
+        fields: BTreeMap<String, String>,
+    },
+    /// Gatilho do scheduler verificado pela primeira vez, antes de qualquer
+    /// disparo (ver [`crate::scheduler`])
+    TriggerArmed {
+        trigger: String,
+        /// Instante a partir do qual o gatilho considera ocorrências e eventos
+        since: DateTime<Utc>,
+    },
+    /// Disparo de um gatilho do scheduler (ver [`crate::scheduler`])
+    CronExecution {
+        trigger: String,
+        /// Ocorrência da agenda, ou instante do evento que disparou o gatilho
+        scheduled_for: DateTime<Utc>,
+        /// Ocorrências perdidas cobertas por este disparo
+        #[serde(default)]
+        missed: usize,
+        /// Evento que disparou o gatilho
+        #[serde(default)]
+        cause: Option<Uuid>,
+    },
+    /// Fim do script de um disparo
+    CronCompletion {
+        trigger: String,
+        /// `completed` ou `failed`
+        status: String,
+        duration_ms: u64,
+        /// Status de cada tarefa do script: `completed`, `failed` ou `skipped`
+        tasks: BTreeMap<String, String>,
+        #[serde(default)]
+        error: Option<String>,
+        #[serde(default)]
+        next_scheduled: Option<DateTime<Utc>>,
+    },
     /// Simulação executada
     SimulationCompleted {
         id: String,
@@ -62,7 +198,49 @@ pub enum EventKind {
     },
 }
 
+impl EventKind {
+    /// Nome da variante, usado para filtrar a timeline.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::RuntimeLifecycle { .. } => "RuntimeLifecycle",
+            EventKind::ImperativeExecuted { .. } => "ImperativeExecuted",
+            EventKind::IdeaRegistered { .. } => "IdeaRegistered",
+            EventKind::ContractRegistered { .. } => "ContractRegistered",
+            EventKind::ContractAmended { .. } => "ContractAmended",
+            EventKind::IdeaTagged { .. } => "IdeaTagged",
+            EventKind::IdeaUntagged { .. } => "IdeaUntagged",
+            EventKind::IdeaUpdated { .. } => "IdeaUpdated",
+            EventKind::EntityRetired { .. } => "EntityRetired",
+            EventKind::EntityRegistered { .. } => "EntityRegistered",
+            EventKind::EntityCreated { .. } => "EntityCreated",
+            EventKind::EntityUpdated { .. } => "EntityUpdated",
+            EventKind::ContractDeleted { .. } => "ContractDeleted",
+            EventKind::IdeaDeleted { .. } => "IdeaDeleted",
+            EventKind::EventReverted { .. } => "EventReverted",
+            EventKind::TimelineCompacted { .. } => "TimelineCompacted",
+            EventKind::RuleVerdict { .. } => "RuleVerdict",
+            EventKind::OrchestrationStarted { .. } => "OrchestrationStarted",
+            EventKind::OrchestrationCompleted { .. } => "OrchestrationCompleted",
+            EventKind::ExecutionAttempt { .. } => "ExecutionAttempt",
+            EventKind::BootSpan { .. } => "BootSpan",
+            EventKind::TriggerArmed { .. } => "TriggerArmed",
+            EventKind::CronExecution { .. } => "CronExecution",
+            EventKind::CronCompletion { .. } => "CronCompletion",
+            EventKind::SimulationCompleted { .. } => "SimulationCompleted",
+            EventKind::ErrorOccurred { .. } => "ErrorOccurred",
+        }
+    }
+}
+
 /// Evento com metadados
+///
+/// Os eventos da timeline formam uma cadeia causal: cada um guarda o hash
+/// SHA-256 do anterior em `causal_hash`, e o seu próprio `hash` cobre esse
+/// elo. Alterar qualquer evento quebra a cadeia a partir dele (ver
+/// [`crate::timeline::verify`]).
+///
+/// Eventos registrados durante um `process_command` também carregam o
+/// trace, o evento pai e os atores do contexto (ver [`crate::trace`]).
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Event {
     /// Identificador único do evento
@@ -71,15 +249,91 @@ pub struct Event {
     pub timestamp: DateTime<Utc>,
     /// Tipo do evento com dados específicos
     pub kind: EventKind,
+    /// Hash do evento, no formato `sha256:<hex>`
+    #[serde(default)]
+    pub hash: String,
+    /// Hash do evento anterior na timeline (`None` no primeiro)
+    #[serde(default)]
+    pub causal_hash: Option<String>,
+    /// Trace de que o evento faz parte
+    #[serde(default)]
+    pub trace_id: Option<Uuid>,
+    /// Evento que iniciou o trabalho que produziu este
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+    /// Quem produziu o evento
+    #[serde(default)]
+    pub actors: Vec<String>,
+}
+
+/// Conteúdo coberto pelo hash de um evento.
+#[derive(Serialize)]
+struct HashedContent<'a> {
+    id: &'a Uuid,
+    timestamp: &'a DateTime<Utc>,
+    kind: &'a EventKind,
+    causal_hash: &'a Option<String>,
+    // Omitidos quando vazios, preservando o hash de eventos sem contexto
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: &'a Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: &'a Option<Uuid>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    actors: &'a [String],
 }
 
 impl Event {
-    /// Cria um novo evento com timestamp atual e ID gerado
+    /// Cria um novo evento com timestamp atual e ID gerado, fora de qualquer
+    /// cadeia
     pub fn new(kind: EventKind) -> Self {
-        Self {
+        Self::chained(kind, None)
+    }
+
+    /// Cria um novo evento ligado ao evento anterior pelo hash dele
+    pub fn chained(kind: EventKind, causal_hash: Option<String>) -> Self {
+        Self::build(kind, causal_hash, None)
+    }
+
+    /// Cria um novo evento ligado ao evento anterior, com o trace, o evento
+    /// pai e os atores do contexto
+    pub fn traced(kind: EventKind, causal_hash: Option<String>, context: &TraceContext) -> Self {
+        Self::build(kind, causal_hash, Some(context))
+    }
+
+    fn build(kind: EventKind, causal_hash: Option<String>, context: Option<&TraceContext>) -> Self {
+        let mut event = Self {
             id: Uuid::new_v4(),
             timestamp: Utc::now(),
             kind,
-        }
+            hash: String::new(),
+            causal_hash,
+            trace_id: context.map(|context| context.trace_id),
+            parent_id: context.and_then(|context| context.parent_id),
+            actors: context.map(|context| context.actors.clone()).unwrap_or_default(),
+        };
+        event.hash = event.compute_hash();
+        event
+    }
+
+    /// Calcula o hash SHA-256 do evento sobre o ID, o timestamp, o tipo, o
+    /// elo com o evento anterior e o contexto de rastreamento
+    pub fn compute_hash(&self) -> String {
+        let content = HashedContent {
+            id: &self.id,
+            timestamp: &self.timestamp,
+            kind: &self.kind,
+            causal_hash: &self.causal_hash,
+            trace_id: &self.trace_id,
+            parent_id: &self.parent_id,
+            actors: &self.actors,
+        };
+        let bytes = serde_json::to_vec(&content).expect("Eventos são sempre serializáveis");
+        sha256_hex(bytes)
     }
-}
\ No newline at end of file
+}
+
+/// Hash SHA-256 dos bytes no formato `sha256:<hex>`, o dos eventos e dos
+/// snapshots.
+pub(crate) fn sha256_hex(bytes: impl AsRef<[u8]>) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(bytes)))
+}
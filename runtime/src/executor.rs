@@ -0,0 +1,174 @@
+// === File: runtime/src/executor.rs ===
+/*
+    Description: Executor dos comandos LogLine: despacha cada comando para o crate responsável e registra os eventos na timeline.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{anyhow, bail, Result};
+use futures::future::BoxFuture;
+use parser::ast::{Command, Imperative, ImperativeKind};
+use uuid::Uuid;
+use crate::events::EventKind;
+use crate::policy::{self, ExecutionPolicy};
+use crate::vars::Scope;
+use crate::{control, mutations, orchestrator, query, replay, revert, timeline, transaction, view, Runtime};
+
+/// Executa um comando no escopo dado e devolve a descrição do resultado.
+///
+/// `LET` altera o escopo; `IF` e `FOR EACH` executam seus blocos no
+/// próprio escopo, e o resultado é o dos comandos executados, um por linha.
+/// `WITH` executa seu bloco sob a política declarada (ver [`policy`]), e
+/// `BEGIN ... COMMIT`, como uma transação (ver [`transaction`]).
+pub async fn execute(command: &Command, scope: &mut Scope) -> Result<String> {
+    match command {
+        Command::Imperative(imperative) => {
+            let kind = scope.resolve_kind(&imperative.kind)?;
+            dispatch(&kind).await
+        }
+        Command::Let { name, value } => {
+            let value = scope.interpolate(value)?;
+            scope.bind(name, &value);
+            Ok(format!("Variável {} definida", name))
+        }
+        Command::If { condition, then_branch, else_branch } => {
+            if control::evaluate(condition, scope).await? {
+                execute_block(then_branch, scope).await
+            } else if let Some(else_branch) = else_branch {
+                execute_block(else_branch, scope).await
+            } else {
+                Ok(String::new())
+            }
+        }
+        Command::ForEach { variable, source, body } => {
+            let mut results = Vec::new();
+            for item in control::list_items(source, scope).await? {
+                scope.bind(variable, &item);
+                results.push(execute_block(body, scope).await?);
+            }
+            Ok(join(results))
+        }
+        Command::With { policy, body, on_failure } => {
+            let policy = ExecutionPolicy::from_ast(policy, on_failure.as_deref());
+            policy::execute(&policy, body, scope).await
+        }
+        Command::Transaction { body } => transaction::execute(body, scope).await,
+    }
+}
+
+/// Executa os comandos de um bloco em sequência, parando no primeiro erro.
+pub async fn execute_block(commands: &[Command], scope: &mut Scope) -> Result<String> {
+    let mut results = Vec::with_capacity(commands.len());
+    for command in commands {
+        results.push(execute_boxed(command, scope).await?);
+    }
+    Ok(join(results))
+}
+
+/// [`execute`] numa `BoxFuture`, para a recursão dos blocos.
+fn execute_boxed<'a>(command: &'a Command, scope: &'a mut Scope) -> BoxFuture<'a, Result<String>> {
+    Box::pin(execute(command, scope))
+}
+
+/// Executa um comando imperativo já resolvido.
+///
+/// Cada comando registra seus eventos na timeline; consultas registram um
+/// `ImperativeExecuted` com o texto canônico do comando. Os comandos que
+/// alteram o estado o fazem apenas por meio de eventos ([`replay::commit`]),
+/// validados e registrados sob [`replay::exclusive`]: `DEFINE` também
+/// inclui a entidade (`CONTRACT` ou `IDEA`) no registro, e `REVERT`
+/// registra os eventos de compensação (ver [`revert::revert`]).
+pub async fn dispatch(kind: &ImperativeKind) -> Result<String> {
+    match kind {
+        ImperativeKind::DefineContract { id, clauses } => {
+            replay::exclusive(async {
+                if view::get_contract(id).await.is_ok() {
+                    bail!("Falha ao criar contrato: Contrato com ID '{}' já existe", id);
+                }
+                replay::commit(EventKind::ContractRegistered { id: id.clone(), clauses: clauses.clone() }).await
+            })
+            .await?;
+            Ok(format!("Contrato registrado: {} ({} cláusulas)", id, clauses.len()))
+        }
+        ImperativeKind::DefineIdea { id, text } => {
+            replay::exclusive(async {
+                if view::get_idea(id).await.is_ok() {
+                    bail!("Falha ao criar ideia: Ideia com ID '{}' já existe", id);
+                }
+                replay::commit(EventKind::IdeaRegistered { id: id.clone(), text: text.clone() }).await
+            })
+            .await?;
+            Ok(format!("Ideia registrada: {}", id))
+        }
+        ImperativeKind::SimulateEntity { id, rounds } => {
+            // Entidades ainda desconhecidas entram no registro com o tipo genérico
+            replay::exclusive(async {
+                if view::fetch_entity(id).await.is_err() {
+                    let entity_type = "ENTITY".to_string();
+                    replay::commit(EventKind::EntityRegistered { id: id.clone(), entity_type }).await?;
+                }
+                Ok::<_, anyhow::Error>(())
+            })
+            .await?;
+            // Os resultados da simulação são gravados pelo próprio simulate
+            if view::dry_run() {
+                timeline::append(EventKind::SimulationCompleted { id: id.clone(), rounds: *rounds }).await?;
+                return Ok(format!("Simulação de {} não executada no plano: {} rodadas", id, rounds));
+            }
+            simulate::run_simulation(Runtime::current().registry(), id, *rounds)
+                .await
+                .map_err(|e| anyhow!("Falha na simulação: {}", e))?;
+            timeline::append(EventKind::SimulationCompleted { id: id.clone(), rounds: *rounds }).await?;
+            Ok(format!("Simulação de {} concluída: {} rodadas", id, rounds))
+        }
+        ImperativeKind::Orchestrate { mode, concurrency, steps } => orchestrator::run(mode, *concurrency, steps).await,
+        ImperativeKind::InvokeRuleset { entity_id, ruleset_id } => {
+            let verdict = ruleset::apply_ruleset(ruleset_id, entity_id)
+                .await
+                .map_err(|e| anyhow!("Falha ao aplicar o ruleset '{}': {}", ruleset_id, e))?;
+            let message = format!("Veredicto de {} sobre {}: {:?}", ruleset_id, entity_id, verdict);
+            timeline::append(EventKind::RuleVerdict { rule: ruleset_id.clone(), verdict }).await?;
+            Ok(message)
+        }
+        ImperativeKind::AmendContract { .. }
+        | ImperativeKind::TagIdea { .. }
+        | ImperativeKind::UpdateIdea { .. }
+        | ImperativeKind::RetireEntity { .. } => {
+            // A alteração já é registrada na timeline por `mutations::apply`
+            let event = mutations::apply(kind).await?;
+            let message = match &event {
+                EventKind::ContractAmended { id, clauses } => format!("Contrato emendado: {} ({} cláusulas)", id, clauses.len()),
+                EventKind::IdeaTagged { id, tags } => format!("Tags adicionadas à ideia {}: {}", id, tags.join(", ")),
+                EventKind::IdeaUpdated { id, .. } => format!("Ideia atualizada: {}", id),
+                EventKind::EntityRetired { id } => format!("Entidade removida: {}", id),
+                other => format!("{:?}", other),
+            };
+            Ok(message)
+        }
+        ImperativeKind::Revert { event_id } => {
+            let event_id = Uuid::parse_str(event_id)
+                .map_err(|_| anyhow!("Identificador de evento inválido: {}", event_id))?;
+            let compensations = revert::revert(event_id).await?;
+            Ok(format!("Evento {} revertido: {} eventos de compensação", event_id, compensations.len()))
+        }
+        ImperativeKind::ShowContract { .. }
+        | ImperativeKind::ListIdeas { .. }
+        | ImperativeKind::FindSpans { .. }
+        | ImperativeKind::ShowSimulation { .. } => {
+            let result = query::run_query(kind).await?;
+            let command = Imperative { kind: kind.clone() }.to_string();
+            timeline::append(EventKind::ImperativeExecuted { kind: command }).await?;
+            Ok(serde_json::to_string_pretty(&result)?)
+        }
+    }
+}
+
+/// Junta os resultados não vazios, um por linha.
+fn join(results: Vec<String>) -> String {
+    results.into_iter().filter(|r| !r.is_empty()).collect::<Vec<_>>().join("\n")
+}
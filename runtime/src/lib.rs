@@ -0,0 +1,221 @@
+// === File: runtime/src/lib.rs ===
+/*
+    Description: Runtime principal do LogLine, para orquestração e execução de comandos.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+pub mod boot;
+mod context;
+pub mod control;
+pub mod cron;
+pub mod entities;
+pub mod events;
+pub mod executor;
+pub mod mutations;
+pub mod orchestrator;
+pub mod policy;
+pub mod query;
+pub mod replay;
+pub mod revert;
+pub mod scheduler;
+pub mod snapshot;
+pub mod state;
+pub mod store;
+pub mod timeline;
+pub mod trace;
+pub mod transaction;
+pub mod vars;
+pub mod view;
+
+use std::path::Path;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use events::{Event, EventKind};
+use policy::ExecutionPolicy;
+use trace::TraceContext;
+use vars::Scope;
+
+pub use boot::boot;
+pub use context::Runtime;
+pub use registry::Registry;
+
+/// Variável de ambiente com o caminho do arquivo da timeline.
+pub const TIMELINE_PATH_ENV: &str = "LOGLINE_TIMELINE_PATH";
+
+/// Variável de ambiente com o caminho do arquivo do registro de entidades.
+pub const REGISTRY_PATH_ENV: &str = "LOGLINE_REGISTRY_PATH";
+
+/// Inicializa o runtime, registrando o início do ciclo de vida na timeline.
+///
+/// Se `LOGLINE_TIMELINE_PATH` estiver definida, a timeline é persistida
+/// nesse arquivo (ver [`init_with_timeline`]); caso contrário, fica apenas
+/// em memória. O registro de entidades é o da instância do runtime da
+/// tarefa atual (ver [`Runtime::from_env`]); se ele for persistido, a
+/// timeline também deve ser, ou a inicialização falha.
+pub async fn init() -> Result<()> {
+    match std::env::var(TIMELINE_PATH_ENV) {
+        Ok(path) if !path.is_empty() => init_with_timeline(path).await,
+        _ => {
+            require_timeline_file()?;
+            timeline::append(EventKind::RuntimeLifecycle { status: "initialized".to_string() }).await?;
+            info!("Runtime LogLine inicializado (timeline em memória)");
+            Ok(())
+        }
+    }
+}
+
+/// Inicializa o runtime com a timeline persistida no arquivo indicado.
+///
+/// O registro, os contratos e as ideias são reconstruídos antes de o
+/// runtime aceitar novos comandos: a partir do último snapshot, se houver,
+/// e dos eventos gravados depois dele.
+pub async fn init_with_timeline(path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let replayed = load_timeline(path).await?;
+    timeline::append(EventKind::RuntimeLifecycle { status: "initialized".to_string() }).await?;
+    info!("Runtime LogLine inicializado: {} eventos reaplicados de {}", replayed, path.display());
+    Ok(())
+}
+
+/// Reconstrói o estado a partir da timeline de `LOGLINE_TIMELINE_PATH`, se
+/// definida, como [`init`], mas sem registrar o início do ciclo de vida.
+/// Como em [`init`], o registro persistido exige a timeline em arquivo.
+/// Serve para planos (ver [`plan_command`]) sobre o estado persistido.
+pub async fn load() -> Result<()> {
+    match std::env::var(TIMELINE_PATH_ENV) {
+        Ok(path) if !path.is_empty() => load_timeline(Path::new(&path)).await.map(|_| ()),
+        _ => require_timeline_file(),
+    }
+}
+
+/// Falha se o registro de entidades da instância for persistido.
+///
+/// O registro é uma projeção da timeline: com ela apenas em memória, o
+/// estado recomeçaria vazio a cada processo, e os comandos regravariam as
+/// entidades persistidas a partir dele, voltando-as à versão 1.
+fn require_timeline_file() -> Result<()> {
+    if Runtime::current().registry().is_durable() {
+        bail!("O registro persistido ({}) exige a timeline em arquivo ({})", REGISTRY_PATH_ENV, TIMELINE_PATH_ENV);
+    }
+    Ok(())
+}
+
+/// Associa a timeline ao arquivo, cujo snapshot e eventos seguintes
+/// substituem o estado, e recarrega o registro a partir dele. Devolve o
+/// número de eventos reaplicados.
+async fn load_timeline(path: &Path) -> Result<usize> {
+    let (_, events) = timeline::attach(path, store::DEFAULT_SYNC_BATCH, snapshot::DEFAULT_SNAPSHOT_INTERVAL).await?;
+    replay::rebuild().await?;
+    Ok(events.len())
+}
+
+/// Encerra o runtime, registrando o fim do ciclo de vida na timeline e
+/// levando ao disco os eventos pendentes.
+pub async fn shutdown() -> Result<()> {
+    timeline::append(EventKind::RuntimeLifecycle { status: "shutdown".to_string() }).await?;
+    timeline::sync().await?;
+    info!("Runtime LogLine encerrado");
+    Ok(())
+}
+
+/// Processa um comando ou script LogLine e devolve a descrição do resultado.
+///
+/// Os comandos são executados em ordem, num escopo de variáveis novo, e o
+/// resultado de cada um ocupa uma linha. Erros de sintaxe são devolvidos
+/// como [`parser::Error`] (acessível com `downcast_ref`); tanto eles quanto
+/// os erros de execução são registrados na timeline como `ErrorOccurred`,
+/// uma única vez por transação desfeita (ver [`transaction`]).
+///
+/// Os eventos registrados formam um trace (ver [`trace`]) em nome do ator
+/// padrão. Chamado dentro de outro trace, o comando continua nele.
+pub async fn process_command(input: &str) -> Result<String> {
+    let context = trace::current().unwrap_or_else(|| TraceContext::new(vec![trace::DEFAULT_ACTOR.to_string()]));
+    trace::scope(context, run(input, None)).await
+}
+
+/// Como [`process_command`], registrando os eventos em nome dos atores
+/// dados.
+pub async fn process_command_as(input: &str, actors: Vec<String>) -> Result<String> {
+    let context = match trace::current() {
+        Some(current) => TraceContext { actors, ..current },
+        None => TraceContext::new(actors),
+    };
+    trace::scope(context, run(input, None)).await
+}
+
+/// Como [`process_command`], executando o script inteiro sob a política
+/// dada, como um bloco `WITH` (ver [`policy::execute`]).
+pub async fn process_command_with_policy(input: &str, policy: &ExecutionPolicy) -> Result<String> {
+    let context = trace::current().unwrap_or_else(|| TraceContext::new(vec![trace::DEFAULT_ACTOR.to_string()]));
+    trace::scope(context, run(input, Some(policy))).await
+}
+
+/// Comando executado como plano (ver [`plan_command`]).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Plan {
+    /// Resultado que o comando teria
+    pub output: Option<String>,
+    /// Erro com que o comando falharia
+    pub error: Option<String>,
+    /// Eventos que seriam registrados, em ordem, inclusive o
+    /// `ErrorOccurred` de uma falha
+    pub events: Vec<Event>,
+}
+
+/// Executa um comando ou script LogLine sem persistir nada e devolve o
+/// plano: o resultado que teria e os eventos que registraria.
+///
+/// O comando lê e altera uma cópia sob demanda do registro, dos contratos e
+/// das ideias (ver [`view::plan`]), de modo que cada passo vê o efeito dos
+/// anteriores. Os veredictos de rulesets são avaliados normalmente; as
+/// simulações não são executadas, pois gravam os próprios resultados.
+///
+/// Erros de sintaxe são devolvidos como em [`process_command`]; erros de
+/// execução fazem parte do plano.
+pub async fn plan_command(input: &str) -> Result<Plan> {
+    let context = trace::current().unwrap_or_else(|| TraceContext::new(vec![trace::DEFAULT_ACTOR.to_string()]));
+    let (result, events) = view::plan(trace::scope(context, run(input, None))).await;
+    match result {
+        Ok(output) => Ok(Plan { output: Some(output), error: None, events }),
+        Err(e) if e.downcast_ref::<parser::Error>().is_some() => Err(e),
+        Err(e) => Ok(Plan { output: None, error: Some(e.to_string()), events }),
+    }
+}
+
+async fn run(input: &str, policy: Option<&ExecutionPolicy>) -> Result<String> {
+    let commands = match parser::parse_program(input) {
+        Ok(commands) => commands,
+        Err(e) => {
+            record_error("parsing", &e.to_string()).await?;
+            return Err(e.into());
+        }
+    };
+
+    let mut scope = Scope::new();
+    let result = match policy {
+        Some(policy) => policy::execute(policy, &commands, &mut scope).await,
+        None => executor::execute_block(&commands, &mut scope).await,
+    };
+    match result {
+        Ok(result) => Ok(result),
+        // O erro da transação já foi registrado por ela
+        Err(e) if e.downcast_ref::<transaction::Aborted>().is_some() => Err(e),
+        Err(e) => {
+            record_error("execution", &e.to_string()).await?;
+            Err(e)
+        }
+    }
+}
+
+async fn record_error(context: &str, message: &str) -> Result<()> {
+    error!("Erro de {}: {}", context, message);
+    timeline::append(EventKind::ErrorOccurred { context: context.to_string(), message: message.to_string() }).await?;
+    Ok(())
+}
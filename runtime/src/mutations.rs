@@ -0,0 +1,68 @@
+// === File: runtime/src/mutations.rs ===
+/*
+    Description: Execução dos comandos que alteram contratos, ideias e entidades existentes.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{anyhow, bail, Result};
+use parser::ast::ImperativeKind;
+use crate::events::EventKind;
+use crate::{replay, view};
+
+/// Valida uma alteração e registra o evento correspondente na timeline (ver
+/// [`replay::commit`]), sem outros registros entre os dois passos (ver
+/// [`replay::exclusive`]).
+///
+/// * `AMEND CONTRACT` substitui as cláusulas do contrato;
+/// * `TAG IDEA` adiciona tags à ideia, sem repetir as existentes;
+/// * `UPDATE IDEA` substitui o texto da ideia;
+/// * `RETIRE ENTITY` remove a entidade do registro. Se ela for um contrato
+///   ou uma ideia, um `ContractDeleted` ou `IdeaDeleted` o remove antes.
+///
+/// Alterar um item inexistente é um erro. As interpolações do comando já
+/// devem estar resolvidas. Devolve o evento principal da alteração.
+pub async fn apply(kind: &ImperativeKind) -> Result<EventKind> {
+    replay::exclusive(validate_and_commit(kind)).await
+}
+
+async fn validate_and_commit(kind: &ImperativeKind) -> Result<EventKind> {
+    let event = match kind {
+        ImperativeKind::AmendContract { id, clauses } => {
+            view::get_contract(id).await.map_err(|e| anyhow!("Falha ao emendar contrato: {}", e))?;
+            EventKind::ContractAmended { id: id.clone(), clauses: clauses.clone() }
+        }
+        ImperativeKind::TagIdea { id, tags } => {
+            view::get_idea(id).await.map_err(|e| anyhow!("Falha ao adicionar tags: {}", e))?;
+            EventKind::IdeaTagged { id: id.clone(), tags: tags.clone() }
+        }
+        ImperativeKind::UpdateIdea { id, text } => {
+            view::get_idea(id).await.map_err(|e| anyhow!("Falha ao atualizar ideia: {}", e))?;
+            EventKind::IdeaUpdated { id: id.clone(), text: text.clone() }
+        }
+        ImperativeKind::RetireEntity { id } => {
+            let (_, entity_type) = view::fetch_entity(id)
+                .await
+                .map_err(|_| anyhow!("Entidade não encontrada: {}", id))?;
+            match entity_type.as_str() {
+                "CONTRACT" if view::get_contract(id).await.is_ok() => {
+                    replay::commit(EventKind::ContractDeleted { id: id.clone() }).await?;
+                }
+                "IDEA" if view::get_idea(id).await.is_ok() => {
+                    replay::commit(EventKind::IdeaDeleted { id: id.clone() }).await?;
+                }
+                _ => {}
+            }
+            EventKind::EntityRetired { id: id.clone() }
+        }
+        other => bail!("Comando não é uma alteração: {:?}", other),
+    };
+
+    replay::commit(event.clone()).await?;
+    Ok(event)
+}
@@ -0,0 +1,232 @@
+// === File: runtime/src/orchestrator.rs ===
+/*
+    Description: Orquestrador do ORCHESTRATE: executa os passos de um bloco em sequência, em paralelo limitado ou pelo grafo de dependências.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+use anyhow::{anyhow, bail, Result};
+use futures::future::BoxFuture;
+use parser::ast::{ImperativeKind, Step};
+use tokio::task::JoinSet;
+use crate::events::EventKind;
+use crate::{executor, timeline, trace, view, Runtime};
+
+/// Modo de execução dos passos de uma orquestração.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Um passo por vez, na ordem do bloco.
+    Sequential,
+    /// Todos os passos ao mesmo tempo, até o limite de concorrência.
+    Parallel,
+    /// Cada passo assim que os passos de que depende terminam.
+    Dag,
+}
+
+impl FromStr for Mode {
+    type Err = anyhow::Error;
+
+    fn from_str(mode: &str) -> Result<Self> {
+        match mode.to_lowercase().as_str() {
+            "sequential" | "sequencial" | "seq" => Ok(Mode::Sequential),
+            "parallel" | "paralelo" => Ok(Mode::Parallel),
+            "dag" => Ok(Mode::Dag),
+            _ => bail!("Modo de orquestração desconhecido: {} (use sequential, parallel ou dag)", mode),
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Mode::Sequential => "sequential",
+            Mode::Parallel => "parallel",
+            Mode::Dag => "dag",
+        })
+    }
+}
+
+/// Executa os passos de um `ORCHESTRATE` e devolve o resultado de cada um,
+/// um por linha, seguido do resumo da orquestração.
+///
+/// Registra um `OrchestrationStarted` com o limite de concorrência e, ao
+/// final, um `OrchestrationCompleted` com a maior quantidade de passos que
+/// de fato rodaram ao mesmo tempo e a duração. Os eventos dos passos são
+/// filhos do início da orquestração (ver [`crate::trace`]). No primeiro
+/// passo que falhar, nenhum outro é iniciado; os que já estão em execução
+/// terminam e o erro é devolvido.
+///
+/// Sem limite informado, `parallel` e `dag` executam todos os passos
+/// prontos ao mesmo tempo. `DEPENDS ON` só é aceito no modo `dag`.
+pub async fn run(mode: &str, concurrency: Option<usize>, steps: &[Step]) -> Result<String> {
+    let mode: Mode = mode.parse()?;
+    let dependencies = plan(mode, steps)?;
+    let limit = match (mode, concurrency) {
+        (_, Some(0)) => bail!("Concorrência da orquestração deve ser maior que zero"),
+        (Mode::Sequential, _) => 1,
+        (_, Some(limit)) => limit,
+        (_, None) => steps.len().max(1),
+    };
+
+    let started = Instant::now();
+    let start = timeline::append(EventKind::OrchestrationStarted { mode: mode.to_string(), concurrency: limit }).await?;
+    let (results, peak) = trace::child_of(&start, schedule(steps, &dependencies, limit)).await?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    trace::child_of(
+        &start,
+        timeline::append(EventKind::OrchestrationCompleted { mode: mode.to_string(), concurrency: peak, duration_ms }),
+    )
+    .await?;
+
+    let mut lines: Vec<String> = results.into_iter().filter(|r| !r.is_empty()).collect();
+    lines.push(format!(
+        "Orquestração {} concluída: {} passos em {} ms (concorrência máxima {})",
+        mode,
+        steps.len(),
+        duration_ms,
+        peak
+    ));
+    Ok(lines.join("\n"))
+}
+
+/// Dependências de cada passo, por índice.
+///
+/// No modo `sequential` cada passo depende do anterior; no `parallel`,
+/// de nenhum; no `dag`, dos passos nomeados em `DEPENDS ON`. Falha com
+/// nomes repetidos, dependências desconhecidas ou ciclos.
+fn plan(mode: Mode, steps: &[Step]) -> Result<Vec<Vec<usize>>> {
+    if mode != Mode::Dag {
+        if let Some(step) = steps.iter().find(|step| !step.depends_on.is_empty()) {
+            bail!("DEPENDS ON só é aceito no modo dag (passo {})", label(step, 0));
+        }
+        return Ok(match mode {
+            Mode::Sequential => (0..steps.len()).map(|i| if i == 0 { vec![] } else { vec![i - 1] }).collect(),
+            _ => vec![Vec::new(); steps.len()],
+        });
+    }
+
+    let mut names = HashMap::new();
+    for (index, step) in steps.iter().enumerate() {
+        if let Some(name) = &step.name {
+            if names.insert(name.as_str(), index).is_some() {
+                bail!("Passo repetido na orquestração: {}", name);
+            }
+        }
+    }
+    let dependencies = steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            step.depends_on
+                .iter()
+                .map(|dependency| {
+                    names.get(dependency.as_str()).copied().ok_or_else(|| {
+                        anyhow!("Passo {} depende de um passo inexistente: {}", label(step, index), dependency)
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Ordenação topológica: se sobrar algum passo, há um ciclo
+    let mut pending: Vec<usize> = dependencies.iter().map(Vec::len).collect();
+    let mut ready: Vec<usize> = (0..steps.len()).filter(|&i| pending[i] == 0).collect();
+    let mut visited = 0;
+    while let Some(done) = ready.pop() {
+        visited += 1;
+        for (index, deps) in dependencies.iter().enumerate() {
+            for _ in deps.iter().filter(|&&d| d == done) {
+                pending[index] -= 1;
+                if pending[index] == 0 {
+                    ready.push(index);
+                }
+            }
+        }
+    }
+    if visited < steps.len() {
+        let cycle: Vec<String> = (0..steps.len()).filter(|&i| pending[i] > 0).map(|i| label(&steps[i], i)).collect();
+        bail!("Ciclo de dependências entre os passos: {}", cycle.join(", "));
+    }
+    Ok(dependencies)
+}
+
+/// Executa os passos respeitando as dependências e o limite de
+/// concorrência. Devolve os resultados, na ordem dos passos, e a maior
+/// quantidade de passos executados ao mesmo tempo.
+async fn schedule(steps: &[Step], dependencies: &[Vec<usize>], limit: usize) -> Result<(Vec<String>, usize)> {
+    let mut pending: Vec<usize> = dependencies.iter().map(Vec::len).collect();
+    let mut ready: BTreeSet<usize> = (0..steps.len()).filter(|&i| pending[i] == 0).collect();
+    let mut results = vec![String::new(); steps.len()];
+    let mut running = JoinSet::new();
+    let mut peak = 0;
+    let mut failure = None;
+
+    loop {
+        while failure.is_none() && running.len() < limit {
+            let Some(index) = ready.pop_first() else { break };
+            let context = trace::current();
+            let runtime = Runtime::current();
+            let step = view::scope(view::current(), spawn_step(steps[index].imperative.kind.clone()));
+            running.spawn(async move {
+                let step = runtime.scope(step);
+                let result = match context {
+                    Some(context) => trace::scope(context, step).await,
+                    None => step.await,
+                };
+                (index, result)
+            });
+            peak = peak.max(running.len());
+        }
+
+        let Some(joined) = running.join_next().await else { break };
+        let (index, result) = joined.map_err(|e| anyhow!("Passo da orquestração interrompido: {}", e))?;
+        match result {
+            Ok(output) => {
+                results[index] = output;
+                for (dependent, deps) in dependencies.iter().enumerate() {
+                    for _ in deps.iter().filter(|&&d| d == index) {
+                        pending[dependent] -= 1;
+                        if pending[dependent] == 0 {
+                            ready.insert(dependent);
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                if failure.is_none() {
+                    failure = Some(anyhow!("Passo {} falhou: {}", label(&steps[index], index), e));
+                }
+            }
+        }
+    }
+
+    match failure {
+        Some(e) => Err(e),
+        None => Ok((results, peak)),
+    }
+}
+
+/// Executa o comando de um passo numa tarefa própria.
+///
+/// O tipo explícito quebra a recursão entre [`executor::dispatch`] e o
+/// orquestrador, que pode executar outro `ORCHESTRATE` como passo.
+fn spawn_step(kind: ImperativeKind) -> BoxFuture<'static, Result<String>> {
+    Box::pin(async move { executor::dispatch(&kind).await })
+}
+
+/// Nome do passo para mensagens: o nome declarado ou a posição no bloco.
+fn label(step: &Step, index: usize) -> String {
+    match &step.name {
+        Some(name) => name.clone(),
+        None => format!("#{}", index + 1),
+    }
+}
@@ -0,0 +1,138 @@
+// === File: runtime/src/policy.rs ===
+/*
+    Description: Política de execução dos blocos WITH: tempo limite por tentativa, novas tentativas com espera crescente e tratamento de falha.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, Result};
+use parser::ast::{Command, Policy};
+use tracing::warn;
+use crate::events::EventKind;
+use crate::vars::Scope;
+use crate::{executor, timeline, trace};
+
+/// Maior espera entre duas tentativas.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Política de execução de um bloco de comandos.
+///
+/// Corresponde às entradas `timeouts.execution`, `retry_strategy` e
+/// `on_failure` dos spans `.lll`. A política padrão executa o bloco uma
+/// vez, sem tempo limite nem tratamento de falha.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutionPolicy {
+    /// Tempo limite de cada tentativa.
+    pub timeout: Option<Duration>,
+    /// Novas tentativas depois da primeira falha.
+    pub retries: usize,
+    /// Espera antes da primeira nova tentativa; dobra a cada tentativa
+    /// seguinte, até [`MAX_BACKOFF`].
+    pub backoff: Duration,
+    /// Comandos executados quando todas as tentativas falham.
+    pub on_failure: Vec<Command>,
+}
+
+impl ExecutionPolicy {
+    /// Política de um comando `WITH`.
+    pub fn from_ast(policy: &Policy, on_failure: Option<&[Command]>) -> Self {
+        Self {
+            timeout: policy.timeout_ms.map(Duration::from_millis),
+            retries: policy.retries,
+            backoff: policy.backoff_ms.map(Duration::from_millis).unwrap_or_default(),
+            on_failure: on_failure.map(<[Command]>::to_vec).unwrap_or_default(),
+        }
+    }
+
+    /// Número total de tentativas.
+    pub fn max_attempts(&self) -> usize {
+        self.retries.saturating_add(1)
+    }
+
+    /// Espera antes da tentativa `attempt` (base 1): nenhuma antes da
+    /// primeira e, a partir da segunda, `backoff` dobrado a cada tentativa.
+    pub fn delay_before(&self, attempt: usize) -> Duration {
+        if attempt <= 1 {
+            return Duration::ZERO;
+        }
+        let doublings = (attempt - 2).min(31) as u32;
+        self.backoff.saturating_mul(1 << doublings).min(MAX_BACKOFF)
+    }
+}
+
+/// Executa um bloco sob a política dada e devolve o resultado da tentativa
+/// que teve sucesso.
+///
+/// Cada tentativa começa de uma cópia do escopo, que só substitui o
+/// original se a tentativa tiver sucesso, e é registrada ao final como um
+/// `ExecutionAttempt`. Uma tentativa que excede o tempo limite é
+/// interrompida no próximo ponto de espera; os eventos que ela já registrou
+/// permanecem na timeline.
+///
+/// Se todas as tentativas falharem, os comandos de `on_failure` são
+/// executados no escopo original, como filhos da última tentativa (ver
+/// [`crate::trace`]), e o resultado deles é devolvido. Sem tratamento de
+/// falha, o erro da última tentativa é devolvido.
+pub async fn execute(policy: &ExecutionPolicy, body: &[Command], scope: &mut Scope) -> Result<String> {
+    let max_attempts = policy.max_attempts();
+    let mut attempt = 0;
+
+    let (last, error) = loop {
+        attempt += 1;
+        tokio::time::sleep(policy.delay_before(attempt)).await;
+
+        let mut attempt_scope = scope.clone();
+        let started = Instant::now();
+        let (status, result) = run_attempt(policy.timeout, body, &mut attempt_scope).await;
+        let event = timeline::append(EventKind::ExecutionAttempt {
+            attempt,
+            max_attempts,
+            status: status.to_string(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.as_ref().err().map(|e| e.to_string()),
+        })
+        .await?;
+
+        match result {
+            Ok(output) => {
+                *scope = attempt_scope;
+                return Ok(output);
+            }
+            Err(e) if attempt >= max_attempts => break (event, e),
+            Err(e) => warn!("Tentativa {} de {} falhou: {}", attempt, max_attempts, e),
+        }
+    };
+
+    if policy.on_failure.is_empty() {
+        return Err(error);
+    }
+    trace::child_of(&last, executor::execute_block(&policy.on_failure, scope))
+        .await
+        .map_err(|e| anyhow!("Tratamento de falha falhou: {} (erro original: {})", e, error))
+}
+
+/// Executa uma tentativa e devolve seu status com o resultado.
+///
+/// Uma tentativa cancelada pelo tempo limite no meio de um registro não
+/// deixa o registro de entidades à frente da timeline (ver
+/// [`crate::replay::Projection`]).
+async fn run_attempt(timeout: Option<Duration>, body: &[Command], scope: &mut Scope) -> (&'static str, Result<String>) {
+    let run = executor::execute_block(body, scope);
+    let result = match timeout {
+        Some(limit) => match tokio::time::timeout(limit, run).await {
+            Ok(result) => result,
+            Err(_) => return ("timed_out", Err(anyhow!("Tempo limite de {} ms excedido", limit.as_millis()))),
+        },
+        None => run.await,
+    };
+    match result {
+        Ok(output) => ("succeeded", Ok(output)),
+        Err(e) => ("failed", Err(e)),
+    }
+}
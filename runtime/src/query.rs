@@ -0,0 +1,79 @@
+// === File: runtime/src/query.rs ===
+/*
+    Description: Execução das consultas SHOW, LIST e FIND da linguagem LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{anyhow, bail, Result};
+use datatrack::SpanFilter;
+use parser::ast::ImperativeKind;
+use serde_json::{json, Value};
+use crate::view;
+
+/// Número de resultados de `SHOW SIMULATION` quando `LAST` é omitido.
+pub const DEFAULT_SIMULATION_RESULTS: usize = 10;
+
+/// Executa uma consulta e devolve o resultado em JSON.
+///
+/// * `SHOW CONTRACT` devolve o objeto do contrato;
+/// * `LIST IDEAS` devolve a lista de ideias, ordenada por ID;
+/// * `FIND SPANS` devolve os spans do datatrack;
+/// * `SHOW SIMULATION` devolve os resultados mais recentes da entidade.
+///
+/// As interpolações do comando já devem estar resolvidas.
+pub async fn run_query(kind: &ImperativeKind) -> Result<Value> {
+    match kind {
+        ImperativeKind::ShowContract { id } => {
+            let (id, clauses, created_at) = view::get_contract(id)
+                .await
+                .map_err(|e| anyhow!("Falha ao consultar contrato: {}", e))?;
+            Ok(json!({
+                "id": id,
+                "clauses": clauses,
+                "created_at": created_at.to_rfc3339(),
+            }))
+        }
+        ImperativeKind::ListIdeas { tag } => {
+            let mut ids = view::list_ideas().await;
+            ids.sort();
+
+            let mut found = Vec::new();
+            for id in ids {
+                let tags = view::get_tags(&id).await.map_err(|e| anyhow!("Falha ao consultar ideia: {}", e))?;
+                if tag.as_ref().is_some_and(|tag| !tags.contains(tag)) {
+                    continue;
+                }
+                let (id, text, created_at) = view::get_idea(&id)
+                    .await
+                    .map_err(|e| anyhow!("Falha ao consultar ideia: {}", e))?;
+                found.push(json!({
+                    "id": id,
+                    "text": text,
+                    "tags": tags,
+                    "created_at": created_at.to_rfc3339(),
+                }));
+            }
+            Ok(Value::Array(found))
+        }
+        ImperativeKind::FindSpans { kind, limit } => {
+            let filter = SpanFilter { kind: kind.clone(), limit: *limit };
+            let spans = datatrack::fetch_spans(filter)
+                .await
+                .map_err(|e| anyhow!("Falha ao buscar spans: {}", e))?;
+            Ok(serde_json::to_value(spans)?)
+        }
+        ImperativeKind::ShowSimulation { entity_id, last } => {
+            let results = simulate::get_simulation_results(entity_id, last.unwrap_or(DEFAULT_SIMULATION_RESULTS))
+                .await
+                .map_err(|e| anyhow!("Falha ao consultar simulações: {}", e))?;
+            Ok(serde_json::to_value(results)?)
+        }
+        other => bail!("Comando não é uma consulta: {:?}", other),
+    }
+}
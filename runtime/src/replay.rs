@@ -0,0 +1,211 @@
+// === File: runtime/src/replay.rs ===
+/*
+    Description: Registro dos eventos que alteram o estado e projeção deles sobre o registro de entidades.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::future::Future;
+use anyhow::{anyhow, Result};
+use registry::{Entity, Registry, RegistryError};
+use tracing::warn;
+use crate::events::{Event, EventKind};
+use crate::state::{EntityState, State};
+use crate::{timeline, view, Runtime};
+
+/// Executa `future`, que valida comandos contra o estado e registra os
+/// eventos deles, sem que outro registro aconteça no meio.
+///
+/// Sem o lock, um comando concorrente poderia invalidar a validação antes
+/// do registro: duas definições do mesmo contrato, por exemplo, passariam
+/// ambas. Num plano ou transação (ver [`view::planning`]) o lock não é
+/// tomado: os eventos vão para o plano, e a transação já o detém até
+/// registrá-los (ver [`crate::transaction::execute`]).
+pub async fn exclusive<F: Future>(future: F) -> F::Output {
+    if view::planning() {
+        return future.await;
+    }
+    let _commit = Runtime::current().commits().lock_owned().await;
+    future.await
+}
+
+/// Registra um evento que altera o estado.
+///
+/// É a única forma de o runtime alterar os contratos, as ideias e as
+/// entidades: o estado é a dobra da timeline, e o registro de entidades,
+/// uma projeção dela (ver [`timeline::append`]). O evento deve ter sido
+/// validado antes, sob [`exclusive`]. Durante um plano, o efeito vai
+/// apenas para a cópia do plano (ver [`view::plan`]).
+pub async fn commit(kind: EventKind) -> Result<Event> {
+    let event = timeline::append(kind).await?;
+    if view::planning() {
+        view::apply(&event).await?;
+    }
+    Ok(event)
+}
+
+/// Registra de uma vez os eventos de uma transação (ver
+/// [`timeline::append_all`]): todos têm efeito, ou nenhum.
+///
+/// Os eventos já foram validados contra a cópia do estado em que a
+/// transação executou, sob [`exclusive`].
+pub async fn commit_all(events: Vec<Event>) -> Result<Vec<Event>> {
+    if events.is_empty() {
+        return Ok(events);
+    }
+    timeline::append_all(events).await
+}
+
+/// Recarrega o registro de entidades com as do estado atual, descartando
+/// as que ele tinha. Serve para alinhar o registro a uma timeline lida do
+/// disco (ver [`timeline::attach`]).
+pub async fn rebuild() -> Result<()> {
+    let registry = Runtime::current().registry().clone();
+    let state = timeline::current_state().await?;
+    for entity in registry.list_entities(|_| true).await? {
+        registry.remove_entity(&entity.id).await?;
+    }
+    for entity in state.entities.values() {
+        registry
+            .put_entity(entity.to_entity())
+            .await
+            .map_err(|e| anyhow!("Falha ao registrar entidade '{}': {}", entity.id, e))?;
+    }
+    Ok(())
+}
+
+/// Efeito de eventos sobre o registro de entidades, desfeito ao ser
+/// descartado sem [`Projection::commit`].
+///
+/// Assim o registro não fica à frente da timeline quando o registro dos
+/// eventos falha, é cancelado no meio, como por um tempo limite (ver
+/// [`crate::policy`]), ou entra em pânico.
+pub(crate) struct Projection {
+    registry: Registry,
+    /// Entidades alteradas, em ordem, como estavam antes
+    previous: Vec<(String, Option<Entity>)>,
+    committed: bool,
+}
+
+/// Projeta sobre o registro de entidades eventos que seguem o estado
+/// `state`.
+///
+/// As entidades alteradas pelos eventos são gravadas como ficam no estado
+/// depois deles (ver [`crate::state::State::apply`]), com tipo, dono,
+/// metadados e versão, ou removidas. Se uma gravação falhar, as anteriores
+/// são desfeitas antes de o erro ser devolvido; se tudo der certo, elas são
+/// desfeitas quando a projeção for descartada sem [`Projection::commit`].
+pub(crate) async fn project(state: &State, events: &[Event]) -> Result<Projection> {
+    let mut ids: Vec<&str> = Vec::new();
+    for id in events.iter().filter_map(|event| entity_id(&event.kind)) {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
+    // Dobra os eventos apenas sobre as entidades que eles alteram
+    let mut after = State::default();
+    for id in &ids {
+        if let Some(entity) = state.entities.get(*id) {
+            after.entities.insert(id.to_string(), entity.clone());
+        }
+    }
+    let after = State::fold_from(after, events);
+
+    let mut projection = Projection {
+        registry: Runtime::current().registry().clone(),
+        previous: Vec::new(),
+        committed: false,
+    };
+    for id in ids {
+        projection.apply(id, after.entities.get(id).map(EntityState::to_entity)).await?;
+    }
+    Ok(projection)
+}
+
+/// Entidade alterada por um evento.
+fn entity_id(kind: &EventKind) -> Option<&str> {
+    match kind {
+        EventKind::ContractRegistered { id, .. }
+        | EventKind::IdeaRegistered { id, .. }
+        | EventKind::EntityRegistered { id, .. }
+        | EventKind::EntityCreated { id, .. }
+        | EventKind::EntityUpdated { id, .. }
+        | EventKind::EntityRetired { id } => Some(id),
+        EventKind::ContractAmended { .. }
+        | EventKind::IdeaTagged { .. }
+        | EventKind::IdeaUntagged { .. }
+        | EventKind::IdeaUpdated { .. }
+        | EventKind::ContractDeleted { .. }
+        | EventKind::IdeaDeleted { .. }
+        | EventKind::RuntimeLifecycle { .. }
+        | EventKind::ImperativeExecuted { .. }
+        | EventKind::EventReverted { .. }
+        | EventKind::TimelineCompacted { .. }
+        | EventKind::RuleVerdict { .. }
+        | EventKind::OrchestrationStarted { .. }
+        | EventKind::OrchestrationCompleted { .. }
+        | EventKind::ExecutionAttempt { .. }
+        | EventKind::BootSpan { .. }
+        | EventKind::TriggerArmed { .. }
+        | EventKind::CronExecution { .. }
+        | EventKind::CronCompletion { .. }
+        | EventKind::SimulationCompleted { .. }
+        | EventKind::ErrorOccurred { .. } => None,
+    }
+}
+
+impl Projection {
+    /// Grava a entidade como ficou, ou a remove, guardando a anterior.
+    async fn apply(&mut self, id: &str, entity: Option<Entity>) -> Result<()> {
+        let registry = &self.registry;
+        let previous = match registry.get_entity(id).await {
+            Ok(entity) => Some(entity),
+            Err(RegistryError::NotFound(_)) => None,
+            Err(e) => return Err(anyhow!("Falha ao consultar entidade '{}': {}", id, e)),
+        };
+        self.previous.push((id.to_string(), previous));
+        match entity {
+            Some(entity) => {
+                registry
+                    .put_entity(entity)
+                    .await
+                    .map_err(|e| anyhow!("Falha ao registrar entidade '{}': {}", id, e))?;
+            }
+            None => {
+                registry
+                    .remove_entity(id)
+                    .await
+                    .map_err(|e| anyhow!("Falha ao remover entidade '{}': {}", id, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Mantém as alterações no registro, depois que os eventos foram
+    /// registrados na timeline.
+    pub(crate) fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for Projection {
+    /// Devolve as entidades alteradas ao que eram, da última à primeira,
+    /// se a projeção não foi mantida. Uma falha aqui é apenas registrada no
+    /// log.
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        for (id, previous) in self.previous.drain(..).rev() {
+            if let Err(e) = self.registry.restore(&id, previous) {
+                warn!("Falha ao desfazer a alteração da entidade '{}': {}", id, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+// === File: runtime/src/revert.rs ===
+/*
+    Description: Reversão de eventos da timeline por eventos de compensação, sem apagar o histórico.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{anyhow, bail, Result};
+use uuid::Uuid;
+use crate::events::{Event, EventKind};
+use crate::state::{EntityState, State};
+use crate::{replay, timeline};
+
+/// Reverte um evento da timeline.
+///
+/// Os eventos de compensação (ver [`compensations`]) são registrados e
+/// aplicados em ordem, seguidos de um `EventReverted` que guarda o ID e o
+/// hash do evento revertido. Nada é removido da timeline. Eventos já
+/// cobertos por um snapshot não podem ser revertidos. Devolve os eventos de
+/// compensação.
+///
+/// A validação e os registros acontecem sob [`replay::exclusive`].
+pub async fn revert(event_id: Uuid) -> Result<Vec<Event>> {
+    replay::exclusive(revert_exclusive(event_id)).await
+}
+
+async fn revert_exclusive(event_id: Uuid) -> Result<Vec<Event>> {
+    let (snapshot, events) = timeline::history().await?;
+    let base = snapshot.map(|snapshot| snapshot.state).unwrap_or_default();
+    let kinds = compensations_from(&base, &events, event_id)?;
+    let event_hash = events
+        .iter()
+        .find(|event| event.id == event_id)
+        .map(|event| event.hash.clone())
+        .unwrap_or_default();
+
+    let mut applied = Vec::with_capacity(kinds.len());
+    for kind in kinds {
+        applied.push(replay::commit(kind).await?);
+    }
+    timeline::append(EventKind::EventReverted {
+        event_id,
+        event_hash,
+        compensations: applied.iter().map(|event| event.id).collect(),
+    })
+    .await?;
+    Ok(applied)
+}
+
+/// Calcula os eventos que desfazem o efeito de um evento da timeline.
+///
+/// O estado anterior ao evento é obtido pela dobra dos eventos que o
+/// precedem, e a compensação é validada contra o estado atual: reverter a
+/// emenda de um contrato removido depois, por exemplo, é um erro. Também
+/// falham eventos inexistentes, já revertidos, sem efeito sobre o estado e
+/// os próprios `EventReverted` (reverta os eventos de compensação).
+pub fn compensations(events: &[Event], event_id: Uuid) -> Result<Vec<EventKind>> {
+    compensations_from(&State::default(), events, event_id)
+}
+
+/// Como [`compensations`], para eventos que seguem o estado `base` (o de um
+/// snapshot).
+pub fn compensations_from(base: &State, events: &[Event], event_id: Uuid) -> Result<Vec<EventKind>> {
+    let index = events
+        .iter()
+        .position(|event| event.id == event_id)
+        .ok_or_else(|| anyhow!("Evento não encontrado: {}", event_id))?;
+    let already_reverted = events.iter().any(|event| {
+        matches!(&event.kind, EventKind::EventReverted { event_id: reverted, .. } if *reverted == event_id)
+    });
+    if already_reverted {
+        bail!("Evento {} já foi revertido", event_id);
+    }
+
+    let kind = &events[index].kind;
+    let before = State::fold_from(base.clone(), &events[..index]);
+    let now = State::fold_from(base.clone(), events);
+    let no_effect = || anyhow!("Evento {} ({}) não teve efeito sobre o estado", event_id, kind.name());
+
+    let compensations = match kind {
+        EventKind::ContractRegistered { id, .. } => {
+            require(now.contracts.contains_key(id), "Contrato", id)?;
+            match before.contracts.get(id) {
+                Some(previous) => vec![EventKind::ContractAmended { id: id.clone(), clauses: previous.clauses.clone() }],
+                None => {
+                    let mut kinds = vec![EventKind::ContractDeleted { id: id.clone() }];
+                    if now.entities.contains_key(id) {
+                        kinds.push(EventKind::EntityRetired { id: id.clone() });
+                    }
+                    kinds
+                }
+            }
+        }
+        EventKind::IdeaRegistered { id, .. } => {
+            require(now.ideas.contains_key(id), "Ideia", id)?;
+            match before.ideas.get(id) {
+                Some(previous) => restore_idea(id, &previous.text, &previous.tags, false),
+                None => {
+                    let mut kinds = vec![EventKind::IdeaDeleted { id: id.clone() }];
+                    if now.entities.contains_key(id) {
+                        kinds.push(EventKind::EntityRetired { id: id.clone() });
+                    }
+                    kinds
+                }
+            }
+        }
+        EventKind::ContractAmended { id, .. } => {
+            let previous = before.contracts.get(id).ok_or_else(no_effect)?;
+            require(now.contracts.contains_key(id), "Contrato", id)?;
+            vec![EventKind::ContractAmended { id: id.clone(), clauses: previous.clauses.clone() }]
+        }
+        EventKind::IdeaUpdated { id, .. } => {
+            let previous = before.ideas.get(id).ok_or_else(no_effect)?;
+            require(now.ideas.contains_key(id), "Ideia", id)?;
+            vec![EventKind::IdeaUpdated { id: id.clone(), text: previous.text.clone() }]
+        }
+        EventKind::IdeaTagged { id, tags } => {
+            let previous = before.ideas.get(id).ok_or_else(no_effect)?;
+            require(now.ideas.contains_key(id), "Ideia", id)?;
+            // Apenas as tags que o evento de fato adicionou
+            let mut added: Vec<String> = Vec::new();
+            for tag in tags {
+                if !previous.tags.contains(tag) && !added.contains(tag) {
+                    added.push(tag.clone());
+                }
+            }
+            if added.is_empty() {
+                return Err(no_effect());
+            }
+            vec![EventKind::IdeaUntagged { id: id.clone(), tags: added }]
+        }
+        EventKind::IdeaUntagged { id, tags } => {
+            let previous = before.ideas.get(id).ok_or_else(no_effect)?;
+            require(now.ideas.contains_key(id), "Ideia", id)?;
+            let removed: Vec<String> = previous.tags.iter().filter(|tag| tags.contains(tag)).cloned().collect();
+            if removed.is_empty() {
+                return Err(no_effect());
+            }
+            vec![EventKind::IdeaTagged { id: id.clone(), tags: removed }]
+        }
+        EventKind::ContractDeleted { id } => {
+            let previous = before.contracts.get(id).ok_or_else(no_effect)?;
+            if now.contracts.contains_key(id) {
+                bail!("Contrato '{}' já existe", id);
+            }
+            vec![EventKind::ContractRegistered { id: id.clone(), clauses: previous.clauses.clone() }]
+        }
+        EventKind::IdeaDeleted { id } => {
+            let previous = before.ideas.get(id).ok_or_else(no_effect)?;
+            if now.ideas.contains_key(id) {
+                bail!("Ideia '{}' já existe", id);
+            }
+            restore_idea(id, &previous.text, &previous.tags, true)
+        }
+        EventKind::EntityRegistered { id, .. } => {
+            require(now.entities.contains_key(id), "Entidade", id)?;
+            match before.entities.get(id) {
+                Some(previous) => vec![EventKind::EntityRegistered {
+                    id: id.clone(),
+                    entity_type: previous.entity_type.clone(),
+                }],
+                None => vec![EventKind::EntityRetired { id: id.clone() }],
+            }
+        }
+        EventKind::EntityCreated { id, .. } => {
+            require(now.entities.contains_key(id), "Entidade", id)?;
+            match before.entities.get(id) {
+                Some(previous) => vec![restore_entity(previous, &now.entities[id])],
+                None => vec![EventKind::EntityRetired { id: id.clone() }],
+            }
+        }
+        EventKind::EntityUpdated { id, .. } => {
+            let previous = before.entities.get(id).ok_or_else(no_effect)?;
+            require(now.entities.contains_key(id), "Entidade", id)?;
+            vec![restore_entity(previous, &now.entities[id])]
+        }
+        EventKind::EntityRetired { id } => {
+            let previous = before.entities.get(id).ok_or_else(no_effect)?;
+            if now.entities.contains_key(id) {
+                bail!("Entidade '{}' já existe", id);
+            }
+            vec![EventKind::EntityCreated {
+                id: id.clone(),
+                entity_type: previous.entity_type.clone(),
+                owner: previous.owner.clone(),
+                metadata: previous.metadata.clone(),
+                version: previous.version + 1,
+            }]
+        }
+        EventKind::EventReverted { .. } => {
+            bail!("Evento {} é uma reversão; reverta os eventos de compensação", event_id)
+        }
+        EventKind::CronExecution { .. } | EventKind::CronCompletion { .. } => {
+            bail!("Evento {} ({}) registra um disparo já executado e não pode ser revertido", event_id, kind.name())
+        }
+        EventKind::TriggerArmed { .. } => {
+            bail!("Evento {} ({}) registra o início de um gatilho e não pode ser revertido", event_id, kind.name())
+        }
+        EventKind::RuntimeLifecycle { .. }
+        | EventKind::ImperativeExecuted { .. }
+        | EventKind::TimelineCompacted { .. }
+        | EventKind::RuleVerdict { .. }
+        | EventKind::OrchestrationStarted { .. }
+        | EventKind::OrchestrationCompleted { .. }
+        | EventKind::ExecutionAttempt { .. }
+        | EventKind::BootSpan { .. }
+        | EventKind::SimulationCompleted { .. }
+        | EventKind::ErrorOccurred { .. } => {
+            bail!("Evento {} ({}) não altera o estado", event_id, kind.name())
+        }
+    };
+    Ok(compensations)
+}
+
+/// Falha se o item alterado pelo evento não existir mais.
+fn require(exists: bool, item: &str, id: &str) -> Result<()> {
+    if !exists {
+        bail!("{} '{}' não existe mais", item, id);
+    }
+    Ok(())
+}
+
+/// Evento que devolve uma entidade ao tipo, dono e metadados de `previous`,
+/// na versão seguinte à atual.
+fn restore_entity(previous: &EntityState, current: &EntityState) -> EventKind {
+    EventKind::EntityUpdated {
+        id: previous.id.clone(),
+        entity_type: previous.entity_type.clone(),
+        owner: previous.owner.clone(),
+        metadata: previous.metadata.clone(),
+        version: current.version + 1,
+    }
+}
+
+/// Eventos que devolvem uma ideia ao texto e às tags dados, recriando-a se
+/// necessário.
+fn restore_idea(id: &str, text: &str, tags: &[String], recreate: bool) -> Vec<EventKind> {
+    let mut kinds = vec![if recreate {
+        EventKind::IdeaRegistered { id: id.to_string(), text: text.to_string() }
+    } else {
+        EventKind::IdeaUpdated { id: id.to_string(), text: text.to_string() }
+    }];
+    if !tags.is_empty() {
+        kinds.push(EventKind::IdeaTagged { id: id.to_string(), tags: tags.to_vec() });
+    }
+    kinds
+}
@@ -0,0 +1,299 @@
+// === File: runtime/src/scheduler.rs ===
+/*
+    Description: Scheduler dos gatilhos de um lllpack: agendas cron e eventos da timeline disparam os scripts .lll declarados no manifesto.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use parser::lll::Span;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+use crate::boot::{execution_timeout, load_lll, Manifest};
+use crate::cron::Cron;
+use crate::events::EventKind;
+use crate::state::TriggerState;
+use crate::trace::{self, TraceContext};
+use crate::timeline;
+
+/// Intervalo padrão entre duas verificações dos gatilhos.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Span do script que lista, em `children`, as tarefas de um disparo.
+pub const EXECUTION_SPAN: &str = "CronExecution";
+
+/// Gatilho declarado em `triggers` no `lllpack.yaml`.
+///
+/// Um gatilho tem uma agenda (`cron`) ou um evento (`event`), nunca os
+/// dois. A agenda do manifesto prevalece sobre o `trigger_config` do script.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Trigger {
+    pub name: String,
+    #[serde(default)]
+    pub cron: Option<String>,
+    /// Nome da variante de [`EventKind`], em `CamelCase` ou `snake_case`
+    /// (`IdeaRegistered` ou `idea_registered`).
+    #[serde(default)]
+    pub event: Option<String>,
+    /// Script `.lll` executado a cada disparo, relativo ao pacote.
+    pub file: String,
+}
+
+/// Resultado de um disparo.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Firing {
+    pub trigger: String,
+    /// Ocorrência da agenda, ou instante do evento que disparou o gatilho.
+    pub scheduled_for: DateTime<Utc>,
+    /// Ocorrências perdidas cobertas por este disparo.
+    pub missed: usize,
+    /// Evento que disparou o gatilho.
+    pub cause: Option<Uuid>,
+    /// `completed` ou `failed`.
+    pub status: String,
+    /// Status de cada tarefa: `completed`, `failed` ou `skipped` (sem ação
+    /// nativa).
+    pub tasks: BTreeMap<String, String>,
+    pub error: Option<String>,
+    pub next_scheduled: Option<DateTime<Utc>>,
+}
+
+/// Gatilho validado.
+struct Armed {
+    name: String,
+    file: String,
+    schedule: Schedule,
+}
+
+enum Schedule {
+    Cron(Cron),
+    /// Nome do evento normalizado (ver [`event_key`]).
+    Event(String),
+}
+
+/// Scheduler dos gatilhos de um pacote.
+///
+/// Cada disparo registra um `CronExecution` e, ao fim do script, um
+/// `CronCompletion` filho dele, com o status de cada tarefa. O último
+/// disparo de cada gatilho faz parte do estado da timeline (ver
+/// [`crate::state::TriggerState`]) e sobrevive a reinícios e snapshots: ao
+/// voltar, um gatilho cron que perdeu ocorrências executa uma única vez, pela
+/// mais recente, com as demais contadas em `missed`. Gatilhos que nunca
+/// dispararam consideram apenas o que ocorrer depois da criação do primeiro
+/// scheduler que os verificou, registrada num `TriggerArmed`, de modo que
+/// as ocorrências perdidas antes do primeiro disparo também são cobertas.
+pub struct Scheduler {
+    pack_dir: PathBuf,
+    triggers: Vec<Armed>,
+    started: DateTime<Utc>,
+}
+
+impl Scheduler {
+    /// Scheduler dos gatilhos declarados no manifesto do pacote.
+    pub fn load(pack_dir: impl AsRef<Path>) -> Result<Self> {
+        let manifest = Manifest::load(&pack_dir)?;
+        Self::new(pack_dir.as_ref(), manifest.triggers)
+    }
+
+    /// Scheduler dos gatilhos dados, cujos scripts são relativos a
+    /// `pack_dir`. Os scripts só são lidos quando o gatilho dispara.
+    pub fn new(pack_dir: impl Into<PathBuf>, triggers: Vec<Trigger>) -> Result<Self> {
+        let mut armed: Vec<Armed> = Vec::with_capacity(triggers.len());
+        for trigger in triggers {
+            if armed.iter().any(|a| a.name == trigger.name) {
+                bail!("Gatilho {} declarado mais de uma vez", trigger.name);
+            }
+            let schedule = match (&trigger.cron, &trigger.event) {
+                (Some(cron), None) => {
+                    Schedule::Cron(cron.parse().with_context(|| format!("Gatilho {} inválido", trigger.name))?)
+                }
+                (None, Some(event)) => Schedule::Event(event_key(event)),
+                _ => bail!("Gatilho {} deve declarar cron ou event", trigger.name),
+            };
+            armed.push(Armed { name: trigger.name, file: trigger.file, schedule });
+        }
+        Ok(Self { pack_dir: pack_dir.into(), triggers: armed, started: Utc::now() })
+    }
+
+    /// Dispara os gatilhos devidos até `now` e devolve os disparos, na ordem
+    /// em que foram executados.
+    ///
+    /// Um gatilho de evento dispara uma vez para cada evento do tipo
+    /// registrado desde o último disparo, exceto os registrados pelos
+    /// próprios scripts do scheduler.
+    ///
+    /// Gatilhos que ainda não dispararam nem foram verificados antes são
+    /// registrados num `TriggerArmed`, com o instante de criação do
+    /// scheduler.
+    pub async fn tick(&self, now: DateTime<Utc>) -> Result<Vec<Firing>> {
+        let state = timeline::current_state().await?;
+        let events = timeline::list_events().await?;
+        let runs: HashSet<Uuid> = events
+            .iter()
+            .filter(|e| matches!(e.kind, EventKind::CronExecution { .. }))
+            .filter_map(|e| e.trace_id)
+            .collect();
+
+        let mut firings = Vec::new();
+        for trigger in &self.triggers {
+            let since = match state.triggers.get(&trigger.name).and_then(TriggerState::since) {
+                Some(since) => since,
+                None => {
+                    let kind = EventKind::TriggerArmed { trigger: trigger.name.clone(), since: self.started };
+                    timeline::append(kind).await?;
+                    self.started
+                }
+            };
+            match &trigger.schedule {
+                Schedule::Cron(cron) => {
+                    let (due, latest) = cron
+                        .occurrences(since)
+                        .take_while(|at| *at <= now)
+                        .fold((0, None), |(due, _), at| (due + 1, Some(at)));
+                    if let Some(latest) = latest {
+                        if due > 1 {
+                            warn!("Gatilho {} perdeu {} ocorrências; executando a de {}", trigger.name, due - 1, latest);
+                        }
+                        firings.push(self.fire(trigger, latest, due - 1, None, now).await?);
+                    }
+                }
+                Schedule::Event(key) => {
+                    let matching = events.iter().filter(|e| {
+                        e.timestamp > since
+                            && e.timestamp <= now
+                            && event_key(e.kind.name()) == *key
+                            && !e.trace_id.is_some_and(|id| runs.contains(&id))
+                    });
+                    for event in matching {
+                        firings.push(self.fire(trigger, event.timestamp, 0, Some(event.id), now).await?);
+                    }
+                }
+            }
+        }
+        Ok(firings)
+    }
+
+    /// Verifica os gatilhos a cada `interval`, até ocorrer um erro. Para
+    /// encerrar, basta descartar o futuro.
+    pub async fn run(&self, interval: Duration) -> Result<()> {
+        loop {
+            for firing in self.tick(Utc::now()).await? {
+                info!("Gatilho {} executado: {}", firing.trigger, firing.status);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Executa o script de um gatilho.
+    ///
+    /// As tarefas são os `children` do span `CronExecution` do script,
+    /// executadas em ordem e todas até o fim, cada uma com o tempo limite de
+    /// `timeouts.execution`. Tarefas sem ação nativa são registradas como
+    /// `skipped`.
+    async fn fire(
+        &self,
+        trigger: &Armed,
+        scheduled_for: DateTime<Utc>,
+        missed: usize,
+        cause: Option<Uuid>,
+        now: DateTime<Utc>,
+    ) -> Result<Firing> {
+        let started = Instant::now();
+        let script = load_lll(&self.pack_dir.join(&trigger.file));
+        let spans = script.as_ref().map(|script| script.spans()).unwrap_or_default();
+        let execution_span = spans.iter().find(|span| span.name == EXECUTION_SPAN);
+
+        let actors = execution_span
+            .map(|span| span.actors.clone())
+            .filter(|actors| !actors.is_empty())
+            .unwrap_or_else(|| vec![trace::DEFAULT_ACTOR.to_string()]);
+        let kind = EventKind::CronExecution { trigger: trigger.name.clone(), scheduled_for, missed, cause };
+        let execution = trace::scope(TraceContext::new(actors), timeline::append(kind)).await?;
+
+        let mut tasks = BTreeMap::new();
+        let mut errors = Vec::new();
+        match (&script, execution_span) {
+            (Err(e), _) => errors.push(e.to_string()),
+            (Ok(_), None) => errors.push(format!("Script {} sem o span {}", trigger.file, EXECUTION_SPAN)),
+            (Ok(_), Some(execution_span)) => {
+                for name in &execution_span.children {
+                    let result = match spans.iter().find(|span| span.name == *name) {
+                        Some(task) => trace::child_of(&execution, run_task(task)).await,
+                        None => Err(anyhow!("Span não declarado em {}", trigger.file)),
+                    };
+                    let status = result.unwrap_or_else(|e| {
+                        errors.push(format!("{}: {}", name, e));
+                        "failed"
+                    });
+                    tasks.insert(name.clone(), status.to_string());
+                }
+            }
+        }
+
+        let status = if errors.is_empty() { "completed" } else { "failed" };
+        let error = (!errors.is_empty()).then(|| errors.join("; "));
+        if let Some(error) = &error {
+            warn!("Gatilho {} falhou: {}", trigger.name, error);
+        }
+        let next_scheduled = match &trigger.schedule {
+            Schedule::Cron(cron) => cron.next_after(now.max(scheduled_for)),
+            Schedule::Event(_) => None,
+        };
+        let kind = EventKind::CronCompletion {
+            trigger: trigger.name.clone(),
+            status: status.to_string(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            tasks: tasks.clone(),
+            error: error.clone(),
+            next_scheduled,
+        };
+        trace::child_of(&execution, timeline::append(kind)).await?;
+
+        Ok(Firing {
+            trigger: trigger.name.clone(),
+            scheduled_for,
+            missed,
+            cause,
+            status: status.to_string(),
+            tasks,
+            error,
+            next_scheduled,
+        })
+    }
+}
+
+/// Ação nativa de uma tarefa de manutenção.
+async fn run_task(task: &Span) -> Result<&'static str> {
+    let run = async {
+        match task.name.as_str() {
+            "TimelineOptimization" => timeline::compact().await.map(|_| "completed"),
+            "HealthCheck" => timeline::verify()
+                .await
+                .map(|()| "completed")
+                .map_err(|e| anyhow!("Timeline adulterada: {}", e)),
+            _ => Ok("skipped"),
+        }
+    };
+    match execution_timeout(task) {
+        Some(limit) => tokio::time::timeout(limit, run)
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Tempo limite de {} ms excedido", limit.as_millis()))),
+        None => run.await,
+    }
+}
+
+/// Forma comparável do nome de um evento: `IdeaRegistered` e
+/// `idea_registered` são o mesmo evento.
+fn event_key(name: &str) -> String {
+    name.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect()
+}
@@ -0,0 +1,148 @@
+// === File: runtime/src/snapshot.rs ===
+/*
+    Description: Snapshots do estado derivado da timeline, usados para compactar o arquivo e acelerar a reconstrução.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::events::{sha256_hex, Event};
+use crate::state::State;
+
+/// Número de eventos no arquivo da timeline que dispara um novo snapshot.
+pub const DEFAULT_SNAPSHOT_INTERVAL: usize = 1024;
+
+/// Estado da timeline até um evento, com o elo para os eventos seguintes.
+///
+/// O primeiro evento gravado depois do snapshot tem `last_hash` como
+/// `causal_hash`, de modo que a cadeia continua verificável a partir dele
+/// (ver [`crate::timeline::verify_chain_from`]).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Snapshot {
+    /// Último evento coberto
+    pub last_event_id: Uuid,
+    /// Hash do último evento coberto
+    pub last_hash: String,
+    /// Quando ocorreu o último evento coberto
+    pub timestamp: DateTime<Utc>,
+    /// Total de eventos cobertos, somando os snapshots anteriores
+    pub events_covered: u64,
+    /// Estado após o último evento coberto
+    pub state: State,
+    /// Hash do snapshot, no formato `sha256:<hex>`
+    pub hash: String,
+}
+
+/// Conteúdo coberto pelo hash de um snapshot.
+#[derive(Serialize)]
+struct HashedContent<'a> {
+    last_event_id: &'a Uuid,
+    last_hash: &'a str,
+    timestamp: &'a DateTime<Utc>,
+    events_covered: u64,
+    state: &'a State,
+}
+
+impl Snapshot {
+    /// Cria o snapshot do estado após `last`, o último evento coberto.
+    pub fn new(last: &Event, events_covered: u64, state: State) -> Self {
+        let mut snapshot = Self {
+            last_event_id: last.id,
+            last_hash: last.hash.clone(),
+            timestamp: last.timestamp,
+            events_covered,
+            state,
+            hash: String::new(),
+        };
+        snapshot.hash = snapshot.compute_hash();
+        snapshot
+    }
+
+    /// Calcula o hash SHA-256 do snapshot.
+    pub fn compute_hash(&self) -> String {
+        let content = HashedContent {
+            last_event_id: &self.last_event_id,
+            last_hash: &self.last_hash,
+            timestamp: &self.timestamp,
+            events_covered: self.events_covered,
+            state: &self.state,
+        };
+        let bytes = serde_json::to_vec(&content).expect("Snapshots são sempre serializáveis");
+        sha256_hex(bytes)
+    }
+
+    /// Caminho do snapshot associado a um arquivo de timeline
+    /// (`main.timeline` → `main.snapshot`).
+    pub fn path_for(timeline: impl AsRef<Path>) -> PathBuf {
+        timeline.as_ref().with_extension("snapshot")
+    }
+
+    /// Lê o snapshot do arquivo, se existir.
+    ///
+    /// Falha se o hash gravado não conferir com o conteúdo.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read(path).with_context(|| format!("Falha ao ler snapshot {}", path.display()))?;
+        let snapshot: Self = serde_json::from_slice(&contents)
+            .with_context(|| format!("Snapshot inválido em {}", path.display()))?;
+
+        let computed = snapshot.compute_hash();
+        if snapshot.hash != computed {
+            bail!(
+                "Snapshot {} foi alterado: hash registrado {}, hash calculado {}",
+                path.display(),
+                snapshot.hash,
+                computed
+            );
+        }
+        Ok(Some(snapshot))
+    }
+
+    /// Grava o snapshot no arquivo.
+    ///
+    /// A gravação é atômica: o conteúdo vai para um arquivo temporário,
+    /// levado ao disco, que então substitui o anterior.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let temp = path.with_extension("snapshot.tmp");
+        let mut file = File::create(&temp).with_context(|| format!("Falha ao criar {}", temp.display()))?;
+        file.write_all(&serde_json::to_vec(self)?)?;
+        file.sync_all()?;
+        fs::rename(&temp, path).with_context(|| format!("Falha ao gravar snapshot {}", path.display()))?;
+        Ok(())
+    }
+}
+
+/// Separa, dos eventos lidos do arquivo, os que vêm depois do snapshot.
+///
+/// Normalmente o arquivo só contém eventos posteriores. Se uma queda
+/// ocorreu entre a gravação do snapshot e a compactação do arquivo, os
+/// eventos já cobertos ainda estão nele e são descartados. Falha se o
+/// arquivo não continuar a cadeia do snapshot.
+pub fn tail_after(snapshot: &Snapshot, events: Vec<Event>) -> Result<Vec<Event>> {
+    match events.first() {
+        None => Ok(events),
+        Some(first) if first.causal_hash.as_deref() == Some(snapshot.last_hash.as_str()) => Ok(events),
+        Some(_) => match events.iter().position(|event| event.hash == snapshot.last_hash) {
+            Some(index) => Ok(events[index + 1..].to_vec()),
+            None => bail!(
+                "Timeline não continua o snapshot: nenhum evento aponta para {}",
+                snapshot.last_hash
+            ),
+        },
+    }
+}
@@ -0,0 +1,286 @@
+// === File: runtime/src/state.rs ===
+/*
+    Description: Estado do LogLine derivado da timeline: contratos, ideias, entidades e gatilhos obtidos pela dobra dos eventos.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use registry::{Entity, EntityType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::events::{Event, EventKind};
+
+/// Contrato reconstruído da timeline.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ContractState {
+    pub id: String,
+    pub clauses: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Ideia reconstruída da timeline.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IdeaState {
+    pub id: String,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Entidade do registro reconstruída da timeline.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EntityState {
+    pub id: String,
+    pub entity_type: String,
+    pub created_at: DateTime<Utc>,
+    // Os campos abaixo são omitidos enquanto vazios ou na versão 1,
+    // preservando o hash de snapshots anteriores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub metadata: Value,
+    #[serde(default = "first_version", skip_serializing_if = "is_first_version")]
+    pub version: u64,
+    /// Última alteração; `None` enquanto a entidade não mudou
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl EntityState {
+    /// A entidade como gravada no registro (ver [`crate::replay`]).
+    pub fn to_entity(&self) -> Entity {
+        Entity {
+            id: self.id.clone(),
+            entity_type: EntityType::from(self.entity_type.as_str()),
+            owner: self.owner.clone(),
+            metadata: self.metadata.clone(),
+            version: self.version,
+            created_at: self.created_at,
+            updated_at: self.updated_at.unwrap_or(self.created_at),
+        }
+    }
+}
+
+fn first_version() -> u64 {
+    1
+}
+
+fn is_first_version(version: &u64) -> bool {
+    *version == 1
+}
+
+/// Último disparo de um gatilho do scheduler, reconstruído da timeline.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TriggerState {
+    pub name: String,
+    /// Ocorrência ou evento coberto pelo último disparo; `None` se o gatilho
+    /// ainda não disparou
+    // Omitidos quando vazios, preservando o hash de snapshots anteriores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_fired: Option<DateTime<Utc>>,
+    /// Instante a partir do qual o gatilho considera ocorrências e eventos,
+    /// registrado antes do primeiro disparo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub armed_since: Option<DateTime<Utc>>,
+    pub runs: u64,
+    /// Status do último script concluído; `None` enquanto ele executa
+    pub last_status: Option<String>,
+}
+
+impl TriggerState {
+    /// Instante a partir do qual o próximo disparo considera ocorrências e
+    /// eventos: o último disparo ou, antes dele, o início do gatilho.
+    pub fn since(&self) -> Option<DateTime<Utc>> {
+        self.last_fired.or(self.armed_since)
+    }
+}
+
+/// Estado completo do LogLine em um ponto da timeline.
+///
+/// É obtido apenas pela dobra dos eventos, em ordem: o runtime mantém o
+/// estado atual junto da timeline (ver [`crate::timeline::current_state`])
+/// e lê dele os contratos, as ideias e as entidades; o registro de
+/// entidades é uma projeção do mesmo fluxo (ver [`crate::replay`]). As
+/// datas vêm dos eventos, de modo que a reconstrução é determinística.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct State {
+    pub contracts: BTreeMap<String, ContractState>,
+    pub ideas: BTreeMap<String, IdeaState>,
+    pub entities: BTreeMap<String, EntityState>,
+    // Omitido quando vazio, preservando o hash de snapshots anteriores
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub triggers: BTreeMap<String, TriggerState>,
+}
+
+impl State {
+    /// Dobra uma sequência de eventos a partir do estado vazio.
+    pub fn fold<'a>(events: impl IntoIterator<Item = &'a Event>) -> Self {
+        Self::fold_from(Self::default(), events)
+    }
+
+    /// Dobra uma sequência de eventos a partir de um estado dado, como o de
+    /// um [`crate::snapshot::Snapshot`].
+    pub fn fold_from<'a>(mut state: Self, events: impl IntoIterator<Item = &'a Event>) -> Self {
+        for event in events {
+            state.apply(event);
+        }
+        state
+    }
+
+    /// Aplica o efeito de um evento.
+    ///
+    /// Alterações sobre itens inexistentes são ignoradas: os eventos já
+    /// foram validados quando entraram na timeline.
+    pub fn apply(&mut self, event: &Event) {
+        let at = event.timestamp;
+        match &event.kind {
+            EventKind::ContractRegistered { id, clauses } => {
+                self.contracts.insert(
+                    id.clone(),
+                    ContractState { id: id.clone(), clauses: clauses.clone(), created_at: at, updated_at: at },
+                );
+                self.register(id, "CONTRACT", at);
+            }
+            EventKind::IdeaRegistered { id, text } => {
+                self.ideas.insert(
+                    id.clone(),
+                    IdeaState { id: id.clone(), text: text.clone(), tags: Vec::new(), created_at: at, updated_at: at },
+                );
+                self.register(id, "IDEA", at);
+            }
+            EventKind::ContractAmended { id, clauses } => {
+                if let Some(contract) = self.contracts.get_mut(id) {
+                    contract.clauses = clauses.clone();
+                    contract.updated_at = at;
+                }
+            }
+            EventKind::IdeaTagged { id, tags } => {
+                if let Some(idea) = self.ideas.get_mut(id) {
+                    for tag in tags {
+                        if !idea.tags.contains(tag) {
+                            idea.tags.push(tag.clone());
+                        }
+                    }
+                    idea.updated_at = at;
+                }
+            }
+            EventKind::IdeaUntagged { id, tags } => {
+                if let Some(idea) = self.ideas.get_mut(id) {
+                    idea.tags.retain(|tag| !tags.contains(tag));
+                    idea.updated_at = at;
+                }
+            }
+            EventKind::IdeaUpdated { id, text } => {
+                if let Some(idea) = self.ideas.get_mut(id) {
+                    idea.text = text.clone();
+                    idea.updated_at = at;
+                }
+            }
+            EventKind::ContractDeleted { id } => {
+                self.contracts.remove(id);
+            }
+            EventKind::IdeaDeleted { id } => {
+                self.ideas.remove(id);
+            }
+            EventKind::EntityRegistered { id, entity_type } => self.register(id, entity_type, at),
+            EventKind::EntityCreated { id, entity_type, owner, metadata, version } => {
+                self.entities.insert(
+                    id.clone(),
+                    EntityState {
+                        id: id.clone(),
+                        entity_type: entity_type.clone(),
+                        created_at: at,
+                        owner: owner.clone(),
+                        metadata: metadata.clone(),
+                        version: *version,
+                        updated_at: None,
+                    },
+                );
+            }
+            EventKind::EntityUpdated { id, entity_type, owner, metadata, version } => {
+                if let Some(entity) = self.entities.get_mut(id) {
+                    entity.entity_type = entity_type.clone();
+                    entity.owner = owner.clone();
+                    entity.metadata = metadata.clone();
+                    entity.version = *version;
+                    entity.updated_at = Some(at);
+                }
+            }
+            EventKind::EntityRetired { id } => {
+                self.entities.remove(id);
+            }
+            EventKind::TriggerArmed { trigger, since } => {
+                self.triggers.entry(trigger.clone()).or_insert_with(|| TriggerState {
+                    name: trigger.clone(),
+                    last_fired: None,
+                    armed_since: Some(*since),
+                    runs: 0,
+                    last_status: None,
+                });
+            }
+            EventKind::CronExecution { trigger, scheduled_for, .. } => {
+                let previous = self.triggers.get(trigger);
+                let state = TriggerState {
+                    name: trigger.clone(),
+                    last_fired: Some(*scheduled_for),
+                    armed_since: previous.and_then(|state| state.armed_since),
+                    runs: previous.map_or(0, |state| state.runs) + 1,
+                    last_status: None,
+                };
+                self.triggers.insert(trigger.clone(), state);
+            }
+            EventKind::CronCompletion { trigger, status, .. } => {
+                if let Some(state) = self.triggers.get_mut(trigger) {
+                    state.last_status = Some(status.clone());
+                }
+            }
+            EventKind::RuntimeLifecycle { .. }
+            | EventKind::ImperativeExecuted { .. }
+            | EventKind::EventReverted { .. }
+            | EventKind::TimelineCompacted { .. }
+            | EventKind::RuleVerdict { .. }
+            | EventKind::OrchestrationStarted { .. }
+            | EventKind::OrchestrationCompleted { .. }
+            | EventKind::ExecutionAttempt { .. }
+            | EventKind::BootSpan { .. }
+            | EventKind::SimulationCompleted { .. }
+            | EventKind::ErrorOccurred { .. } => {}
+        }
+    }
+
+    /// Registra uma entidade como [`registry::Registry::register_entity`]:
+    /// com o mesmo tipo, nada muda; com outro, apenas o tipo e a versão.
+    fn register(&mut self, id: &str, entity_type: &str, at: DateTime<Utc>) {
+        match self.entities.get_mut(id) {
+            Some(entity) if entity.entity_type == entity_type => {}
+            Some(entity) => {
+                entity.entity_type = entity_type.to_string();
+                entity.version += 1;
+                entity.updated_at = Some(at);
+            }
+            None => {
+                self.entities.insert(
+                    id.to_string(),
+                    EntityState {
+                        id: id.to_string(),
+                        entity_type: entity_type.to_string(),
+                        created_at: at,
+                        owner: None,
+                        metadata: Value::Null,
+                        version: 1,
+                        updated_at: None,
+                    },
+                );
+            }
+        }
+    }
+}
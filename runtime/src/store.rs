@@ -0,0 +1,226 @@
+// === File: runtime/src/store.rs ===
+/*
+    Description: Arquivo da timeline: registro append-only em JSON por linha, com fsync em lotes e recuperação após falhas.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::warn;
+use uuid::Uuid;
+use crate::events::{Event, EventKind};
+
+/// Número de registros gravados entre duas chamadas a `fsync`.
+pub const DEFAULT_SYNC_BATCH: usize = 32;
+
+/// Registro de um evento no arquivo, no formato dos spans de
+/// `lllpack/timeline/main.timeline`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineRecord {
+    /// Sempre `"span"`.
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// Nome da variante de [`EventKind`].
+    pub span_type: String,
+    pub parent_id: Option<Uuid>,
+    pub trace_id: Option<Uuid>,
+    #[serde(default)]
+    pub actors: Vec<String>,
+    /// Dados da variante de [`EventKind`].
+    pub fields: Map<String, Value>,
+    pub hash: String,
+    pub causal_hash: Option<String>,
+}
+
+impl TimelineRecord {
+    /// Converte um evento em registro.
+    pub fn from_event(event: &Event) -> Result<Self> {
+        // `EventKind` é serializado como `{ "Variante": { campos } }`
+        let (span_type, fields) = match serde_json::to_value(&event.kind)? {
+            Value::Object(kind) => kind.into_iter().next().ok_or_else(|| anyhow!("Evento sem tipo"))?,
+            other => bail!("Formato de evento inesperado: {}", other),
+        };
+        let fields = match fields {
+            Value::Object(fields) => fields,
+            other => bail!("Campos de evento inesperados: {}", other),
+        };
+
+        Ok(Self {
+            record_type: "span".to_string(),
+            id: event.id,
+            timestamp: event.timestamp,
+            span_type,
+            parent_id: event.parent_id,
+            trace_id: event.trace_id,
+            actors: event.actors.clone(),
+            fields,
+            hash: event.hash.clone(),
+            causal_hash: event.causal_hash.clone(),
+        })
+    }
+
+    /// Reconstrói o evento do registro.
+    pub fn into_event(self) -> Result<Event> {
+        let mut kind = Map::new();
+        kind.insert(self.span_type.clone(), Value::Object(self.fields));
+        let kind: EventKind = serde_json::from_value(Value::Object(kind))
+            .with_context(|| format!("Tipo de evento desconhecido: {}", self.span_type))?;
+
+        Ok(Event {
+            id: self.id,
+            timestamp: self.timestamp,
+            kind,
+            hash: self.hash,
+            causal_hash: self.causal_hash,
+            trace_id: self.trace_id,
+            parent_id: self.parent_id,
+            actors: self.actors,
+        })
+    }
+}
+
+/// Arquivo append-only da timeline, um registro JSON por linha.
+///
+/// Um registro só é considerado gravado quando termina em `\n`. Os dados
+/// são levados ao disco com `fsync` a cada `sync_batch` registros e em
+/// [`TimelineFile::sync`].
+#[derive(Debug)]
+pub struct TimelineFile {
+    path: PathBuf,
+    file: File,
+    sync_batch: usize,
+    pending: usize,
+}
+
+impl TimelineFile {
+    /// Abre (ou cria) o arquivo e devolve os eventos já gravados.
+    ///
+    /// Um registro final incompleto, deixado por uma queda durante a
+    /// escrita, é descartado e o arquivo é truncado antes dele. Registros
+    /// inválidos em outras posições indicam corrupção e são um erro.
+    pub fn open(path: impl AsRef<Path>, sync_batch: usize) -> Result<(Self, Vec<Event>)> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Falha ao criar diretório {}", dir.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .with_context(|| format!("Falha ao abrir timeline {}", path.display()))?;
+
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        let (events, valid_len) = read_records(&contents)
+            .with_context(|| format!("Timeline corrompida em {}", path.display()))?;
+
+        if valid_len < contents.len() {
+            warn!(
+                "Descartando registro incompleto no fim de {} ({} bytes)",
+                path.display(),
+                contents.len() - valid_len
+            );
+            file.set_len(valid_len as u64)?;
+            file.sync_all()?;
+        }
+
+        let store = Self { path, file, sync_batch: sync_batch.max(1), pending: 0 };
+        Ok((store, events))
+    }
+
+    /// Caminho do arquivo.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Acrescenta um evento ao fim do arquivo.
+    pub fn append(&mut self, event: &Event) -> Result<()> {
+        let mut line = serde_json::to_vec(&TimelineRecord::from_event(event)?)?;
+        line.push(b'\n');
+        // Uma única escrita por registro: uma queda só pode cortar o último
+        self.file.write_all(&line)?;
+
+        self.pending += 1;
+        if self.pending >= self.sync_batch {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Acrescenta vários eventos ao fim do arquivo, numa única escrita, e os
+    /// leva ao disco.
+    ///
+    /// Uma queda durante a escrita pode deixar no arquivo apenas os
+    /// primeiros registros completos.
+    pub fn append_all(&mut self, events: &[Event]) -> Result<()> {
+        let mut lines = Vec::new();
+        for event in events {
+            serde_json::to_writer(&mut lines, &TimelineRecord::from_event(event)?)?;
+            lines.push(b'\n');
+        }
+        self.file.write_all(&lines)?;
+
+        self.pending += events.len();
+        self.sync()
+    }
+
+    /// Leva ao disco os registros ainda não sincronizados.
+    pub fn sync(&mut self) -> Result<()> {
+        if self.pending > 0 {
+            self.file.sync_data()?;
+            self.pending = 0;
+        }
+        Ok(())
+    }
+
+    /// Remove todos os registros do arquivo.
+    pub fn truncate(&mut self) -> Result<()> {
+        self.file.set_len(0)?;
+        self.file.sync_all()?;
+        self.pending = 0;
+        Ok(())
+    }
+}
+
+/// Lê os registros completos e devolve os eventos e o tamanho, em bytes,
+/// do trecho válido.
+fn read_records(contents: &[u8]) -> Result<(Vec<Event>, usize)> {
+    let mut events = Vec::new();
+    let mut start = 0;
+
+    while start < contents.len() {
+        let Some(end) = contents[start..].iter().position(|&b| b == b'\n').map(|i| start + i) else {
+            // Registro sem `\n` final: escrita interrompida
+            break;
+        };
+        let line = &contents[start..end];
+        let is_last = end + 1 == contents.len();
+
+        if !line.iter().all(u8::is_ascii_whitespace) {
+            let record = serde_json::from_slice::<TimelineRecord>(line).map_err(anyhow::Error::from);
+            match record.and_then(TimelineRecord::into_event) {
+                Ok(event) => events.push(event),
+                Err(_) if is_last => break,
+                Err(e) => bail!("Registro {} inválido: {}", events.len() + 1, e),
+            }
+        }
+        start = end + 1;
+    }
+
+    Ok((events, start))
+}
@@ -0,0 +1,363 @@
+// === File: runtime/src/timeline.rs ===
+/*
+    Description: Timeline do runtime: registro ordenado dos eventos produzidos pela execução de comandos.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::path::Path;
+use std::sync::Arc;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use tokio::sync::RwLock;
+use tracing::warn;
+use uuid::Uuid;
+use crate::events::{Event, EventKind};
+use crate::snapshot::{self, Snapshot};
+use crate::state::State;
+use crate::store::TimelineFile;
+use crate::{replay, trace, view, Runtime};
+
+/// Elo quebrado na cadeia de hashes da timeline.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ChainError {
+    #[error("Evento {index} ({id}) foi alterado: hash registrado {recorded}, hash calculado {computed}")]
+    Tampered {
+        index: usize,
+        id: Uuid,
+        recorded: String,
+        computed: String,
+    },
+
+    #[error("Evento {index} ({id}) não aponta para o anterior: esperado {expected:?}, encontrado {found:?}")]
+    BrokenLink {
+        index: usize,
+        id: Uuid,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+}
+
+impl ChainError {
+    /// Posição, na timeline, do primeiro evento inválido.
+    pub fn index(&self) -> usize {
+        match self {
+            ChainError::Tampered { index, .. } | ChainError::BrokenLink { index, .. } => *index,
+        }
+    }
+}
+
+/// Eventos registrados desde o último snapshot e, se houver, o arquivo onde
+/// são persistidos.
+#[derive(Default)]
+pub(crate) struct Timeline {
+    events: Vec<Event>,
+    file: Option<TimelineFile>,
+    snapshot: Option<Snapshot>,
+    /// Eventos no arquivo que disparam um novo snapshot (`0` desativa)
+    snapshot_interval: usize,
+    /// Dobra do snapshot e dos eventos, atualizada a cada registro
+    state: State,
+}
+
+impl Timeline {
+    /// Hash do último evento, inclusive os cobertos pelo snapshot.
+    fn last_hash(&self) -> Option<String> {
+        match self.events.last() {
+            Some(last) => Some(last.hash.clone()),
+            None => self.snapshot.as_ref().map(|snapshot| snapshot.last_hash.clone()),
+        }
+    }
+
+    /// Estado do snapshot, ou o estado vazio.
+    fn base_state(&self) -> State {
+        self.snapshot.as_ref().map(|snapshot| snapshot.state.clone()).unwrap_or_default()
+    }
+
+    /// Acrescenta eventos já encadeados e aplica seus efeitos ao estado.
+    fn push(&mut self, events: impl IntoIterator<Item = Event>) {
+        for event in events {
+            self.state.apply(&event);
+            self.events.push(event);
+        }
+    }
+
+    /// Substitui os eventos por um snapshot do estado (ver [`compact`]).
+    fn compact(&mut self) -> Result<Option<Snapshot>> {
+        let Some(last) = self.events.last() else {
+            return Ok(None);
+        };
+        let covered = self.snapshot.as_ref().map_or(0, |snapshot| snapshot.events_covered);
+        let snapshot = Snapshot::new(last, covered + self.events.len() as u64, self.state.clone());
+        let Some(file) = self.file.as_mut() else {
+            bail!("Timeline não está associada a um arquivo");
+        };
+
+        // Os eventos cobertos precisam estar no disco antes do snapshot, para
+        // que uma queda antes da compactação seja recuperável
+        file.sync()?;
+        snapshot.save(Snapshot::path_for(file.path()))?;
+        file.truncate()?;
+
+        let compacted = Event::chained(
+            EventKind::TimelineCompacted { events_collapsed: self.events.len(), snapshot_hash: snapshot.hash.clone() },
+            Some(snapshot.last_hash.clone()),
+        );
+        file.append(&compacted)?;
+        self.events = vec![compacted];
+        self.snapshot = Some(snapshot.clone());
+        Ok(Some(snapshot))
+    }
+
+    /// Compacta a timeline se o arquivo atingiu o intervalo de snapshots;
+    /// uma falha é apenas registrada no log.
+    fn compact_if_due(&mut self) {
+        if self.file.is_some() && self.snapshot_interval > 0 && self.events.len() >= self.snapshot_interval {
+            if let Err(e) = self.compact() {
+                warn!("Falha ao compactar a timeline: {}", e);
+            }
+        }
+    }
+}
+
+/// Timeline do runtime da tarefa atual (ver [`Runtime::current`]).
+fn timeline() -> Arc<RwLock<Timeline>> {
+    Runtime::current().timeline()
+}
+
+/// Passa a persistir a timeline no arquivo indicado.
+///
+/// Se houver um snapshot ao lado do arquivo (ver [`Snapshot::path_for`]),
+/// apenas os eventos posteriores a ele são lidos. O snapshot e esses
+/// eventos substituem os da memória, o estado é reconstruído do zero pela
+/// dobra deles, e ambos são devolvidos. Um novo snapshot é gravado sempre que o arquivo
+/// acumular `snapshot_interval` eventos (`0` desativa). Falha se a cadeia
+/// de hashes do arquivo estiver quebrada ou não continuar o snapshot.
+pub async fn attach(
+    path: impl AsRef<Path>,
+    sync_batch: usize,
+    snapshot_interval: usize,
+) -> Result<(Option<Snapshot>, Vec<Event>)> {
+    let path = path.as_ref();
+    let (file, events) = TimelineFile::open(path, sync_batch)?;
+    let snapshot = Snapshot::load(Snapshot::path_for(path))?;
+    let events = match &snapshot {
+        Some(snapshot) => snapshot::tail_after(snapshot, events)?,
+        None => events,
+    };
+    let anchor = snapshot.as_ref().map(|snapshot| snapshot.last_hash.as_str());
+    verify_chain_from(&events, anchor).with_context(|| format!("Timeline {} adulterada", path.display()))?;
+
+    let mut timeline = timeline().write_owned().await;
+    if let Some(mut previous) = timeline.file.take() {
+        previous.sync()?;
+    }
+    timeline.file = Some(file);
+    timeline.snapshot = snapshot.clone();
+    timeline.snapshot_interval = snapshot_interval;
+    timeline.state = timeline.base_state();
+    timeline.events.clear();
+    timeline.push(events.clone());
+    Ok((snapshot, events))
+}
+
+/// Registra um evento no fim da timeline, ligado ao último pelo hash,
+/// aplica seu efeito ao estado e o devolve.
+///
+/// O efeito sobre o registro de entidades (ver [`replay::project`]) é
+/// aplicado sob o mesmo lock, antes da gravação no arquivo: se uma das
+/// duas falhar, ou o registro for cancelado no meio, nem a timeline, nem o
+/// estado, nem o registro mudam.
+///
+/// O evento recebe o contexto de rastreamento da tarefa atual, se houver
+/// (ver [`trace::current`]).
+///
+/// Se o arquivo atingir o intervalo de snapshots, a timeline é compactada
+/// logo depois; uma falha na compactação não desfaz o evento.
+///
+/// Durante um plano (ver [`view::plan`]), o evento é devolvido e guardado
+/// no plano, sem entrar na timeline.
+pub async fn append(kind: EventKind) -> Result<Event> {
+    if view::planning() {
+        let last_hash = timeline().read_owned().await.last_hash();
+        return view::record(kind, last_hash).ok_or_else(|| anyhow!("Plano encerrado durante o registro"));
+    }
+    let mut timeline = timeline().write_owned().await;
+    let event = match trace::current() {
+        Some(context) => Event::traced(kind, timeline.last_hash(), &context),
+        None => Event::chained(kind, timeline.last_hash()),
+    };
+    let projection = replay::project(&timeline.state, std::slice::from_ref(&event)).await?;
+    if let Some(file) = timeline.file.as_mut() {
+        file.append(&event)?;
+    }
+    timeline.push([event.clone()]);
+    projection.commit();
+    timeline.compact_if_due();
+    Ok(event)
+}
+
+/// Registra no fim da timeline, em sequência e sem eventos de outros
+/// comandos entre eles, eventos criados numa transação (ver
+/// [`view::transaction`]) e os devolve como registrados.
+///
+/// Os eventos mantêm o ID e o contexto de rastreamento. Como a timeline
+/// pode ter avançado desde que foram criados, o elo com o evento anterior
+/// e o hash são recalculados, e nenhum instante fica antes do evento que o
+/// precede. No arquivo, os eventos são gravados numa única escrita e
+/// levados ao disco em seguida. Como em [`append`], se a projeção sobre o
+/// registro ou a gravação falhar, nenhum dos eventos tem efeito.
+pub async fn append_all(events: Vec<Event>) -> Result<Vec<Event>> {
+    let mut timeline = timeline().write_owned().await;
+    let mut causal_hash = timeline.last_hash();
+    let mut after = timeline.events.last().map(|event| event.timestamp);
+    let mut appended = Vec::with_capacity(events.len());
+    for mut event in events {
+        event.causal_hash = causal_hash;
+        event.timestamp = after.map_or(event.timestamp, |after| event.timestamp.max(after));
+        event.hash = event.compute_hash();
+        causal_hash = Some(event.hash.clone());
+        after = Some(event.timestamp);
+        appended.push(event);
+    }
+    let projection = replay::project(&timeline.state, &appended).await?;
+    if let Some(file) = timeline.file.as_mut() {
+        file.append_all(&appended)?;
+    }
+    timeline.push(appended.iter().cloned());
+    projection.commit();
+    timeline.compact_if_due();
+    Ok(appended)
+}
+
+/// Grava um snapshot do estado atual e remove do arquivo os eventos que ele
+/// cobre.
+///
+/// O snapshot guarda o hash do último evento coberto, e a timeline recomeça
+/// com um `TimelineCompacted` ligado a esse hash, de modo que a cadeia
+/// continua verificável. Devolve `None` se não houver eventos a compactar.
+/// Falha se a timeline não estiver associada a um arquivo.
+pub async fn compact() -> Result<Option<Snapshot>> {
+    timeline().write_owned().await.compact()
+}
+
+/// Último snapshot e eventos registrados depois dele.
+pub async fn history() -> Result<(Option<Snapshot>, Vec<Event>)> {
+    let timeline = timeline().read_owned().await;
+    Ok((timeline.snapshot.clone(), timeline.events.clone()))
+}
+
+/// Leva ao disco os eventos ainda não sincronizados do arquivo da timeline.
+pub async fn sync() -> Result<()> {
+    if let Some(file) = timeline().write_owned().await.file.as_mut() {
+        file.sync()?;
+    }
+    Ok(())
+}
+
+/// Lista os eventos desde o último snapshot, do mais antigo ao mais recente.
+pub async fn list_events() -> Result<Vec<Event>> {
+    Ok(timeline().read_owned().await.events.clone())
+}
+
+/// Lista os eventos de um tipo, pelo nome da variante de [`EventKind`]
+/// (ex: `"IdeaRegistered"`).
+pub async fn find_events_by_kind(kind: &str) -> Result<Vec<Event>> {
+    let timeline = timeline().read_owned().await;
+    Ok(timeline.events.iter().filter(|event| event.kind.name() == kind).cloned().collect())
+}
+
+/// Remove todos os eventos da timeline, inclusive do arquivo, e o snapshot,
+/// esvaziando o estado.
+pub async fn clear_timeline() -> Result<()> {
+    let mut timeline = timeline().write_owned().await;
+    timeline.events.clear();
+    timeline.snapshot = None;
+    timeline.state = State::default();
+    if let Some(file) = timeline.file.as_mut() {
+        file.truncate()?;
+        let path = Snapshot::path_for(file.path());
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Falha ao remover snapshot {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Estado atual: a dobra dos eventos da timeline a partir do último
+/// snapshot.
+pub async fn current_state() -> Result<State> {
+    Ok(timeline().read_owned().await.state.clone())
+}
+
+/// Lê o estado atual sem copiá-lo (ver [`current_state`]).
+pub(crate) async fn read_state<T>(f: impl FnOnce(&State) -> T) -> T {
+    f(&timeline().read_owned().await.state)
+}
+
+/// Estado reconstruído pela dobra dos eventos registrados até o instante
+/// dado, inclusive.
+///
+/// A timeline é percorrida em ordem e a dobra para no primeiro evento
+/// posterior a `at`. Eventos revertidos continuam na timeline: o estado
+/// anterior à reversão pode ser consultado pelo instante dela. Instantes
+/// anteriores ao último snapshot não estão mais disponíveis.
+pub async fn state_at(at: DateTime<Utc>) -> Result<State> {
+    let timeline = timeline().read_owned().await;
+    if let Some(snapshot) = timeline.snapshot.as_ref().filter(|snapshot| at < snapshot.timestamp) {
+        bail!("Estado em {} foi compactado no snapshot de {}", at, snapshot.timestamp);
+    }
+    Ok(State::fold_from(
+        timeline.base_state(),
+        timeline.events.iter().take_while(|event| event.timestamp <= at),
+    ))
+}
+
+/// Verifica a cadeia de hashes da timeline a partir do último snapshot.
+pub async fn verify() -> Result<(), ChainError> {
+    let timeline = timeline().read_owned().await;
+    let anchor = timeline.snapshot.as_ref().map(|snapshot| snapshot.last_hash.as_str());
+    verify_chain_from(&timeline.events, anchor)
+}
+
+/// Verifica uma sequência de eventos: cada hash deve conferir com o
+/// conteúdo do evento, e cada `causal_hash` com o hash do evento anterior.
+///
+/// Devolve o primeiro elo quebrado.
+pub fn verify_chain(events: &[Event]) -> Result<(), ChainError> {
+    verify_chain_from(events, None)
+}
+
+/// Como [`verify_chain`], para eventos que continuam a cadeia a partir de
+/// `anchor`, o hash do último evento coberto por um snapshot.
+pub fn verify_chain_from(events: &[Event], anchor: Option<&str>) -> Result<(), ChainError> {
+    let mut previous = anchor;
+    for (index, event) in events.iter().enumerate() {
+        if event.causal_hash.as_deref() != previous {
+            return Err(ChainError::BrokenLink {
+                index,
+                id: event.id,
+                expected: previous.map(str::to_string),
+                found: event.causal_hash.clone(),
+            });
+        }
+        let computed = event.compute_hash();
+        if event.hash != computed {
+            return Err(ChainError::Tampered {
+                index,
+                id: event.id,
+                recorded: event.hash.clone(),
+                computed,
+            });
+        }
+        previous = Some(&event.hash);
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// === File: runtime/src/trace.rs ===
+/*
+    Description: Contexto de rastreamento dos eventos: trace, evento pai e atores, propagados pela execução dos comandos.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::future::Future;
+use uuid::Uuid;
+use crate::events::Event;
+
+/// Ator registrado quando nenhum outro é informado.
+pub const DEFAULT_ACTOR: &str = "system";
+
+/// Contexto aplicado aos eventos registrados durante uma execução.
+///
+/// Todos os eventos de um mesmo `process_command` compartilham o
+/// `trace_id`. Eventos registrados dentro de outro trabalho (uma simulação
+/// dentro de uma orquestração, por exemplo) apontam para o evento que o
+/// iniciou em `parent_id`, formando uma árvore.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceContext {
+    pub trace_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub actors: Vec<String>,
+}
+
+impl TraceContext {
+    /// Inicia um novo trace, sem evento pai.
+    pub fn new(actors: Vec<String>) -> Self {
+        Self { trace_id: Uuid::new_v4(), parent_id: None, actors }
+    }
+
+    /// Contexto dos eventos filhos de `parent`, no trace dele.
+    pub fn child_of(&self, parent: &Event) -> Self {
+        Self {
+            trace_id: parent.trace_id.unwrap_or(self.trace_id),
+            parent_id: Some(parent.id),
+            actors: self.actors.clone(),
+        }
+    }
+}
+
+tokio::task_local! {
+    static CONTEXT: TraceContext;
+}
+
+/// Contexto da tarefa atual, se houver.
+pub fn current() -> Option<TraceContext> {
+    CONTEXT.try_with(Clone::clone).ok()
+}
+
+/// Executa `future` com o contexto dado.
+///
+/// O contexto vale apenas para a tarefa atual: tarefas criadas com
+/// `tokio::spawn` devem recebê-lo de novo (ver [`current`]).
+pub async fn scope<F: Future>(context: TraceContext, future: F) -> F::Output {
+    CONTEXT.scope(context, future).await
+}
+
+/// Executa `future` com os eventos registrados nele como filhos de
+/// `parent`, no trace dele.
+///
+/// Fora de um contexto, os atores são os do evento pai.
+pub async fn child_of<F: Future>(parent: &Event, future: F) -> F::Output {
+    let context = current().unwrap_or_else(|| TraceContext::new(parent.actors.clone()));
+    scope(context.child_of(parent), future).await
+}
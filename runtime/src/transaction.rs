@@ -0,0 +1,114 @@
+// === File: runtime/src/transaction.rs ===
+/*
+    Description: Transações BEGIN ... COMMIT: os eventos de um lote de comandos são registrados todos juntos, ou nenhum.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use anyhow::{bail, Result};
+use parser::ast::{Command, ImperativeKind};
+use thiserror::Error;
+use tracing::error;
+use crate::events::EventKind;
+use crate::vars::Scope;
+use crate::{executor, replay, timeline, view};
+
+/// Contexto do `ErrorOccurred` de uma transação desfeita.
+pub const ERROR_CONTEXT: &str = "transaction";
+
+/// Transação desfeita. O `ErrorOccurred` da falha já foi registrado.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("Transação desfeita: {cause}")]
+pub struct Aborted {
+    /// Erro do comando que falhou.
+    pub cause: String,
+}
+
+/// Executa os comandos de uma transação e devolve o resultado deles, um
+/// por linha.
+///
+/// Os comandos executam sobre uma cópia sob demanda do estado (ver
+/// [`view::transaction`]): cada um vê o efeito dos anteriores, mas nada
+/// chega ao estado, ao registro nem à timeline antes do fim. Se todos
+/// tiverem sucesso, os eventos são registrados de uma vez, sem eventos de
+/// outros comandos entre eles (ver [`replay::commit_all`]); as variáveis
+/// definidas passam ao escopo. Nenhum outro comando altera o estado entre
+/// o início da transação e o registro (ver [`replay::exclusive`]), de
+/// modo que as validações feitas na cópia continuam valendo.
+///
+/// Se um comando ou o registro falhar, nenhum evento da transação tem
+/// efeito, o escopo não muda e um único `ErrorOccurred` é registrado para
+/// a transação inteira. O erro devolvido é [`Aborted`].
+///
+/// Simulações gravam os próprios resultados, que não podem ser desfeitos:
+/// uma transação com `SIMULATE ENTITY` falha antes de executar qualquer
+/// comando.
+pub async fn execute(body: &[Command], scope: &mut Scope) -> Result<String> {
+    let mut batch_scope = scope.clone();
+    let batch = async {
+        check(body)?;
+        executor::execute_block(body, &mut batch_scope).await
+    };
+    let result = replay::exclusive(async {
+        let (result, events) = view::transaction(batch).await;
+        let output = result?;
+        replay::commit_all(events).await?;
+        Ok::<_, anyhow::Error>(output)
+    })
+    .await;
+
+    match result {
+        Ok(output) => {
+            *scope = batch_scope;
+            Ok(output)
+        }
+        Err(e) => {
+            // Uma transação interna desfeita já descreve a causa
+            let cause = match e.downcast_ref::<Aborted>() {
+                Some(inner) => inner.cause.clone(),
+                None => e.to_string(),
+            };
+            let aborted = Aborted { cause };
+            error!("{}", aborted);
+            timeline::append(EventKind::ErrorOccurred { context: ERROR_CONTEXT.to_string(), message: aborted.to_string() })
+                .await?;
+            Err(aborted.into())
+        }
+    }
+}
+
+/// Verifica se todos os comandos, inclusive os de blocos internos, podem
+/// fazer parte de uma transação.
+fn check(commands: &[Command]) -> Result<()> {
+    for command in commands {
+        match command {
+            Command::Imperative(imperative) => check_kind(&imperative.kind)?,
+            Command::Let { .. } => {}
+            Command::If { then_branch, else_branch, .. } => {
+                check(then_branch)?;
+                check(else_branch.as_deref().unwrap_or_default())?;
+            }
+            Command::ForEach { body, .. } | Command::Transaction { body } => check(body)?,
+            Command::With { body, on_failure, .. } => {
+                check(body)?;
+                check(on_failure.as_deref().unwrap_or_default())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_kind(kind: &ImperativeKind) -> Result<()> {
+    match kind {
+        ImperativeKind::SimulateEntity { id, .. } => {
+            bail!("SIMULATE ENTITY {} não pode fazer parte de uma transação: a simulação não pode ser desfeita", id)
+        }
+        ImperativeKind::Orchestrate { steps, .. } => steps.iter().try_for_each(|step| check_kind(&step.imperative.kind)),
+        _ => Ok(()),
+    }
+}
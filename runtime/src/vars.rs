@@ -0,0 +1,170 @@
+// === File: runtime/src/vars.rs ===
+/*
+    Description: Escopo de variáveis dos scripts LogLine e resolução de interpolações `${nome}`.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::HashMap;
+use chrono::Utc;
+use thiserror::Error;
+use uuid::Uuid;
+use parser::ast::{Imperative, ImperativeKind, Step};
+use parser::template::{self, Segment};
+
+/// Variáveis embutidas, calculadas a cada referência.
+pub const BUILTINS: &[&str] = &["timestamp", "current_time", "random_id", "uuid"];
+
+/// Erros na resolução de variáveis.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum VarError {
+    #[error("Variável não definida: {0}")]
+    Undefined(String),
+
+    #[error("Interpolação inválida: {0}")]
+    Malformed(String),
+}
+
+/// Escopo de variáveis de um script em execução.
+///
+/// As variáveis definidas com `LET` têm precedência sobre as embutidas:
+/// `timestamp` (segundos desde a época Unix), `current_time` (RFC 3339,
+/// UTC), `random_id` (6 dígitos hexadecimais) e `uuid` (UUID v4).
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    bindings: HashMap<String, String>,
+}
+
+impl Scope {
+    /// Cria um escopo vazio (apenas com as variáveis embutidas).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Associa um valor, já resolvido, a uma variável.
+    pub fn bind(&mut self, name: &str, value: &str) {
+        self.bindings.insert(name.to_string(), value.to_string());
+    }
+
+    /// Busca o valor de uma variável definida ou embutida.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.bindings.get(name).cloned().or_else(|| builtin(name))
+    }
+
+    /// Substitui todas as interpolações `${nome}` de um texto; `$${` é
+    /// escrito como `${` literal.
+    pub fn interpolate(&self, text: &str) -> Result<String, VarError> {
+        let segments = template::segments(text).map_err(VarError::Malformed)?;
+        let mut out = String::with_capacity(text.len());
+
+        for segment in segments {
+            match segment {
+                Segment::Literal(literal) => out.push_str(literal),
+                Segment::Variable(name) => {
+                    let value = self.get(name).ok_or_else(|| VarError::Undefined(name.to_string()))?;
+                    out.push_str(&value);
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Devolve uma cópia do comando imperativo com as interpolações resolvidas.
+    pub fn resolve_kind(&self, kind: &ImperativeKind) -> Result<ImperativeKind, VarError> {
+        let resolved = match kind {
+            ImperativeKind::DefineContract { id, clauses } => ImperativeKind::DefineContract {
+                id: self.interpolate(id)?,
+                clauses: clauses
+                    .iter()
+                    .map(|c| self.interpolate(c))
+                    .collect::<Result<_, _>>()?,
+            },
+            ImperativeKind::DefineIdea { id, text } => ImperativeKind::DefineIdea {
+                id: self.interpolate(id)?,
+                text: self.interpolate(text)?,
+            },
+            ImperativeKind::SimulateEntity { id, rounds } => ImperativeKind::SimulateEntity {
+                id: self.interpolate(id)?,
+                rounds: *rounds,
+            },
+            ImperativeKind::Orchestrate { mode, concurrency, steps } => ImperativeKind::Orchestrate {
+                mode: self.interpolate(mode)?,
+                concurrency: *concurrency,
+                steps: steps
+                    .iter()
+                    .map(|step| {
+                        Ok(Step {
+                            name: step.name.as_deref().map(|n| self.interpolate(n)).transpose()?,
+                            depends_on: step
+                                .depends_on
+                                .iter()
+                                .map(|d| self.interpolate(d))
+                                .collect::<Result<_, _>>()?,
+                            imperative: Imperative { kind: self.resolve_kind(&step.imperative.kind)? },
+                        })
+                    })
+                    .collect::<Result<_, VarError>>()?,
+            },
+            ImperativeKind::InvokeRuleset { entity_id, ruleset_id } => ImperativeKind::InvokeRuleset {
+                entity_id: self.interpolate(entity_id)?,
+                ruleset_id: self.interpolate(ruleset_id)?,
+            },
+            ImperativeKind::AmendContract { id, clauses } => ImperativeKind::AmendContract {
+                id: self.interpolate(id)?,
+                clauses: clauses
+                    .iter()
+                    .map(|c| self.interpolate(c))
+                    .collect::<Result<_, _>>()?,
+            },
+            ImperativeKind::TagIdea { id, tags } => ImperativeKind::TagIdea {
+                id: self.interpolate(id)?,
+                tags: tags
+                    .iter()
+                    .map(|t| self.interpolate(t))
+                    .collect::<Result<_, _>>()?,
+            },
+            ImperativeKind::UpdateIdea { id, text } => ImperativeKind::UpdateIdea {
+                id: self.interpolate(id)?,
+                text: self.interpolate(text)?,
+            },
+            ImperativeKind::RetireEntity { id } => ImperativeKind::RetireEntity {
+                id: self.interpolate(id)?,
+            },
+            ImperativeKind::Revert { event_id } => ImperativeKind::Revert {
+                event_id: self.interpolate(event_id)?,
+            },
+            ImperativeKind::ShowContract { id } => ImperativeKind::ShowContract {
+                id: self.interpolate(id)?,
+            },
+            ImperativeKind::ListIdeas { tag } => ImperativeKind::ListIdeas {
+                tag: tag.as_deref().map(|t| self.interpolate(t)).transpose()?,
+            },
+            ImperativeKind::FindSpans { kind, limit } => ImperativeKind::FindSpans {
+                kind: kind.as_deref().map(|k| self.interpolate(k)).transpose()?,
+                limit: *limit,
+            },
+            ImperativeKind::ShowSimulation { entity_id, last } => ImperativeKind::ShowSimulation {
+                entity_id: self.interpolate(entity_id)?,
+                last: *last,
+            },
+        };
+        Ok(resolved)
+    }
+}
+
+/// Calcula o valor de uma variável embutida.
+fn builtin(name: &str) -> Option<String> {
+    match name {
+        "timestamp" => Some(Utc::now().timestamp().to_string()),
+        "current_time" => Some(Utc::now().to_rfc3339()),
+        "random_id" => Some(Uuid::new_v4().simple().to_string()[..6].to_string()),
+        "uuid" => Some(Uuid::new_v4().to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,268 @@
+// === File: runtime/src/view.rs ===
+/*
+    Description: Visão do registro, dos contratos e das ideias lida pelo executor: o estado da timeline ou, num plano, uma cópia sob demanda dele.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use crate::events::{Event, EventKind};
+use crate::state::{EntityState, State};
+use crate::{timeline, trace};
+
+/// Alterações de um plano sobre o estado da timeline.
+///
+/// Um item é copiado do estado na primeira vez que um evento do
+/// plano o altera; a partir daí, é lido daqui. Itens alterados que não
+/// estão em `state` foram removidos pelo plano.
+#[derive(Clone, Default)]
+pub(crate) struct Overlay {
+    /// Se o plano nunca será registrado (ver [`plan`]), ao contrário do de
+    /// uma transação (ver [`transaction`])
+    dry_run: bool,
+    state: State,
+    contracts: HashSet<String>,
+    ideas: HashSet<String>,
+    entities: HashSet<String>,
+    /// Eventos que seriam registrados, encadeados como na timeline
+    events: Vec<Event>,
+}
+
+/// Plano compartilhado pelas tarefas de uma execução.
+pub(crate) type Plan = Arc<Mutex<Overlay>>;
+
+tokio::task_local! {
+    static PLAN: Plan;
+}
+
+/// Executa `future` como um plano e devolve o resultado com os eventos que
+/// ele teria registrado.
+///
+/// Durante o plano, a timeline não recebe eventos (ver
+/// [`crate::timeline::append`]) e os eventos de [`crate::replay::commit`]
+/// alteram apenas a cópia sob demanda lida pelas funções deste módulo.
+pub async fn plan<F: Future>(future: F) -> (F::Output, Vec<Event>) {
+    let plan = Plan::new(Mutex::new(Overlay { dry_run: true, ..Overlay::default() }));
+    let output = PLAN.scope(plan.clone(), future).await;
+    let events = std::mem::take(&mut lock(&plan).events);
+    (output, events)
+}
+
+/// Se a tarefa atual executa um plano ou uma transação: seus eventos vão
+/// para o plano, e não para a timeline.
+pub fn planning() -> bool {
+    PLAN.try_with(|_| ()).is_ok()
+}
+
+/// Se a tarefa atual executa um plano que nunca será registrado (ver
+/// [`plan`]). Nele, nem ações fora dos armazenamentos, como simulações,
+/// são executadas.
+pub fn dry_run() -> bool {
+    read(|overlay| overlay.dry_run).unwrap_or(false)
+}
+
+/// Executa `future` como uma transação: os eventos ficam num plano até o
+/// fim e só são devolvidos se ela tiver sucesso, para serem registrados de
+/// uma vez (ver [`crate::replay::commit_all`]). Se falhar, nenhum evento
+/// nem alteração sobrevive.
+///
+/// Dentro de um plano ou de outra transação, os eventos continuam no plano
+/// externo, e uma falha o devolve ao estado em que estava antes dela.
+pub(crate) async fn transaction<T, F>(future: F) -> (Result<T>, Vec<Event>)
+where
+    F: Future<Output = Result<T>>,
+{
+    if let Some(plan) = current() {
+        let checkpoint = lock(&plan).clone();
+        let result = future.await;
+        if result.is_err() {
+            *lock(&plan) = checkpoint;
+        }
+        return (result, Vec::new());
+    }
+
+    let plan = Plan::default();
+    let result = PLAN.scope(plan.clone(), future).await;
+    let events = match &result {
+        Ok(_) => std::mem::take(&mut lock(&plan).events),
+        Err(_) => Vec::new(),
+    };
+    (result, events)
+}
+
+/// Plano da tarefa atual, se houver.
+///
+/// Como o contexto de [`trace`], o plano vale apenas para a tarefa atual:
+/// tarefas criadas com `tokio::spawn` devem recebê-lo de novo (ver
+/// [`scope`]).
+pub(crate) fn current() -> Option<Plan> {
+    PLAN.try_with(Clone::clone).ok()
+}
+
+/// Executa `future` no plano dado, se houver.
+pub(crate) async fn scope<F: Future>(plan: Option<Plan>, future: F) -> F::Output {
+    match plan {
+        Some(plan) => PLAN.scope(plan, future).await,
+        None => future.await,
+    }
+}
+
+/// Registra um evento no plano da tarefa atual em vez da timeline, ligado
+/// ao último evento do plano ou, no primeiro, a `last_hash`.
+pub(crate) fn record(kind: EventKind, last_hash: Option<String>) -> Option<Event> {
+    let plan = current()?;
+    let mut overlay = lock(&plan);
+    let causal_hash = overlay.events.last().map(|event| event.hash.clone()).or(last_hash);
+    let event = match trace::current() {
+        Some(context) => Event::traced(kind, causal_hash, &context),
+        None => Event::chained(kind, causal_hash),
+    };
+    overlay.events.push(event.clone());
+    Some(event)
+}
+
+/// Aplica um evento do plano à cópia sob demanda, copiando antes do estado
+/// da timeline os itens que ele altera.
+pub(crate) async fn apply(event: &Event) -> Result<()> {
+    let plan = current().ok_or_else(|| anyhow!("Nenhum plano em execução"))?;
+    let (contract, idea, entity) = touched(&event.kind);
+    let (contract_copy, idea_copy, entity_copy) = timeline::read_state(|state| {
+        (
+            contract.and_then(|id| state.contracts.get(id).cloned()),
+            idea.and_then(|id| state.ideas.get(id).cloned()),
+            entity.and_then(|id| state.entities.get(id).cloned()),
+        )
+    })
+    .await;
+
+    let mut overlay = lock(&plan);
+    let overlay = &mut *overlay;
+    copy(&mut overlay.contracts, &mut overlay.state.contracts, contract, contract_copy);
+    copy(&mut overlay.ideas, &mut overlay.state.ideas, idea, idea_copy);
+    copy(&mut overlay.entities, &mut overlay.state.entities, entity, entity_copy);
+    overlay.state.apply(event);
+    Ok(())
+}
+
+/// Copia para o plano um item ainda não alterado por ele.
+fn copy<T>(touched: &mut HashSet<String>, items: &mut BTreeMap<String, T>, id: Option<&str>, item: Option<T>) {
+    let Some(id) = id else {
+        return;
+    };
+    if touched.insert(id.to_string()) {
+        if let Some(item) = item {
+            items.insert(id.to_string(), item);
+        }
+    }
+}
+
+/// Contrato, ideia e entidade alterados por um evento.
+fn touched(kind: &EventKind) -> (Option<&str>, Option<&str>, Option<&str>) {
+    match kind {
+        EventKind::ContractRegistered { id, .. } => (Some(id.as_str()), None, Some(id.as_str())),
+        EventKind::IdeaRegistered { id, .. } => (None, Some(id.as_str()), Some(id.as_str())),
+        EventKind::ContractAmended { id, .. } | EventKind::ContractDeleted { id } => (Some(id.as_str()), None, None),
+        EventKind::IdeaTagged { id, .. }
+        | EventKind::IdeaUntagged { id, .. }
+        | EventKind::IdeaUpdated { id, .. }
+        | EventKind::IdeaDeleted { id } => (None, Some(id.as_str()), None),
+        EventKind::EntityRegistered { id, .. }
+        | EventKind::EntityCreated { id, .. }
+        | EventKind::EntityUpdated { id, .. }
+        | EventKind::EntityRetired { id } => (None, None, Some(id.as_str())),
+        _ => (None, None, None),
+    }
+}
+
+/// Contrato pelo ID: `(id, cláusulas, criação)`.
+pub async fn get_contract(id: &str) -> Result<(String, Vec<String>, DateTime<Utc>)> {
+    let contract = lookup(|overlay| &overlay.contracts, id, |state| state.contracts.get(id).cloned()).await;
+    let contract = contract.ok_or_else(|| anyhow!("Contrato com ID '{}' não encontrado", id))?;
+    Ok((contract.id, contract.clauses, contract.created_at))
+}
+
+/// Ideia pelo ID: `(id, texto, criação)`.
+pub async fn get_idea(id: &str) -> Result<(String, String, DateTime<Utc>)> {
+    let idea = lookup(|overlay| &overlay.ideas, id, |state| state.ideas.get(id).cloned()).await;
+    let idea = idea.ok_or_else(|| anyhow!("Ideia com ID '{}' não encontrada", id))?;
+    Ok((idea.id, idea.text, idea.created_at))
+}
+
+/// Tags de uma ideia.
+pub async fn get_tags(id: &str) -> Result<Vec<String>> {
+    let tags = lookup(|overlay| &overlay.ideas, id, |state| state.ideas.get(id).map(|idea| idea.tags.clone())).await;
+    tags.ok_or_else(|| anyhow!("Ideia com ID '{}' não encontrada", id))
+}
+
+/// Entidade do registro pelo ID: `(id, tipo)`.
+pub async fn fetch_entity(id: &str) -> Result<(String, String)> {
+    let entity = get_entity(id).await.ok_or_else(|| anyhow!("Entidade não encontrada: {}", id))?;
+    Ok((entity.id, entity.entity_type))
+}
+
+/// Entidade do registro pelo ID, com dono, metadados e versão.
+pub async fn get_entity(id: &str) -> Option<EntityState> {
+    lookup(|overlay| &overlay.entities, id, |state| state.entities.get(id).cloned()).await
+}
+
+/// IDs de todos os contratos, sem ordem definida.
+pub async fn list_contracts() -> Vec<String> {
+    let mut ids = timeline::read_state(|state| state.contracts.keys().cloned().collect()).await;
+    read(|overlay| merge(&mut ids, &overlay.contracts, |id| overlay.state.contracts.contains_key(id)));
+    ids
+}
+
+/// IDs de todas as ideias, sem ordem definida.
+pub async fn list_ideas() -> Vec<String> {
+    let mut ids = timeline::read_state(|state| state.ideas.keys().cloned().collect()).await;
+    read(|overlay| merge(&mut ids, &overlay.ideas, |id| overlay.state.ideas.contains_key(id)));
+    ids
+}
+
+/// IDs das entidades de um tipo, sem ordem definida.
+pub async fn list_entities_by_type(entity_type: &str) -> Vec<String> {
+    let of_type = |entity: &EntityState| entity.entity_type == entity_type;
+    let mut ids = timeline::read_state(|state| {
+        state.entities.values().filter(|entity| of_type(entity)).map(|entity| entity.id.clone()).collect()
+    })
+    .await;
+    read(|overlay| merge(&mut ids, &overlay.entities, |id| overlay.state.entities.get(id).is_some_and(of_type)));
+    ids
+}
+
+/// Item lido do plano, se ele o alterou, ou do estado da timeline.
+async fn lookup<T>(
+    touched: impl Fn(&Overlay) -> &HashSet<String>,
+    id: &str,
+    get: impl Fn(&State) -> Option<T>,
+) -> Option<T> {
+    match read(|overlay| touched(overlay).contains(id).then(|| get(&overlay.state))).flatten() {
+        Some(planned) => planned,
+        None => timeline::read_state(get).await,
+    }
+}
+
+/// Substitui, na lista do estado da timeline, os itens alterados pelo
+/// plano pelos que ainda existem nele.
+fn merge(ids: &mut Vec<String>, touched: &HashSet<String>, exists: impl Fn(&str) -> bool) {
+    ids.retain(|id| !touched.contains(id));
+    ids.extend(touched.iter().filter(|id| exists(id)).cloned());
+}
+
+/// Lê o plano da tarefa atual, se houver.
+fn read<T>(f: impl FnOnce(&Overlay) -> T) -> Option<T> {
+    PLAN.try_with(|plan| f(&lock(plan))).ok()
+}
+
+fn lock(plan: &Plan) -> MutexGuard<'_, Overlay> {
+    plan.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
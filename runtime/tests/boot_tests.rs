@@ -0,0 +1,116 @@
+// === File: runtime/tests/boot_tests.rs ===
+/*
+    Description: Testes do boot nativo de um lllpack a partir do manifesto e da boot_sequence.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use runtime::boot::{AGENT_ENTITY_TYPE, REPORT_SPAN};
+use runtime::events::EventKind;
+use runtime::{boot, timeline, view, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+/// Raiz do lllpack do repositório.
+fn lllpack() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../lllpack")
+}
+
+#[tokio::test]
+async fn test_boot_lllpack() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let report = boot(lllpack()).await?;
+
+            assert_eq!(report.pack, "logline-core-institutional");
+            assert_eq!(report.status, "boot_complete", "Boot deveria concluir: {:?}", report.steps);
+            let spans: Vec<&str> = report.steps.iter().map(|s| s.span.as_str()).collect();
+            assert_eq!(spans, vec!["InitializeTimeline", "LoadContracts", "ActivateAgents", "ConnectEmbeddings", "RunDiagnostic"]);
+            assert_eq!(report.contracts, vec!["Constituicao", "Onboarding", "RegrasTenant"]);
+            assert_eq!(report.agents.len(), 3, "Agentes do manifesto deveriam ser ativados");
+
+            // Os campos de on_success são resolvidos com os resultados dos passos
+            let load = &report.steps[1];
+            assert_eq!(load.fields["status"], "loaded");
+            assert_eq!(load.fields["contracts_count"], "3");
+            assert_eq!(report.steps[3].fields["vectors_count"], "1254");
+            assert_eq!(report.steps[4].fields["health_status"], "healthy");
+
+            let constituicao = view::get_contract("Constituicao").await.expect("Contrato não registrado");
+            assert!(!constituicao.1.is_empty(), "Contrato deveria ter as cláusulas do .lll");
+            let (_, entity_type) = view::fetch_entity(&report.agents[0]).await.expect("Agente não registrado");
+            assert_eq!(entity_type, AGENT_ENTITY_TYPE);
+
+            let spans = timeline::find_events_by_kind("BootSpan").await?;
+            let summary = spans
+                .iter()
+                .find(|e| matches!(&e.kind, EventKind::BootSpan { span, status, .. } if span == REPORT_SPAN && status == "boot_complete"))
+                .expect("Resumo do boot não registrado");
+            assert!(
+                matches!(&summary.kind, EventKind::BootSpan { fields, .. } if fields["contracts_loaded"] == "3"),
+                "Resumo incorreto: {:?}",
+                summary.kind
+            );
+            assert_eq!(summary.actors, vec!["system".to_string()], "Atores do span deveriam ser registrados");
+
+            // Um segundo boot não altera o estado
+            let before = timeline::current_state().await?;
+            assert_eq!(boot(lllpack()).await?.status, "boot_complete");
+            assert_eq!(timeline::current_state().await?, before, "Boot repetido não deveria alterar o estado");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_boot_reports_failed_steps() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            // Pacote sem os agentes declarados e sem o índice de embeddings
+            let pack = std::env::temp_dir().join(format!("lllpack-{}", Uuid::new_v4()));
+            fs::create_dir_all(pack.join("spans"))?;
+            fs::copy(lllpack().join("spans/boot_sequence.lll"), pack.join("spans/boot_sequence.lll"))?;
+            let manifest = fs::read_to_string(lllpack().join("lllpack.yaml"))?;
+            let boot_sequence = &manifest[manifest.find("boot_sequence:").unwrap()..manifest.find("# Triggers").unwrap()];
+            fs::write(
+                pack.join("lllpack.yaml"),
+                format!(
+                    "name: \"incompleto\"\nversion: \"0.1.0\"\nentrypoint: \"spans/boot_sequence.lll\"\nagents:\n  - \"agents/ausente.lll\"\n{}",
+                    boot_sequence
+                ),
+            )?;
+
+            let report = boot(&pack).await?;
+            assert_eq!(report.status, "boot_failed");
+            let status: Vec<&str> = report.steps.iter().map(|s| s.status.as_str()).collect();
+            assert_eq!(status, vec!["succeeded", "succeeded", "failed", "failed", "skipped"]);
+            assert!(report.steps[3].error.as_deref().unwrap_or_default().contains("knowledge.index"), "Erro deveria indicar o índice");
+
+            let spans = timeline::find_events_by_kind("BootSpan").await?;
+            assert!(
+                spans.iter().any(|e| matches!(&e.kind, EventKind::BootSpan { span, .. } if span == "EmbeddingConnectFailure")),
+                "Span de on_failure deveria ser registrado"
+            );
+
+            // A timeline é a do runtime, não a do pacote
+            fs::write(
+                pack.join("lllpack.yaml"),
+                "name: \"timeline\"\nversion: \"0.1.0\"\nentrypoint: \"spans/boot_sequence.lll\"\ntimeline: \"timeline/main.timeline\"\n",
+            )?;
+            let error = boot(&pack).await.unwrap_err().to_string();
+            assert!(error.contains("LOGLINE_TIMELINE_PATH"), "Timeline declarada deveria ser rejeitada: {}", error);
+
+            fs::write(pack.join("lllpack.yaml"), "name: \"quebrado\"\n")?;
+            assert!(boot(&pack).await.is_err(), "Manifesto inválido deveria falhar");
+            fs::remove_dir_all(&pack)?;
+            Ok(())
+        })
+        .await
+}
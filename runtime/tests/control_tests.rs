@@ -0,0 +1,92 @@
+// === File: runtime/tests/control_tests.rs ===
+/*
+    Description: Testes da avaliação de condições e listas de IF e FOR EACH no runtime do LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use parser::{parse, ast::Command};
+use runtime::control::{evaluate, list_items};
+use runtime::events::EventKind;
+use runtime::{replay, Runtime};
+use runtime::vars::Scope;
+use anyhow::Result;
+
+/// Extrai a condição de um comando IF.
+async fn eval_if(input: &str, scope: &Scope) -> Result<bool> {
+    match parse(input)? {
+        Command::If { condition, .. } => evaluate(&condition, scope).await,
+        other => panic!("Comando deveria ser IF: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_verdict_condition() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let scope = Scope::new();
+
+            assert!(eval_if("IF VERDICT(always-accept ON e-1) == Accepted THEN ORCHESTRATE x", &scope).await?);
+            assert!(!eval_if("IF VERDICT(always-reject ON e-1) == Accepted THEN ORCHESTRATE x", &scope).await?);
+            assert!(eval_if("IF VERDICT(always-reject ON e-1) != Accepted THEN ORCHESTRATE x", &scope).await?);
+
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_exists_condition() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("control-contract-{}", uuid::Uuid::new_v4());
+            let mut scope = Scope::new();
+            scope.bind("alvo", &id);
+
+            assert!(!eval_if("IF EXISTS CONTRACT ${alvo} THEN ORCHESTRATE x", &scope).await?, "Contrato ainda não existe");
+
+            replay::commit(EventKind::ContractRegistered { id: id.clone(), clauses: vec!["Termos".to_string()] }).await?;
+            assert!(eval_if("IF EXISTS CONTRACT ${alvo} THEN ORCHESTRATE x", &scope).await?, "Contrato deveria existir");
+            assert!(!eval_if("IF NOT EXISTS CONTRACT ${alvo} THEN ORCHESTRATE x", &scope).await?, "Negação incorreta");
+            assert!(eval_if("IF EXISTS ENTITY ${alvo} THEN ORCHESTRATE x", &scope).await?, "Entidade deveria existir");
+
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_list_items() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let entity_type = format!("CONTROL_{}", uuid::Uuid::new_v4().simple());
+            for id in ["control-b", "control-a"] {
+                replay::commit(EventKind::EntityRegistered { id: id.to_string(), entity_type: entity_type.clone() }).await?;
+            }
+
+            let mut scope = Scope::new();
+            scope.bind("tipo", &entity_type);
+
+            let source = match parse("FOR EACH id IN LIST ENTITIES ${tipo} DO ORCHESTRATE ${id}")? {
+                Command::ForEach { source, .. } => source,
+                other => panic!("Comando deveria ser FOR EACH: {:?}", other),
+            };
+            let items = list_items(&source, &scope).await?;
+            assert_eq!(items, vec!["control-a".to_string(), "control-b".to_string()], "Itens devem vir ordenados");
+
+            let source = match parse("FOR EACH x IN [z, \"${tipo}\", a] DO ORCHESTRATE ${x}")? {
+                Command::ForEach { source, .. } => source,
+                other => panic!("Comando deveria ser FOR EACH: {:?}", other),
+            };
+            let items = list_items(&source, &scope).await?;
+            assert_eq!(items, vec!["z".to_string(), entity_type, "a".to_string()], "Lista literal mantém a ordem");
+
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,140 @@
+// === File: runtime/tests/entities_tests.rs ===
+/*
+    Description: Testes da criação e alteração de entidades registradas na timeline.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::entities::{create_entity, update_entity};
+use runtime::events::{Event, EventKind};
+use runtime::revert::compensations;
+use runtime::state::State;
+use runtime::{init_with_timeline, timeline, view, Runtime};
+use registry::{Entity, EntityType, EntityUpdate, RegistryError};
+use anyhow::Result;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Caminho de um arquivo de timeline temporário, único por teste.
+fn temp_timeline() -> PathBuf {
+    std::env::temp_dir().join(format!("logline-{}", Uuid::new_v4())).join("main.timeline")
+}
+
+/// Eventos encadeados pelo hash.
+fn chain(kinds: Vec<EventKind>) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    for kind in kinds {
+        let previous = events.last().map(|e| e.hash.clone());
+        events.push(Event::chained(kind, previous));
+    }
+    events
+}
+
+#[test]
+fn test_entity_version_only_increases() -> Result<()> {
+    let events = chain(vec![
+        EventKind::EntityCreated {
+            id: "e-1".to_string(),
+            entity_type: "AGENT".to_string(),
+            owner: Some("tenant-a".to_string()),
+            metadata: json!({ "modelo": "v1" }),
+            version: 1,
+        },
+        EventKind::EntityUpdated {
+            id: "e-1".to_string(),
+            entity_type: "AGENT".to_string(),
+            owner: Some("tenant-a".to_string()),
+            metadata: json!({ "modelo": "v2" }),
+            version: 2,
+        },
+        // Registrar com o mesmo tipo não altera a entidade
+        EventKind::EntityRegistered { id: "e-1".to_string(), entity_type: "AGENT".to_string() },
+        // Com outro tipo, altera apenas o tipo
+        EventKind::EntityRegistered { id: "e-1".to_string(), entity_type: "IDEA".to_string() },
+        EventKind::EntityRetired { id: "e-1".to_string() },
+    ]);
+
+    let state = State::fold(&events[..4]);
+    let entity = &state.entities["e-1"];
+    assert_eq!((entity.entity_type.as_str(), entity.version), ("IDEA", 3), "Tipo novo deveria subir a versão");
+    assert_eq!(entity.owner.as_deref(), Some("tenant-a"), "Dono deveria ser mantido");
+    assert_eq!(entity.metadata, json!({ "modelo": "v2" }), "Metadados deveriam ser mantidos");
+    assert_eq!(entity.created_at, events[0].timestamp, "Criação não deveria mudar");
+
+    // Reverter uma alteração devolve os campos anteriores na versão seguinte
+    assert_eq!(
+        compensations(&events[..2], events[1].id)?,
+        vec![EventKind::EntityUpdated {
+            id: "e-1".to_string(),
+            entity_type: "AGENT".to_string(),
+            owner: Some("tenant-a".to_string()),
+            metadata: json!({ "modelo": "v1" }),
+            version: 3,
+        }]
+    );
+    // Reverter a remoção recria a entidade completa, também sem repetir versões
+    assert_eq!(
+        compensations(&events, events[4].id)?,
+        vec![EventKind::EntityCreated {
+            id: "e-1".to_string(),
+            entity_type: "IDEA".to_string(),
+            owner: Some("tenant-a".to_string()),
+            metadata: json!({ "modelo": "v2" }),
+            version: 4,
+        }]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_entity_changes_survive_replay_and_snapshot() -> Result<()> {
+    let path = temp_timeline();
+    let id = format!("entidade-{}", Uuid::new_v4().simple());
+    let instance = Runtime::in_memory();
+    let registry = instance.registry();
+    instance
+        .scope(async {
+            init_with_timeline(&path).await?;
+
+            let created = create_entity(Entity::new(&id, EntityType::Agent).with_owner("tenant-a")).await?;
+            assert_eq!(created.version, 1, "Entidade deveria começar na versão 1");
+            let duplicate = create_entity(Entity::new(&id, EntityType::Idea)).await.unwrap_err();
+            assert_eq!(duplicate.downcast_ref(), Some(&RegistryError::AlreadyExists(id.clone())));
+
+            let update = EntityUpdate { metadata: Some(json!({ "modelo": "v2" })), ..EntityUpdate::default() };
+            let updated = update_entity(&id, 1, update).await?;
+            assert_eq!(updated.version, 2, "Alteração deveria subir a versão");
+            assert_eq!(updated.owner.as_deref(), Some("tenant-a"), "Campos não informados deveriam ser mantidos");
+            let stale = EntityUpdate { owner: Some("tenant-b".to_string()), ..EntityUpdate::default() };
+            assert_eq!(
+                update_entity(&id, 1, stale).await.unwrap_err().downcast_ref(),
+                Some(&RegistryError::VersionConflict { id: id.clone(), expected: 1, found: 2 })
+            );
+            let events = timeline::find_events_by_kind("EntityUpdated").await?;
+            assert_eq!(events.len(), 1, "Apenas a alteração aceita deveria ser registrada");
+            assert_eq!(registry.get_entity(&id).await?, updated, "Registro deveria refletir a alteração");
+
+            // Replay do arquivo
+            init_with_timeline(&path).await?;
+            assert_eq!(view::get_entity(&id).await.map(|entity| entity.to_entity()), Some(updated.clone()));
+            assert_eq!(registry.get_entity(&id).await?, updated, "Registro deveria ser reconstruído do arquivo");
+
+            // Replay a partir de um snapshot
+            timeline::compact().await?.expect("Deveria haver eventos a compactar");
+            init_with_timeline(&path).await?;
+            assert_eq!(view::get_entity(&id).await.map(|entity| entity.to_entity()), Some(updated.clone()));
+            assert_eq!(registry.get_entity(&id).await?, updated, "Registro deveria ser reconstruído do snapshot");
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+
+    fs::remove_dir_all(path.parent().unwrap())?;
+    Ok(())
+}
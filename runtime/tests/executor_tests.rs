@@ -0,0 +1,85 @@
+// === File: runtime/tests/executor_tests.rs ===
+/*
+    Description: Testes do executor de scripts do runtime do LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::{process_command, timeline, view, events::EventKind, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_script_with_variables_and_control_flow() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let suffix = Uuid::new_v4().simple().to_string();
+            let script = format!(
+                "LET prefixo = \"exec-{}\"\n\
+                 DEFINE CONTRACT ${{prefixo}}-c \"Cláusula\"\n\
+                 FOR EACH id IN [a, b] DO {{\n\
+                 \x20   DEFINE IDEA ${{prefixo}}-${{id}} \"Ideia ${{id}}\"\n\
+                 }}\n\
+                 IF EXISTS CONTRACT ${{prefixo}}-c THEN {{\n\
+                 \x20   TAG IDEA ${{prefixo}}-a urgente\n\
+                 }} ELSE {{\n\
+                 \x20   RETIRE ENTITY ${{prefixo}}-c\n\
+                 }}\n",
+                suffix
+            );
+
+            let result = process_command(&script).await?;
+            let lines: Vec<_> = result.lines().collect();
+            assert_eq!(lines.len(), 5, "Deveria haver uma linha por comando executado: {}", result);
+            assert!(lines[3].starts_with("Ideia registrada"), "Resultado incorreto: {}", lines[3]);
+            assert!(lines[4].contains("urgente"), "Bloco THEN deveria ser executado: {}", lines[4]);
+
+            let prefix = format!("exec-{}", suffix);
+            let (_, entity_type) = view::fetch_entity(&format!("{}-b", prefix)).await.expect("Ideia não registrada");
+            assert_eq!(entity_type, "IDEA", "Tipo da entidade incorreto");
+            assert_eq!(view::get_tags(&format!("{}-a", prefix)).await.expect("Ideia ausente"), vec!["urgente".to_string()]);
+
+            let tagged = timeline::find_events_by_kind("IdeaTagged").await?;
+            assert!(
+                tagged.iter().any(|e| e.kind == EventKind::IdeaTagged { id: format!("{}-a", prefix), tags: vec!["urgente".to_string()] }),
+                "Evento de tags ausente"
+            );
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_queries_and_execution_errors() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("exec-query-{}", Uuid::new_v4().simple());
+            process_command(&format!("DEFINE CONTRACT {} \"A\", \"B\"", id)).await?;
+
+            let result = process_command(&format!("SHOW CONTRACT {}", id)).await?;
+            let json: serde_json::Value = serde_json::from_str(&result)?;
+            assert_eq!(json["clauses"], serde_json::json!(["A", "B"]), "Consulta deveria devolver JSON");
+
+            let executed = timeline::find_events_by_kind("ImperativeExecuted").await?;
+            assert!(
+                executed.iter().any(|e| e.kind == EventKind::ImperativeExecuted { kind: format!("SHOW CONTRACT {}", id) }),
+                "Consulta deveria ser registrada com o texto canônico"
+            );
+
+            // Erros de execução não são erros de sintaxe
+            let err = process_command("AMEND CONTRACT exec-inexistente \"X\"").await.expect_err("Contrato inexistente");
+            assert!(err.downcast_ref::<parser::Error>().is_none(), "Erro não deveria ser de sintaxe");
+            let errors = timeline::find_events_by_kind("ErrorOccurred").await?;
+            assert!(
+                errors.iter().any(|e| matches!(&e.kind, EventKind::ErrorOccurred { context, .. } if context == "execution")),
+                "Erro de execução deveria ser registrado"
+            );
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,91 @@
+// === File: runtime/tests/mutations_tests.rs ===
+/*
+    Description: Testes dos comandos de alteração de contratos, ideias e entidades do runtime.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use parser::{parse, ast::Command};
+use runtime::{events::EventKind, mutations::apply, replay, view, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+/// Faz o parsing de uma alteração e a aplica.
+async fn mutate(input: &str) -> Result<EventKind> {
+    match parse(input)? {
+        Command::Imperative(imperative) => apply(&imperative.kind).await,
+        other => panic!("Comando deveria ser imperativo: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_amend_contract() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("mutation-contract-{}", Uuid::new_v4());
+            replay::commit(EventKind::ContractRegistered { id: id.clone(), clauses: vec!["Original".to_string()] }).await?;
+
+            let event = mutate(&format!("AMEND CONTRACT {} \"Nova\", \"Adicional\"", id)).await?;
+            assert_eq!(
+                event,
+                EventKind::ContractAmended { id: id.clone(), clauses: vec!["Nova".to_string(), "Adicional".to_string()] },
+                "Evento incorreto"
+            );
+
+            let (_, clauses, _) = view::get_contract(&id).await.expect("Contrato deveria existir");
+            assert_eq!(clauses, vec!["Nova".to_string(), "Adicional".to_string()], "Cláusulas não foram substituídas");
+
+            assert!(mutate("AMEND CONTRACT inexistente \"x\"").await.is_err(), "Contrato inexistente deveria falhar");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_tag_and_update_idea() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("mutation-idea-{}", Uuid::new_v4());
+            replay::commit(EventKind::IdeaRegistered { id: id.clone(), text: "Texto original".to_string() }).await?;
+
+            let event = mutate(&format!("TAG IDEA {} urgente, revisar", id)).await?;
+            assert_eq!(
+                event,
+                EventKind::IdeaTagged { id: id.clone(), tags: vec!["urgente".to_string(), "revisar".to_string()] },
+                "Evento incorreto"
+            );
+            assert_eq!(view::get_tags(&id).await.expect("Ideia deveria existir"), vec!["urgente", "revisar"], "Tags incorretas");
+
+            let event = mutate(&format!("UPDATE IDEA {} \"Texto revisado\"", id)).await?;
+            assert_eq!(event, EventKind::IdeaUpdated { id: id.clone(), text: "Texto revisado".to_string() }, "Evento incorreto");
+            let (_, text, _) = view::get_idea(&id).await.expect("Ideia deveria existir");
+            assert_eq!(text, "Texto revisado", "Texto não foi atualizado");
+
+            assert!(mutate("UPDATE IDEA inexistente \"x\"").await.is_err(), "Ideia inexistente deveria falhar");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_retire_entity() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("mutation-entity-{}", Uuid::new_v4());
+            replay::commit(EventKind::EntityRegistered { id: id.clone(), entity_type: "TEST".to_string() }).await?;
+
+            let event = mutate(&format!("RETIRE ENTITY {}", id)).await?;
+            assert_eq!(event, EventKind::EntityRetired { id: id.clone() }, "Evento incorreto");
+            assert!(view::fetch_entity(&id).await.is_err(), "Entidade deveria ter sido removida");
+
+            // Uma entidade só pode ser removida uma vez
+            assert!(mutate(&format!("RETIRE ENTITY {}", id)).await.is_err(), "Remoção repetida deveria falhar");
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,148 @@
+// === File: runtime/tests/orchestrate_tests.rs ===
+/*
+    Description: Testes do ORCHESTRATE nos modos sequential, parallel e dag.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::{process_command, timeline, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+/// Prefixo único para os IDs de um teste.
+fn unique(name: &str) -> String {
+    format!("orq-{}-{}", name, Uuid::new_v4().simple())
+}
+
+/// Eventos da orquestração cujos passos registraram ideias com o prefixo:
+/// início, ideias registradas (na ordem da timeline) e conclusão.
+async fn orchestration(prefix: &str) -> Result<(Event, Vec<String>, Event)> {
+    let events = timeline::list_events().await?;
+    let ideas: Vec<&Event> = events
+        .iter()
+        .filter(|e| matches!(&e.kind, EventKind::IdeaRegistered { id, .. } if id.starts_with(prefix)))
+        .collect();
+    let first = ideas.first().expect("Passos não executados");
+    let start = events.iter().find(|e| Some(e.id) == first.parent_id).cloned().expect("Início ausente");
+    let completed = events
+        .iter()
+        .find(|e| e.kind.name() == "OrchestrationCompleted" && e.parent_id == Some(start.id))
+        .cloned()
+        .expect("Conclusão ausente");
+    let ids = ideas
+        .iter()
+        .map(|e| match &e.kind {
+            EventKind::IdeaRegistered { id, .. } => id.trim_start_matches(prefix).to_string(),
+            _ => unreachable!(),
+        })
+        .collect();
+    Ok((start, ids, completed))
+}
+
+#[tokio::test]
+async fn test_orchestrate_sequential_and_parallel() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let prefix = unique("seq");
+            let result = process_command(&format!(
+                "ORCHESTRATE sequential {{\n    DEFINE IDEA {p}a \"A\"\n    DEFINE IDEA {p}b \"B\"\n    DEFINE IDEA {p}c \"C\"\n}}",
+                p = prefix
+            ))
+            .await?;
+            assert!(result.contains("Orquestração sequential concluída: 3 passos"), "Resumo ausente: {}", result);
+
+            let (start, ids, completed) = orchestration(&prefix).await?;
+            assert_eq!(ids, vec!["a", "b", "c"], "Passos deveriam seguir a ordem do bloco");
+            assert!(matches!(start.kind, EventKind::OrchestrationStarted { concurrency: 1, .. }), "Limite sequencial incorreto");
+            assert!(
+                matches!(completed.kind, EventKind::OrchestrationCompleted { concurrency: 1, .. }),
+                "Passos sequenciais não deveriam se sobrepor"
+            );
+
+            let prefix = unique("par");
+            process_command(&format!(
+                "ORCHESTRATE parallel 2 {{\n    DEFINE IDEA {p}a \"A\"\n    DEFINE IDEA {p}b \"B\"\n    DEFINE IDEA {p}c \"C\"\n}}",
+                p = prefix
+            ))
+            .await?;
+            let (start, mut ids, completed) = orchestration(&prefix).await?;
+            ids.sort();
+            assert_eq!(ids, vec!["a", "b", "c"], "Todos os passos deveriam executar");
+            assert!(matches!(start.kind, EventKind::OrchestrationStarted { concurrency: 2, .. }), "Limite informado ausente");
+            assert!(
+                matches!(completed.kind, EventKind::OrchestrationCompleted { concurrency: 2, .. }),
+                "Dois passos deveriam ter executado ao mesmo tempo"
+            );
+            assert_eq!(completed.trace_id, start.trace_id, "Orquestração deveria compartilhar o trace");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_orchestrate_dag_honours_dependencies() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let prefix = unique("dag");
+            process_command(&format!(
+                "ORCHESTRATE dag {{\n    STEP d DEPENDS ON b, c: DEFINE IDEA {p}d \"D\"\n    STEP b DEPENDS ON a: DEFINE IDEA {p}b \"B\"\n    STEP c DEPENDS ON a: DEFINE IDEA {p}c \"C\"\n    STEP a: DEFINE IDEA {p}a \"A\"\n}}",
+                p = prefix
+            ))
+            .await?;
+
+            let (start, ids, completed) = orchestration(&prefix).await?;
+            let position = |id: &str| ids.iter().position(|i| i == id).expect("Passo ausente");
+            assert_eq!(position("a"), 0, "Passo sem dependências deveria executar primeiro");
+            assert_eq!(position("d"), 3, "Passo dependente deveria esperar as dependências");
+            assert!(matches!(start.kind, EventKind::OrchestrationStarted { concurrency: 4, .. }), "Limite padrão incorreto");
+            assert!(
+                matches!(completed.kind, EventKind::OrchestrationCompleted { concurrency: 2, .. }),
+                "b e c deveriam executar ao mesmo tempo"
+            );
+
+            // Grafos inválidos falham antes de executar qualquer passo
+            let invalid = [
+                "ORCHESTRATE dag {\n    STEP a DEPENDS ON b: SIMULATE ENTITY e 1\n    STEP b DEPENDS ON a: SIMULATE ENTITY e 1\n}",
+                "ORCHESTRATE dag {\n    STEP a DEPENDS ON x: SIMULATE ENTITY e 1\n}",
+                "ORCHESTRATE dag {\n    STEP a: SIMULATE ENTITY e 1\n    STEP a: SIMULATE ENTITY e 1\n}",
+                "ORCHESTRATE parallel {\n    STEP a: SIMULATE ENTITY e 1\n    STEP b DEPENDS ON a: SIMULATE ENTITY e 1\n}",
+                "ORCHESTRATE aleatorio",
+                "ORCHESTRATE parallel 0",
+            ];
+            for input in invalid {
+                assert!(process_command(input).await.is_err(), "Orquestração inválida deveria falhar: {}", input);
+            }
+            let cycle = process_command(invalid[0]).await.unwrap_err().to_string();
+            assert!(cycle.contains("Ciclo"), "Erro de ciclo incorreto: {}", cycle);
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_orchestrate_stops_on_failure() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let prefix = unique("falha");
+            let error = process_command(&format!(
+                "ORCHESTRATE sequential {{\n    DEFINE IDEA {p}a \"A\"\n    STEP quebra: REVERT nao-e-uuid\n    DEFINE IDEA {p}b \"B\"\n}}",
+                p = prefix
+            ))
+            .await
+            .unwrap_err()
+            .to_string();
+            assert!(error.contains("Passo quebra falhou"), "Erro deveria indicar o passo: {}", error);
+
+            let state = timeline::current_state().await?;
+            assert!(state.ideas.contains_key(&format!("{}a", prefix)), "Passo anterior deveria ter executado");
+            assert!(!state.ideas.contains_key(&format!("{}b", prefix)), "Passos seguintes não deveriam executar");
+            Ok(())
+        })
+        .await
+}
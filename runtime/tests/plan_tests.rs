@@ -0,0 +1,124 @@
+// === File: runtime/tests/plan_tests.rs ===
+/*
+    Description: Testes do modo de plano: eventos previstos sobre uma cópia sob demanda do estado, sem persistir nada.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use ruleset::rules::Verdict;
+use runtime::events::{Event, EventKind};
+use runtime::{plan_command, process_command, timeline, view, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+fn unique(prefix: &str) -> String {
+    format!("{}-{}", prefix, Uuid::new_v4().simple())
+}
+
+fn names(events: &[Event]) -> Vec<&str> {
+    events.iter().map(|e| e.kind.name()).collect()
+}
+
+/// Se algum evento da timeline tem o ID planejado.
+async fn persisted(events: &[Event]) -> Result<bool> {
+    let timeline = timeline::list_events().await?;
+    Ok(timeline.iter().any(|e| events.iter().any(|planned| planned.id == e.id)))
+}
+
+#[tokio::test]
+async fn test_plan_reports_events_without_persisting() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let idea = unique("plano");
+            let contract = unique("plano-contrato");
+            let plan = plan_command(&format!(
+                "DEFINE IDEA {i} \"Texto\"\nTAG IDEA {i} urgente\nDEFINE CONTRACT {c} \"A\"\n\
+                 IF EXISTS IDEA {i} THEN AMEND CONTRACT {c} \"B\"\nLIST IDEAS WHERE TAG = urgente",
+                i = idea,
+                c = contract
+            ))
+            .await?;
+
+            assert_eq!(plan.error, None);
+            assert_eq!(
+                names(&plan.events),
+                vec!["IdeaRegistered", "IdeaTagged", "ContractRegistered", "ContractAmended", "ImperativeExecuted"],
+                "Cada passo deveria ver o efeito dos anteriores"
+            );
+            assert!(plan.output.as_deref().unwrap_or_default().contains(&idea), "Consulta deveria ver a ideia do plano");
+
+            // A cadeia de hashes do plano é a que a timeline teria
+            for pair in plan.events.windows(2) {
+                assert_eq!(pair[1].causal_hash.as_deref(), Some(pair[0].hash.as_str()));
+            }
+            timeline::verify_chain_from(&plan.events, plan.events[0].causal_hash.as_deref())?;
+
+            assert!(!persisted(&plan.events).await?, "Eventos do plano não deveriam entrar na timeline");
+            assert!(view::get_idea(&idea).await.is_err(), "Ideia não deveria ser criada");
+            assert!(view::get_contract(&contract).await.is_err(), "Contrato não deveria ser criado");
+            assert!(view::fetch_entity(&idea).await.is_err(), "Entidade não deveria ser registrada");
+            assert!(!view::planning(), "Plano deveria terminar com o comando");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_plan_copies_existing_items_on_write() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let idea = unique("existente");
+            process_command(&format!("DEFINE IDEA {i} \"Original\"\nTAG IDEA {i} antiga", i = idea)).await?;
+
+            let plan = plan_command(&format!(
+                "UPDATE IDEA {i} \"Alterada\"\nTAG IDEA {i} nova\nLIST IDEAS WHERE TAG = antiga\nRETIRE ENTITY {i}\n\
+                 IF NOT EXISTS ENTITY {i} THEN LET removida = \"sim\"",
+                i = idea
+            ))
+            .await?;
+            assert_eq!(plan.error, None);
+            let output = plan.output.unwrap_or_default();
+            assert!(output.contains("Alterada"), "Consulta deveria ver o texto do plano: {}", output);
+            assert!(output.contains("nova"), "Tags existentes e do plano: {}", output);
+            assert!(output.contains("Variável removida definida"), "Remoção deveria valer no plano: {}", output);
+            assert_eq!(
+                names(&plan.events),
+                vec!["IdeaUpdated", "IdeaTagged", "ImperativeExecuted", "IdeaDeleted", "EntityRetired"]
+            );
+
+            let (_, text, _) = view::get_idea(&idea).await?;
+            assert_eq!(text, "Original", "Ideia real não deveria mudar");
+            assert!(view::fetch_entity(&idea).await.is_ok(), "Entidade real não deveria ser removida");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_plan_reports_failures_and_verdicts() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let entity = unique("avaliada");
+            let plan = plan_command(&format!("INVOKE RULESET always-reject ON {e}\nUPDATE IDEA {e} \"X\"", e = entity)).await?;
+
+            assert!(plan.output.is_none());
+            assert!(plan.error.as_deref().unwrap_or_default().contains("Falha ao atualizar ideia"), "{:?}", plan.error);
+            assert!(
+                matches!(&plan.events[0].kind, EventKind::RuleVerdict { verdict: Verdict::Rejected, .. }),
+                "Veredicto deveria ser avaliado: {:?}",
+                plan.events[0].kind
+            );
+            assert!(matches!(&plan.events[1].kind, EventKind::ErrorOccurred { context, .. } if context == "execution"));
+            assert!(!persisted(&plan.events).await?, "Erro do plano não deveria entrar na timeline");
+
+            let error = plan_command("DEFINE IDEA").await.unwrap_err();
+            assert!(error.downcast_ref::<parser::Error>().is_some(), "Erro de sintaxe deveria ser devolvido");
+            Ok(())
+        })
+        .await
+}
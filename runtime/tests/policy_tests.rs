@@ -0,0 +1,168 @@
+// === File: runtime/tests/policy_tests.rs ===
+/*
+    Description: Testes da política de execução do WITH: tempo limite, novas tentativas e tratamento de falha.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::time::Duration;
+use runtime::events::{Event, EventKind};
+use runtime::policy::{ExecutionPolicy, MAX_BACKOFF};
+use runtime::{process_command, process_command_as, process_command_with_policy, timeline, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+/// Ator único, para separar os eventos de um teste.
+fn actor(name: &str) -> String {
+    format!("policy-{}-{}", name, Uuid::new_v4().simple())
+}
+
+/// Tentativas registradas em nome do ator, na ordem da timeline.
+async fn attempts(actor: &str) -> Result<Vec<Event>> {
+    Ok(timeline::find_events_by_kind("ExecutionAttempt")
+        .await?
+        .into_iter()
+        .filter(|e| e.actors.iter().any(|a| a == actor))
+        .collect())
+}
+
+fn status(event: &Event) -> &str {
+    match &event.kind {
+        EventKind::ExecutionAttempt { status, .. } => status,
+        other => panic!("Evento deveria ser uma tentativa: {:?}", other),
+    }
+}
+
+#[test]
+fn test_backoff_doubles_up_to_the_limit() {
+    let policy = ExecutionPolicy { retries: 40, backoff: Duration::from_millis(100), ..Default::default() };
+
+    assert_eq!(policy.max_attempts(), 41);
+    assert_eq!(policy.delay_before(1), Duration::ZERO, "Primeira tentativa não espera");
+    assert_eq!(policy.delay_before(2), Duration::from_millis(100));
+    assert_eq!(policy.delay_before(4), Duration::from_millis(400));
+    assert_eq!(policy.delay_before(41), MAX_BACKOFF, "Espera deveria ser limitada");
+}
+
+#[tokio::test]
+async fn test_retries_then_runs_on_failure() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let actor = actor("fallback");
+            let idea = format!("{}-recuperacao", actor);
+            let result = process_command_as(
+                &format!("WITH RETRY 2 BACKOFF 1ms DO REVERT nao-e-uuid ON FAILURE DEFINE IDEA {} \"Recuperado\"", idea),
+                vec![actor.clone()],
+            )
+            .await?;
+            assert!(result.contains("Ideia registrada"), "Resultado deveria ser o do tratamento de falha: {}", result);
+
+            let attempts = attempts(&actor).await?;
+            assert_eq!(attempts.len(), 3, "Cada tentativa deveria ser registrada");
+            for (i, event) in attempts.iter().enumerate() {
+                assert!(
+                    matches!(&event.kind, EventKind::ExecutionAttempt { attempt, max_attempts: 3, error: Some(_), .. } if *attempt == i + 1),
+                    "Tentativa incorreta: {:?}",
+                    event.kind
+                );
+                assert_eq!(status(event), "failed");
+            }
+
+            let events = timeline::list_events().await?;
+            let registered = events
+                .iter()
+                .find(|e| matches!(&e.kind, EventKind::IdeaRegistered { id, .. } if *id == idea))
+                .expect("Tratamento de falha não executado");
+            assert_eq!(registered.parent_id, Some(attempts[2].id), "Tratamento deveria ser filho da última tentativa");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_timeout_and_scope_of_attempts() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            // A espera da tentativa interna excede o tempo limite do bloco externo
+            let actor = actor("timeout");
+            let error = process_command_as(
+                "WITH TIMEOUT 50ms DO {\n    WITH RETRY 1 BACKOFF 5s DO REVERT nao-e-uuid\n}",
+                vec![actor.clone()],
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+            assert!(error.contains("Tempo limite de 50 ms excedido"), "Erro de tempo limite incorreto: {}", error);
+            let attempts = attempts(&actor).await?;
+            assert_eq!(attempts.iter().map(status).collect::<Vec<_>>(), vec!["failed", "timed_out"]);
+
+            // Variáveis definidas numa tentativa bem-sucedida continuam visíveis
+            let result = process_command_as("WITH RETRY 1 DO LET alvo = \"x\"\nLET copia = \"${alvo}\"", vec![actor]).await?;
+            assert!(result.contains("Variável copia definida"), "Variável da tentativa deveria continuar no escopo: {}", result);
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_process_command_with_policy() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let policy = ExecutionPolicy { retries: 1, ..Default::default() };
+            let error = process_command_with_policy("REVERT nao-e-uuid", &policy).await.unwrap_err().to_string();
+            assert!(error.contains("Identificador de evento inválido"), "Erro da última tentativa deveria ser devolvido: {}", error);
+
+            let errors = timeline::find_events_by_kind("ErrorOccurred").await?;
+            assert!(
+                errors.iter().any(|e| matches!(&e.kind, EventKind::ErrorOccurred { message, .. } if message.contains("nao-e-uuid"))),
+                "Falha final deveria ser registrada"
+            );
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_timeout_never_leaves_registry_ahead_of_timeline() -> Result<()> {
+    let instance = Runtime::in_memory();
+    let prefix = actor("cancelado");
+
+    // Registros concorrentes fazem as tentativas esperar pelos locks e ser
+    // canceladas em pontos diferentes do registro
+    let background = {
+        let instance = instance.clone();
+        let prefix = prefix.clone();
+        tokio::spawn(async move {
+            instance
+                .scope(async {
+                    for i in 0..50 {
+                        process_command(&format!("DEFINE IDEA {}-fundo-{} \"Fundo\"", prefix, i)).await?;
+                    }
+                    Ok::<_, anyhow::Error>(())
+                })
+                .await
+        })
+    };
+    instance
+        .scope(async {
+            for i in 0..50 {
+                let _ = process_command(&format!("WITH TIMEOUT 0ms DO DEFINE IDEA {}-{} \"Limite\"", prefix, i)).await;
+            }
+        })
+        .await;
+    background.await??;
+
+    let state = instance.scope(timeline::current_state()).await?;
+    let mut expected: Vec<_> = state.entities.values().map(|entity| entity.to_entity()).collect();
+    let mut registered = instance.registry().list_entities(|_| true).await?;
+    expected.sort_by(|a, b| a.id.cmp(&b.id));
+    registered.sort_by(|a, b| a.id.cmp(&b.id));
+    assert!(expected.len() >= 50, "Registros sem tempo limite deveriam estar no estado");
+    assert_eq!(registered, expected, "Registro deveria ser igual ao estado da timeline");
+    Ok(())
+}
@@ -0,0 +1,124 @@
+// === File: runtime/tests/query_tests.rs ===
+/*
+    Description: Testes das consultas SHOW, LIST e FIND do runtime do LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use parser::{parse, ast::Command};
+use runtime::events::EventKind;
+use runtime::query::run_query;
+use runtime::{replay, Registry, Runtime};
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Faz o parsing de uma consulta e a executa.
+async fn query(input: &str) -> Result<Value> {
+    match parse(input)? {
+        Command::Imperative(imperative) => run_query(&imperative.kind).await,
+        other => panic!("Comando deveria ser imperativo: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_show_contract() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("query-contract-{}", Uuid::new_v4());
+            let clauses = vec!["Cláusula A".to_string(), "Cláusula B".to_string()];
+            replay::commit(EventKind::ContractRegistered { id: id.clone(), clauses }).await?;
+
+            let result = query(&format!("SHOW CONTRACT {}", id)).await?;
+            assert_eq!(result["id"], json!(id), "ID do contrato incorreto");
+            assert_eq!(result["clauses"], json!(["Cláusula A", "Cláusula B"]), "Cláusulas incorretas");
+            assert!(result["created_at"].is_string(), "Data de criação ausente");
+
+            assert!(query("SHOW CONTRACT inexistente").await.is_err(), "Contrato inexistente deveria falhar");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_list_ideas_where_tag() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let tag = format!("tag-{}", Uuid::new_v4().simple());
+            for (id, text) in [("query-idea-b", "Segunda"), ("query-idea-a", "Primeira"), ("query-idea-c", "Sem tag")] {
+                replay::commit(EventKind::IdeaRegistered { id: id.to_string(), text: text.to_string() }).await?;
+            }
+            replay::commit(EventKind::IdeaTagged { id: "query-idea-a".to_string(), tags: vec![tag.clone()] }).await?;
+            replay::commit(EventKind::IdeaTagged { id: "query-idea-b".to_string(), tags: vec![tag.clone(), "outra".to_string()] }).await?;
+
+            let result = query(&format!("LIST IDEAS WHERE TAG = {}", tag)).await?;
+            let ids: Vec<_> = result.as_array().expect("Resultado deveria ser uma lista").iter().map(|i| i["id"].clone()).collect();
+            assert_eq!(ids, vec![json!("query-idea-a"), json!("query-idea-b")], "Filtro por tag incorreto");
+            assert_eq!(result[1]["tags"], json!([tag, "outra"]), "Tags incorretas");
+
+            let all = query("LIST IDEAS").await?;
+            assert!(all.as_array().expect("Resultado deveria ser uma lista").len() >= 3, "Todas as ideias deveriam ser listadas");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_find_spans() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let kind = format!("query_kind_{}", Uuid::new_v4().simple());
+            for payload in ["um", "dois", "três"] {
+                datatrack::record_span(datatrack::Span {
+                    id: Uuid::new_v4(),
+                    parent: None,
+                    kind: kind.clone(),
+                    payload: payload.to_string(),
+                    timestamp: Utc::now(),
+                })
+                .await?;
+            }
+
+            let result = query(&format!("FIND SPANS KIND = {} LIMIT 2", kind)).await?;
+            let spans = result.as_array().expect("Resultado deveria ser uma lista");
+            assert_eq!(spans.len(), 2, "LIMIT deveria restringir os spans");
+            assert_eq!(spans[0]["payload"], json!("um"), "Payload incorreto");
+            assert_eq!(spans[0]["kind"], json!(kind), "Tipo incorreto");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_show_simulation_last() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let id = format!("query-entity-{}", Uuid::new_v4());
+            let registry = Registry::in_memory();
+            registry.register_entity(&id, "TEST").await?;
+            simulate::run_simulation(&registry, &id, 4).await?;
+
+            let result = query(&format!("SHOW SIMULATION {} LAST 2", id)).await?;
+            let rounds: Vec<_> = result.as_array().expect("Resultado deveria ser uma lista").iter().map(|r| r["round"].clone()).collect();
+            assert_eq!(rounds.len(), 2, "LAST deveria restringir os resultados");
+            assert_eq!(rounds[1], json!(4), "O último resultado deveria ser da última rodada");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_non_query_is_rejected() {
+    Runtime::in_memory()
+        .scope(async {
+            let result = query("ORCHESTRATE sequential").await;
+            assert!(result.is_err(), "Comandos que não são consultas devem ser rejeitados");
+        })
+        .await
+}
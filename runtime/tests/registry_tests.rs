@@ -0,0 +1,104 @@
+// === File: runtime/tests/registry_tests.rs ===
+/*
+    Description: Testes da escolha do registro de entidades usado pelo runtime.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::entities::{create_entity, update_entity};
+use runtime::{init_with_timeline, process_command, view, Registry, Runtime};
+use registry::{Entity, EntityType, EntityUpdate};
+use anyhow::Result;
+use serde_json::json;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Diretório temporário, único por teste.
+fn temp_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("logline-{}", Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_runtime_uses_persisted_registry() -> Result<()> {
+    let dir = temp_dir();
+    let path = dir.join("registry.redb");
+    let instance = Runtime::new(Registry::open(&path)?);
+    let prefix = format!("registro-{}-", Uuid::new_v4().simple());
+    instance
+        .scope(async {
+            init_with_timeline(dir.join("main.timeline")).await?;
+            // Os passos executam em outras tarefas, sobre a mesma instância
+            let script = format!(
+                "ORCHESTRATE parallel 2 {{\n    DEFINE IDEA {p}a \"A\"\n    DEFINE IDEA {p}b \"B\"\n}}",
+                p = prefix
+            );
+            process_command(&script).await
+        })
+        .await?;
+    for id in [format!("{}a", prefix), format!("{}b", prefix)] {
+        assert_eq!(instance.registry().get_entity(&id).await?.entity_type, EntityType::Idea);
+    }
+
+    // Outras instâncias não compartilham o registro nem a timeline
+    let idea = format!("{}a", prefix);
+    let other = Runtime::in_memory();
+    assert!(other.registry().get_entity(&idea).await.is_err(), "Registro de outra instância deveria estar vazio");
+    assert!(other.scope(view::fetch_entity(&idea)).await.is_err(), "Estado de outra instância deveria estar vazio");
+    assert!(std::panic::catch_unwind(Runtime::current).is_err(), "Fora de um escopo não deveria haver instância");
+
+    drop(instance);
+    let persisted = Registry::open(&path)?;
+    assert_eq!(persisted.get_entity(&idea).await?.entity_type, EntityType::Idea, "Entidade deveria estar no disco");
+    drop(persisted);
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_persisted_registry_survives_restart() -> Result<()> {
+    let dir = temp_dir();
+    let (registry_path, timeline_path) = (dir.join("registry.redb"), dir.join("main.timeline"));
+    let id = format!("reinicio-{}", Uuid::new_v4().simple());
+
+    let first = Runtime::new(Registry::open(&registry_path)?);
+    let updated = first
+        .scope(async {
+            init_with_timeline(&timeline_path).await?;
+            create_entity(Entity::new(&id, EntityType::Agent).with_owner("tenant-a")).await?;
+            let update = EntityUpdate { metadata: Some(json!({ "modelo": "v2" })), ..EntityUpdate::default() };
+            update_entity(&id, 1, update).await
+        })
+        .await?;
+    assert_eq!(updated.version, 2);
+    drop(first);
+
+    // Outro processo, sobre os mesmos arquivos
+    let second = Runtime::new(Registry::open(&registry_path)?);
+    second
+        .scope(async {
+            init_with_timeline(&timeline_path).await?;
+            assert_eq!(second.registry().get_entity(&id).await?, updated, "Entidade deveria ser mantida");
+            process_command(&format!("DEFINE IDEA {}-ideia \"Depois\"", id)).await?;
+            let renamed = EntityUpdate { owner: Some("tenant-b".to_string()), ..EntityUpdate::default() };
+            assert_eq!(update_entity(&id, 2, renamed).await?.version, 3, "Versão deveria continuar da anterior");
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+    let entities = second.registry().list_entities(|entity| entity.id.starts_with(&id)).await?;
+    assert_eq!(entities.len(), 2, "Entidades de antes e depois do reinício deveriam estar no registro");
+    drop(second);
+
+    // Sem a timeline em arquivo, o registro persistido não é aceito
+    let volatile = Runtime::new(Registry::open(&registry_path)?);
+    let error = volatile.scope(runtime::init()).await.unwrap_err().to_string();
+    assert!(error.contains("exige a timeline em arquivo"), "Erro incorreto: {}", error);
+    assert_eq!(volatile.registry().get_entity(&id).await?.version, 3, "Registro não deveria ser alterado");
+    drop(volatile);
+    std::fs::remove_dir_all(dir)?;
+    Ok(())
+}
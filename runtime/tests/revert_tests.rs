@@ -0,0 +1,110 @@
+// === File: runtime/tests/revert_tests.rs ===
+/*
+    Description: Testes da reversão de eventos por compensação e da consulta do estado em um instante passado.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::revert::compensations;
+use runtime::{process_command, timeline, view, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+#[test]
+fn test_compensations_restore_previous_state() -> Result<()> {
+    let events: Vec<Event> = vec![
+        EventKind::ContractRegistered { id: "c-1".to_string(), clauses: vec!["A".to_string()] },
+        EventKind::IdeaRegistered { id: "i-1".to_string(), text: "Texto".to_string() },
+        EventKind::IdeaTagged { id: "i-1".to_string(), tags: vec!["x".to_string()] },
+        EventKind::IdeaTagged { id: "i-1".to_string(), tags: vec!["x".to_string(), "y".to_string()] },
+        EventKind::ContractAmended { id: "c-1".to_string(), clauses: vec!["B".to_string()] },
+        EventKind::IdeaDeleted { id: "i-1".to_string() },
+        EventKind::RuleVerdict { rule: "r".to_string(), verdict: ruleset::rules::Verdict::Accepted },
+    ]
+    .into_iter()
+    .map(Event::new)
+    .collect();
+
+    assert_eq!(
+        compensations(&events, events[4].id)?,
+        vec![EventKind::ContractAmended { id: "c-1".to_string(), clauses: vec!["A".to_string()] }],
+        "Emenda deveria voltar às cláusulas anteriores"
+    );
+    assert_eq!(
+        compensations(&events, events[0].id)?,
+        vec![EventKind::ContractDeleted { id: "c-1".to_string() }, EventKind::EntityRetired { id: "c-1".to_string() }],
+        "Registro deveria ser desfeito com remoção"
+    );
+    assert_eq!(
+        compensations(&events, events[5].id)?,
+        vec![
+            EventKind::IdeaRegistered { id: "i-1".to_string(), text: "Texto".to_string() },
+            EventKind::IdeaTagged { id: "i-1".to_string(), tags: vec!["x".to_string(), "y".to_string()] },
+        ],
+        "Ideia removida deveria ser recriada com suas tags"
+    );
+
+    // A ideia não existe mais: suas tags não podem ser revertidas
+    assert!(compensations(&events, events[3].id).is_err(), "Ideia removida não deveria aceitar compensação");
+    assert!(compensations(&events, events[6].id).is_err(), "Veredicto não altera o estado");
+    assert!(compensations(&events, Uuid::new_v4()).is_err(), "Evento inexistente deveria falhar");
+
+    // Apenas a tag realmente adicionada é removida
+    assert_eq!(
+        compensations(&events[..5], events[3].id)?,
+        vec![EventKind::IdeaUntagged { id: "i-1".to_string(), tags: vec!["y".to_string()] }],
+        "Compensação deveria remover só as tags novas"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revert_appends_compensation_and_keeps_history() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let contract = format!("revert-contract-{}", Uuid::new_v4().simple());
+            process_command(&format!("DEFINE CONTRACT {} \"Original\"", contract)).await?;
+            process_command(&format!("AMEND CONTRACT {} \"Emendada\"", contract)).await?;
+
+            let amended = timeline::find_events_by_kind("ContractAmended")
+                .await?
+                .into_iter()
+                .find(|e| e.kind == EventKind::ContractAmended { id: contract.clone(), clauses: vec!["Emendada".to_string()] })
+                .expect("Evento de emenda ausente");
+            let before_revert = timeline::list_events().await?.last().expect("Timeline vazia").timestamp;
+
+            let output = process_command(&format!("REVERT {}", amended.id)).await?;
+            assert!(output.contains("revertido"), "Saída inesperada: {}", output);
+
+            let (_, clauses, _) = view::get_contract(&contract).await.expect("Contrato ausente");
+            assert_eq!(clauses, vec!["Original".to_string()], "Cláusulas deveriam voltar ao original");
+            assert_eq!(timeline::current_state().await?.contracts[&contract].clauses, clauses, "Estado e armazenamento deveriam coincidir");
+
+            // O histórico é preservado: o estado antes da reversão ainda é consultável
+            let past = timeline::state_at(before_revert).await?;
+            assert_eq!(past.contracts[&contract].clauses, vec!["Emendada".to_string()], "Estado passado incorreto");
+            assert!(timeline::list_events().await?.iter().any(|e| e.id == amended.id), "Evento revertido não deveria ser apagado");
+
+            let marker = timeline::find_events_by_kind("EventReverted")
+                .await?
+                .into_iter()
+                .find(|e| matches!(&e.kind, EventKind::EventReverted { event_id, .. } if *event_id == amended.id))
+                .expect("Marcador de reversão ausente");
+            let EventKind::EventReverted { event_hash, compensations, .. } = &marker.kind else { unreachable!() };
+            assert_eq!(event_hash, &amended.hash, "Reversão deveria apontar para o hash do evento original");
+            assert_eq!(compensations.len(), 1, "Deveria haver um evento de compensação");
+
+            assert!(process_command(&format!("REVERT {}", amended.id)).await.is_err(), "Reverter duas vezes deveria falhar");
+            assert!(process_command(&format!("REVERT {}", marker.id)).await.is_err(), "Reversão não deveria ser revertida");
+            assert!(process_command("REVERT nao-e-um-uuid").await.is_err(), "ID inválido deveria falhar");
+            assert_eq!(timeline::verify().await, Ok(()), "Cadeia deveria continuar válida");
+            Ok(())
+        })
+        .await
+}
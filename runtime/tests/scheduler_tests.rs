@@ -0,0 +1,230 @@
+// === File: runtime/tests/scheduler_tests.rs ===
+/*
+    Description: Testes das expressões cron e do scheduler de gatilhos: disparos, recuperação de ocorrências perdidas e gatilhos de evento.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use runtime::cron::Cron;
+use runtime::events::EventKind;
+use runtime::scheduler::{Scheduler, Trigger};
+use runtime::{init_with_timeline, process_command, shutdown, timeline, Runtime};
+use anyhow::Result;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+const SCRIPT: &str = r#"
+contract Manutencao {
+  span CronExecution {
+    id: "manutencao"
+    actors: ["cron_service"]
+    children: ["HealthCheck", "MetricsCollection"]
+  }
+  span HealthCheck {
+    id: "health"
+    timeouts: {
+      execution: 5
+    }
+  }
+  span MetricsCollection {
+    id: "metrics"
+  }
+}
+"#;
+
+/// Pacote temporário com o script de manutenção.
+fn pack() -> Result<PathBuf> {
+    let pack = std::env::temp_dir().join(format!("lllpack-{}", Uuid::new_v4()));
+    fs::create_dir_all(pack.join("triggers"))?;
+    fs::write(pack.join("triggers/manutencao.lll"), SCRIPT)?;
+    Ok(pack)
+}
+
+fn trigger(name: &str, cron: Option<&str>, event: Option<&str>) -> Trigger {
+    Trigger {
+        name: format!("{}-{}", name, Uuid::new_v4().simple()),
+        cron: cron.map(str::to_string),
+        event: event.map(str::to_string),
+        file: "triggers/manutencao.lll".to_string(),
+    }
+}
+
+fn at(text: &str) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap())
+}
+
+#[test]
+fn test_cron_next_after() -> Result<()> {
+    // 2026-10-16 é uma sexta-feira
+    let friday = at("2026-10-16 12:07:30");
+    let next = |expression: &str| expression.parse::<Cron>().map(|cron| cron.next_after(friday));
+
+    assert_eq!(next("0 4 * * *")?, Some(at("2026-10-17 04:00:00")));
+    assert_eq!(next("*/15 * * * *")?, Some(at("2026-10-16 12:15:00")));
+    assert_eq!(next("5/20 12 * * *")?, Some(at("2026-10-16 12:25:00")));
+    assert_eq!(next("0 0 * * 1-5")?, Some(at("2026-10-19 00:00:00")));
+    assert_eq!(next("0 0 * * 7")?, Some(at("2026-10-18 00:00:00")), "7 também é domingo");
+    assert_eq!(next("0 0 17 * 1")?, Some(at("2026-10-17 00:00:00")), "Dia do mês ou da semana");
+    assert_eq!(next("30 9 1,15 1 *")?, Some(at("2027-01-01 09:30:00")));
+    assert_eq!(next("@hourly")?, Some(at("2026-10-16 13:00:00")));
+    assert_eq!(next("0 0 29 2 *")?, Some(at("2028-02-29 00:00:00")));
+    assert_eq!(next("0 0 31 2 *")?, None, "Agenda que nunca ocorre");
+
+    for invalid in ["61 * * * *", "* * *", "*/0 * * * *", "0 0 20-10 * *", "0 0 * * seg"] {
+        assert!(invalid.parse::<Cron>().is_err(), "Expressão {} deveria ser rejeitada", invalid);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cron_trigger_catches_up_after_downtime() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let pack = pack()?;
+            let trigger = trigger("manutencao", Some("*/10 * * * *"), None);
+            let name = trigger.name.clone();
+            let now = Utc::now();
+            let scheduler = Scheduler::new(&pack, vec![trigger.clone()])?;
+            assert!(scheduler.tick(now).await?.is_empty(), "Nada deveria estar devido");
+
+            let first = "*/10 * * * *".parse::<Cron>()?.next_after(now).unwrap();
+            let firings = scheduler.tick(first).await?;
+            assert_eq!(firings.len(), 1);
+            assert_eq!(firings[0].scheduled_for, first);
+            assert_eq!(firings[0].status, "completed", "Disparo deveria concluir: {:?}", firings[0].error);
+            assert_eq!(firings[0].tasks["HealthCheck"], "completed");
+            assert_eq!(firings[0].tasks["MetricsCollection"], "skipped", "Tarefa sem ação nativa");
+            assert_eq!(firings[0].next_scheduled, Some(first + Duration::minutes(10)));
+            assert!(scheduler.tick(first).await?.is_empty(), "Ocorrência não deveria disparar de novo");
+
+            // Outro scheduler, depois de perder três ocorrências, dispara uma vez
+            let restarted = Scheduler::new(&pack, vec![trigger])?;
+            let firings = restarted.tick(first + Duration::minutes(35)).await?;
+            assert_eq!(firings.len(), 1);
+            assert_eq!(firings[0].scheduled_for, first + Duration::minutes(30));
+            assert_eq!(firings[0].missed, 2);
+
+            let state = timeline::current_state().await?;
+            let fired = &state.triggers[&name];
+            assert_eq!(fired.last_fired, Some(first + Duration::minutes(30)));
+            assert_eq!(fired.runs, 2);
+            assert_eq!(fired.last_status.as_deref(), Some("completed"));
+
+            let executions: Vec<_> = timeline::find_events_by_kind("CronExecution")
+                .await?
+                .into_iter()
+                .filter(|e| matches!(&e.kind, EventKind::CronExecution { trigger, .. } if *trigger == name))
+                .collect();
+            assert_eq!(executions.len(), 2);
+            assert_eq!(executions[0].actors, vec!["cron_service".to_string()], "Atores do span CronExecution");
+            let completions = timeline::find_events_by_kind("CronCompletion").await?;
+            let completion = completions
+                .iter()
+                .find(|e| e.parent_id == Some(executions[1].id))
+                .expect("CronCompletion deveria ser filho do CronExecution");
+            assert_eq!(completion.trace_id, executions[1].trace_id);
+            fs::remove_dir_all(&pack)?;
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_cron_trigger_catches_up_before_first_firing() -> Result<()> {
+    let pack = pack()?;
+    let path = pack.join("main.timeline");
+    let trigger = trigger("inicio", Some("*/10 * * * *"), None);
+    let name = trigger.name.clone();
+    let armed = Utc::now() - Duration::minutes(35);
+
+    // Um scheduler passou a verificar o gatilho e o processo parou antes
+    // do primeiro disparo
+    Runtime::in_memory()
+        .scope(async {
+            init_with_timeline(&path).await?;
+            timeline::append(EventKind::TriggerArmed { trigger: name.clone(), since: armed }).await?;
+            shutdown().await
+        })
+        .await?;
+
+    Runtime::in_memory()
+        .scope(async {
+            init_with_timeline(&path).await?;
+            assert_eq!(timeline::current_state().await?.triggers[&name].since(), Some(armed));
+
+            let now = Utc::now();
+            let due: Vec<_> = "*/10 * * * *".parse::<Cron>()?.occurrences(armed).take_while(|at| *at <= now).collect();
+            let scheduler = Scheduler::new(&pack, vec![trigger])?;
+            let firings = scheduler.tick(now).await?;
+            assert_eq!(firings.len(), 1, "Ocorrências perdidas antes do primeiro disparo deveriam ser cobertas");
+            assert_eq!(Some(&firings[0].scheduled_for), due.last());
+            assert_eq!(firings[0].missed, due.len() - 1);
+
+            let fired = &timeline::current_state().await?.triggers[&name];
+            assert_eq!((fired.since(), fired.runs), (due.last().copied(), 1));
+            assert_eq!(timeline::find_events_by_kind("TriggerArmed").await?.len(), 1, "Gatilho não deveria ser registrado de novo");
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+    fs::remove_dir_all(&pack)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_event_trigger_fires_once_per_event() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let pack = pack()?;
+            let trigger = trigger("ideias", None, Some("idea_registered"));
+            let scheduler = Scheduler::new(&pack, vec![trigger])?;
+
+            process_command(&format!("DEFINE IDEA agendada-{} \"Gatilho\"", Uuid::new_v4().simple())).await?;
+            let registered = timeline::find_events_by_kind("IdeaRegistered").await?.pop().unwrap();
+
+            let firings = scheduler.tick(Utc::now()).await?;
+            assert_eq!(firings.len(), 1);
+            assert_eq!(firings[0].cause, Some(registered.id));
+            assert_eq!(firings[0].scheduled_for, registered.timestamp);
+            assert_eq!(firings[0].next_scheduled, None);
+            assert!(scheduler.tick(Utc::now()).await?.is_empty(), "Evento não deveria disparar de novo");
+            fs::remove_dir_all(&pack)?;
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_invalid_triggers_and_scripts() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let pack = pack()?;
+            assert!(Scheduler::new(&pack, vec![trigger("x", Some("0 4 * *"), None)]).is_err(), "Cron inválido");
+            assert!(Scheduler::new(&pack, vec![trigger("x", None, None)]).is_err(), "Sem cron nem evento");
+            assert!(Scheduler::new(&pack, vec![trigger("x", Some("@daily"), Some("idea_registered"))]).is_err());
+            let duplicate = trigger("x", Some("@daily"), None);
+            assert!(Scheduler::new(&pack, vec![duplicate.clone(), duplicate]).is_err(), "Gatilho duplicado");
+
+            // Script ausente: o disparo é registrado como falho
+            let missing = Trigger { file: "triggers/ausente.lll".to_string(), ..trigger("ausente", Some("@hourly"), None) };
+            let now = Utc::now();
+            let scheduler = Scheduler::new(&pack, vec![missing])?;
+            let firings = scheduler.tick(now + Duration::hours(1)).await?;
+            assert_eq!(firings.len(), 1);
+            assert_eq!(firings[0].status, "failed");
+            assert!(firings[0].error.as_deref().unwrap_or_default().contains("ausente.lll"), "Erro: {:?}", firings[0].error);
+
+            // Gatilhos do lllpack do repositório
+            let lllpack = Path::new(env!("CARGO_MANIFEST_DIR")).join("../lllpack");
+            Scheduler::load(lllpack)?;
+            fs::remove_dir_all(&pack)?;
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,127 @@
+// === File: runtime/tests/snapshot_tests.rs ===
+/*
+    Description: Testes dos snapshots do estado e da compactação do arquivo da timeline.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::snapshot::{tail_after, Snapshot};
+use runtime::state::State;
+use runtime::store::TimelineFile;
+use runtime::{process_command, timeline, Runtime};
+use anyhow::Result;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Caminho de um arquivo de timeline temporário, único por teste.
+fn temp_timeline() -> PathBuf {
+    std::env::temp_dir().join(format!("logline-{}", Uuid::new_v4())).join("main.timeline")
+}
+
+/// Eventos encadeados pelo hash.
+fn chain(kinds: Vec<EventKind>) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    for kind in kinds {
+        let previous = events.last().map(|e| e.hash.clone());
+        events.push(Event::chained(kind, previous));
+    }
+    events
+}
+
+#[test]
+fn test_tail_after_skips_events_already_covered() -> Result<()> {
+    let events = chain(vec![
+        EventKind::EntityRegistered { id: "a".to_string(), entity_type: "ENTITY".to_string() },
+        EventKind::EntityRegistered { id: "b".to_string(), entity_type: "ENTITY".to_string() },
+        EventKind::EntityRetired { id: "a".to_string() },
+    ]);
+    let snapshot = Snapshot::new(&events[1], 2, State::fold(&events[..2]));
+
+    // Queda entre a gravação do snapshot e a compactação do arquivo
+    assert_eq!(tail_after(&snapshot, events.clone())?, events[2..].to_vec(), "Eventos cobertos deveriam ser descartados");
+    // Arquivo já compactado
+    assert_eq!(tail_after(&snapshot, events[2..].to_vec())?, events[2..].to_vec(), "Cauda deveria ser mantida");
+    // Arquivo que não continua o snapshot
+    let unrelated = chain(vec![EventKind::EntityRetired { id: "x".to_string() }]);
+    assert!(tail_after(&snapshot, unrelated).is_err(), "Cadeia sem elo com o snapshot deveria falhar");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compaction_preserves_state_and_chain() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let path = temp_timeline();
+            let suffix = Uuid::new_v4().simple().to_string();
+            let contract = format!("snap-contract-{}", suffix);
+            let idea = format!("snap-idea-{}", suffix);
+
+            timeline::attach(&path, 1, 0).await?;
+            process_command(&format!(
+                "DEFINE CONTRACT {c} \"A\"\nAMEND CONTRACT {c} \"B\"\nDEFINE IDEA {i} \"Texto\"\nTAG IDEA {i} urgente",
+                c = contract,
+                i = idea
+            ))
+            .await?;
+            let (_, before) = timeline::history().await?;
+            let state = timeline::current_state().await?;
+
+            let snapshot = timeline::compact().await?.expect("Deveria haver eventos a compactar");
+            assert_eq!(snapshot.last_hash, before.last().unwrap().hash, "Snapshot deveria apontar para o último evento");
+            assert_eq!(snapshot.state, state, "Snapshot deveria guardar o estado atual");
+            assert_eq!(timeline::current_state().await?, state, "Compactação não deveria alterar o estado");
+            assert_eq!(timeline::verify().await, Ok(()), "Cadeia deveria continuar verificável");
+
+            // O arquivo recomeça com o registro da compactação
+            let (_, tail) = TimelineFile::open(&path, 1)?;
+            assert_eq!(tail.len(), 1, "Eventos cobertos deveriam sair do arquivo");
+            assert_eq!(tail[0].causal_hash.as_deref(), Some(snapshot.last_hash.as_str()), "Elo com o snapshot ausente");
+            assert!(
+                matches!(&tail[0].kind, EventKind::TimelineCompacted { events_collapsed, .. } if *events_collapsed == before.len()),
+                "Evento de compactação incorreto"
+            );
+
+            // A reconstrução parte do snapshot e reaplica só a cauda
+            process_command(&format!("UPDATE IDEA {} \"Revisado\"", idea)).await?;
+            let expected = timeline::current_state().await?;
+            let (loaded, events) = timeline::attach(&path, 1, 0).await?;
+            assert_eq!(loaded.as_ref(), Some(&snapshot), "Snapshot deveria ser lido do disco");
+            assert_eq!(events.len(), 2, "Apenas a cauda deveria ser lida");
+            assert_eq!(State::fold_from(snapshot.state.clone(), &events), expected, "Estado reconstruído incorreto");
+            assert!(timeline::state_at(before[0].timestamp).await.is_err(), "Estado compactado não deveria estar disponível");
+
+            // Snapshot adulterado é rejeitado
+            let snapshot_path = Snapshot::path_for(&path);
+            let tampered = fs::read_to_string(&snapshot_path)?.replace("\"B\"", "\"Z\"");
+            fs::write(&snapshot_path, tampered)?;
+            assert!(timeline::attach(&path, 1, 0).await.is_err(), "Snapshot alterado deveria falhar");
+            fs::remove_dir_all(path.parent().unwrap())?;
+
+            // Snapshots periódicos (no mesmo teste: a timeline é global)
+            let path = temp_timeline();
+            timeline::attach(&path, 1, 4).await?;
+            let ids: Vec<String> = (0..5).map(|n| format!("snap-periodic-{}-{}", n, Uuid::new_v4().simple())).collect();
+            for id in &ids {
+                process_command(&format!("DEFINE IDEA {} \"Texto\"", id)).await?;
+            }
+
+            let (snapshot, events) = timeline::history().await?;
+            let snapshot = snapshot.expect("Snapshot deveria ter sido gravado");
+            assert!(Snapshot::path_for(&path).exists(), "Arquivo do snapshot ausente");
+            let state = timeline::current_state().await?;
+            assert!(ids.iter().all(|id| state.ideas.contains_key(id)), "Ideias deveriam sobreviver à compactação");
+            assert_eq!(snapshot.events_covered, 4, "Snapshot deveria cobrir os quatro primeiros eventos");
+            assert_eq!(events.len(), 2, "Compactação e quinta ideia deveriam estar na cauda");
+
+            fs::remove_dir_all(path.parent().unwrap())?;
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,79 @@
+// === File: runtime/tests/state_tests.rs ===
+/*
+    Description: Testes do estado derivado da timeline e da sua projeção sobre registro, contratos e ideias.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::state::State;
+use runtime::{process_command, timeline, view, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+#[test]
+fn test_fold_rebuilds_state_at_any_point() {
+    let events: Vec<Event> = vec![
+        EventKind::ContractRegistered { id: "c-1".to_string(), clauses: vec!["A".to_string()] },
+        EventKind::IdeaRegistered { id: "i-1".to_string(), text: "Texto".to_string() },
+        EventKind::IdeaTagged { id: "i-1".to_string(), tags: vec!["x".to_string(), "x".to_string()] },
+        EventKind::ContractAmended { id: "c-1".to_string(), clauses: vec!["B".to_string()] },
+        EventKind::EntityRegistered { id: "e-1".to_string(), entity_type: "ENTITY".to_string() },
+        EventKind::ContractDeleted { id: "c-1".to_string() },
+        EventKind::EntityRetired { id: "c-1".to_string() },
+    ]
+    .into_iter()
+    .map(Event::new)
+    .collect();
+
+    let before_amend = State::fold(&events[..3]);
+    assert_eq!(before_amend.contracts["c-1"].clauses, vec!["A".to_string()], "Cláusulas antes da emenda");
+    assert_eq!(before_amend.ideas["i-1"].tags, vec!["x".to_string()], "Tags não deveriam se repetir");
+    assert_eq!(before_amend.entities["i-1"].entity_type, "IDEA", "Ideia deveria estar no registro");
+
+    let after_amend = State::fold(&events[..4]);
+    assert_eq!(after_amend.contracts["c-1"].clauses, vec!["B".to_string()], "Cláusulas após a emenda");
+    assert_eq!(after_amend.contracts["c-1"].updated_at, events[3].timestamp, "Data da emenda vem do evento");
+
+    let end = State::fold(&events);
+    assert!(!end.contracts.contains_key("c-1"), "Contrato deveria ter sido removido");
+    assert!(!end.entities.contains_key("c-1"), "Entidade deveria ter sido removida");
+    assert_eq!(end.entities["e-1"].entity_type, "ENTITY", "Entidade genérica ausente");
+    assert_eq!(State::fold(&events), end, "Dobra deveria ser determinística");
+}
+
+#[tokio::test]
+async fn test_stores_are_projections_of_the_timeline() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let suffix = Uuid::new_v4().simple().to_string();
+            let contract = format!("state-contract-{}", suffix);
+            let idea = format!("state-idea-{}", suffix);
+            process_command(&format!(
+                "DEFINE CONTRACT {c} \"A\"\nDEFINE IDEA {i} \"Texto\"\nAMEND CONTRACT {c} \"B\", \"C\"\nTAG IDEA {i} urgente\nRETIRE ENTITY {i}",
+                c = contract,
+                i = idea
+            ))
+            .await?;
+
+            let state = timeline::current_state().await?;
+            let (_, clauses, _) = view::get_contract(&contract).await.expect("Contrato ausente");
+            assert_eq!(state.contracts[&contract].clauses, clauses, "Estado e armazenamento deveriam coincidir");
+            assert_eq!(view::fetch_entity(&contract).await.expect("Entidade ausente").1, state.entities[&contract].entity_type);
+
+            // Retirar uma ideia também a remove
+            assert!(!state.ideas.contains_key(&idea), "Ideia deveria ter sido removida do estado");
+            assert!(view::get_idea(&idea).await.is_err(), "Ideia deveria ter sido removida do armazenamento");
+            let deleted = timeline::find_events_by_kind("IdeaDeleted").await?;
+            assert!(deleted.iter().any(|e| e.kind == EventKind::IdeaDeleted { id: idea.clone() }), "Evento de remoção ausente");
+
+            assert!(process_command(&format!("DEFINE CONTRACT {} \"X\"", contract)).await.is_err(), "Contrato duplicado");
+            Ok(())
+        })
+        .await
+}
@@ -0,0 +1,137 @@
+// === File: runtime/tests/store_tests.rs ===
+/*
+    Description: Testes do arquivo da timeline: formato dos registros, recuperação após falhas e reconstrução do estado.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::store::{TimelineFile, TimelineRecord};
+use runtime::{init_with_timeline, process_command, shutdown, timeline, view, Runtime};
+use anyhow::Result;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Caminho de um arquivo de timeline temporário, único por teste.
+fn temp_timeline() -> PathBuf {
+    std::env::temp_dir().join(format!("logline-{}", Uuid::new_v4())).join("main.timeline")
+}
+
+/// Eventos encadeados pelo hash.
+fn chain(kinds: Vec<EventKind>) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    for kind in kinds {
+        let previous = events.last().map(|e| e.hash.clone());
+        events.push(Event::chained(kind, previous));
+    }
+    events
+}
+
+#[test]
+fn test_record_matches_timeline_span_shape() -> Result<()> {
+    let event = Event::new(EventKind::IdeaTagged { id: "i-1".to_string(), tags: vec!["a".to_string()] });
+    let record = TimelineRecord::from_event(&event)?;
+    let json = serde_json::to_value(&record)?;
+
+    for key in ["type", "id", "timestamp", "span_type", "parent_id", "trace_id", "actors", "fields", "hash", "causal_hash"] {
+        assert!(json.get(key).is_some(), "Campo {} ausente no registro", key);
+    }
+    assert_eq!(json["type"], "span", "Tipo do registro incorreto");
+    assert_eq!(json["span_type"], "IdeaTagged", "span_type incorreto");
+    assert_eq!(json["fields"]["tags"], serde_json::json!(["a"]), "Campos do evento incorretos");
+
+    assert_eq!(record.into_event()?, event, "Ida e volta do registro falhou");
+    Ok(())
+}
+
+#[test]
+fn test_torn_trailing_record_is_truncated() -> Result<()> {
+    let path = temp_timeline();
+    let events = chain(vec![
+        EventKind::EntityRetired { id: "a".to_string() },
+        EventKind::EntityRetired { id: "b".to_string() },
+    ]);
+
+    let (mut file, loaded) = TimelineFile::open(&path, 1)?;
+    assert!(loaded.is_empty(), "Arquivo novo deveria estar vazio");
+    for event in &events {
+        file.append(event)?;
+    }
+    drop(file);
+    let valid_len = fs::metadata(&path)?.len();
+    assert_eq!(fs::read_to_string(&path)?.lines().count(), 2, "Deveria haver um registro por linha");
+
+    // Simula uma queda no meio da escrita do terceiro registro
+    OpenOptions::new().append(true).open(&path)?.write_all(b"{\"type\":\"span\",\"id\":")?;
+    let (_, loaded) = TimelineFile::open(&path, 1)?;
+    assert_eq!(loaded, events, "Registros completos deveriam ser preservados");
+    assert_eq!(fs::metadata(&path)?.len(), valid_len, "Registro incompleto deveria ser truncado");
+
+    // Um registro inválido seguido de outros não é uma escrita interrompida
+    let contents = fs::read_to_string(&path)?;
+    fs::write(&path, format!("lixo\n{}", contents))?;
+    assert!(TimelineFile::open(&path, 1).is_err(), "Registro corrompido no meio deveria falhar");
+
+    fs::remove_dir_all(path.parent().unwrap())?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_replays_timeline_file() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let path = temp_timeline();
+            let suffix = Uuid::new_v4().simple().to_string();
+            let contract = format!("replay-contract-{}", suffix);
+            let idea = format!("replay-idea-{}", suffix);
+
+            let (mut file, _) = TimelineFile::open(&path, 8)?;
+            for event in chain(vec![
+                EventKind::ContractRegistered { id: contract.clone(), clauses: vec!["Original".to_string()] },
+                EventKind::IdeaRegistered { id: idea.clone(), text: "Texto".to_string() },
+                EventKind::ContractAmended { id: contract.clone(), clauses: vec!["Emendada".to_string()] },
+                EventKind::IdeaTagged { id: idea.clone(), tags: vec!["urgente".to_string()] },
+                EventKind::EntityRetired { id: idea.clone() },
+            ]) {
+                file.append(&event)?;
+            }
+            file.sync()?;
+            drop(file);
+
+            init_with_timeline(&path).await?;
+            let (_, clauses, _) = view::get_contract(&contract).await.expect("Contrato deveria ser reconstruído");
+            assert_eq!(clauses, vec!["Emendada".to_string()], "Emenda deveria ser reaplicada");
+            assert_eq!(view::get_tags(&idea).await.expect("Ideia deveria ser reconstruída"), vec!["urgente".to_string()]);
+            assert_eq!(view::fetch_entity(&contract).await.expect("Entidade ausente").1, "CONTRACT");
+            assert!(view::fetch_entity(&idea).await.is_err(), "Entidade removida não deveria voltar");
+
+            // Ler a timeline de novo substitui o estado e o registro, sem somar a eles
+            let state = timeline::current_state().await?;
+            let registry = Runtime::current().registry().clone();
+            registry.register_entity(&format!("avulsa-{}", suffix), "TEST").await?;
+            init_with_timeline(&path).await?;
+            assert_eq!(timeline::current_state().await?, state, "Estado não deveria mudar ao reler a timeline");
+            let mut registered: Vec<_> = registry.list_entities(|_| true).await?.into_iter().map(|e| e.id).collect();
+            registered.sort();
+            assert_eq!(registered, state.entities.keys().cloned().collect::<Vec<_>>(), "Registro deveria refletir o estado");
+
+            // Novos eventos continuam a cadeia do arquivo
+            process_command(&format!("UPDATE IDEA {} \"Revisado\"", idea)).await?;
+            shutdown().await?;
+            let (_, events) = TimelineFile::open(&path, 8)?;
+            assert_eq!(events.len(), 9, "Inicializações, alteração e encerramento deveriam ser gravados");
+            assert_eq!(timeline::verify_chain(&events), Ok(()), "Cadeia do arquivo deveria continuar válida");
+            assert_eq!(events, timeline::list_events().await?, "Arquivo e memória deveriam coincidir");
+
+            fs::remove_dir_all(path.parent().unwrap())?;
+            Ok(())
+        })
+        .await
+}
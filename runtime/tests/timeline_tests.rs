@@ -0,0 +1,79 @@
+// === File: runtime/tests/timeline_tests.rs ===
+/*
+    Description: Testes da cadeia de hashes da timeline do runtime do LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::timeline::{self, verify_chain, ChainError};
+use runtime::Runtime;
+use anyhow::Result;
+
+/// Cadeia de três eventos ligados pelo hash.
+fn chain() -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    for id in ["a", "b", "c"] {
+        let previous = events.last().map(|e| e.hash.clone());
+        events.push(Event::chained(EventKind::EntityRetired { id: id.to_string() }, previous));
+    }
+    events
+}
+
+#[tokio::test]
+async fn test_timeline_events_are_chained() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            timeline::clear_timeline().await?;
+            timeline::append(EventKind::RuntimeLifecycle { status: "initialized".to_string() }).await?;
+            timeline::append(EventKind::EntityRetired { id: "e-1".to_string() }).await?;
+
+            let events = timeline::list_events().await?;
+            assert_eq!(events[0].causal_hash, None, "Primeiro evento não tem antecessor");
+            assert_eq!(events[1].causal_hash.as_ref(), Some(&events[0].hash), "Evento deveria apontar para o anterior");
+            assert!(events[0].hash.starts_with("sha256:") && events[0].hash.len() == 71, "Formato do hash incorreto");
+            assert_eq!(timeline::verify().await, Ok(()), "Timeline íntegra deveria ser válida");
+
+            // O hash sobrevive à serialização
+            let json = serde_json::to_string(&events[1])?;
+            let restored: Event = serde_json::from_str(&json)?;
+            assert_eq!(restored.compute_hash(), events[1].hash, "Hash deveria ser reproduzível após serialização");
+            Ok(())
+        })
+        .await
+}
+
+#[test]
+fn test_verify_reports_edited_event() {
+    let mut events = chain();
+    assert_eq!(verify_chain(&events), Ok(()), "Cadeia íntegra deveria ser válida");
+
+    events[1].kind = EventKind::EntityRetired { id: "editado".to_string() };
+    match verify_chain(&events) {
+        Err(ChainError::Tampered { index, computed, .. }) => {
+            assert_eq!(index, 1, "Primeiro elo quebrado deveria ser o evento 1");
+            assert_ne!(computed, events[1].hash, "Hash calculado deveria divergir");
+        }
+        other => panic!("Resultado inesperado: {:?}", other),
+    }
+
+    // Recalcular o hash do evento editado quebra o elo seguinte
+    events[1].hash = events[1].compute_hash();
+    let err = verify_chain(&events).expect_err("Elo seguinte deveria quebrar");
+    assert!(matches!(err, ChainError::BrokenLink { index: 2, .. }), "Erro inesperado: {:?}", err);
+}
+
+#[test]
+fn test_verify_reports_removed_event() {
+    let mut events = chain();
+    events.remove(0);
+
+    let err = verify_chain(&events).expect_err("Remoção deveria quebrar a cadeia");
+    assert_eq!(err.index(), 0, "Evento sem antecessor deveria ser o primeiro");
+    assert!(err.to_string().contains("não aponta para o anterior"), "Mensagem incorreta: {}", err);
+}
@@ -0,0 +1,113 @@
+// === File: runtime/tests/trace_tests.rs ===
+/*
+    Description: Testes do contexto de rastreamento: trace, evento pai e atores propagados por process_command.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::store::TimelineRecord;
+use runtime::trace::{self, TraceContext};
+use runtime::{process_command, process_command_as, timeline, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+/// Eventos da timeline registrados depois de `mark`.
+async fn events_since(mark: usize) -> Result<Vec<Event>> {
+    Ok(timeline::list_events().await?.split_off(mark))
+}
+
+#[test]
+fn test_trace_fields_are_hashed_and_persisted() -> Result<()> {
+    let kind = EventKind::EntityRetired { id: "e-1".to_string() };
+    let context = TraceContext::new(vec!["dan".to_string()]);
+    let event = Event::traced(kind, None, &context);
+    assert_eq!(event.trace_id, Some(context.trace_id), "Trace ausente");
+    assert_eq!(event.actors, vec!["dan".to_string()], "Atores ausentes");
+
+    let mut tampered = event.clone();
+    tampered.actors = vec!["outro".to_string()];
+    assert_ne!(tampered.compute_hash(), event.hash, "Atores deveriam fazer parte do hash");
+
+    let json = serde_json::to_value(TimelineRecord::from_event(&event)?)?;
+    assert_eq!(json["trace_id"], serde_json::json!(context.trace_id.to_string()), "trace_id não gravado");
+    assert_eq!(json["actors"], serde_json::json!(["dan"]), "actors não gravado");
+    assert_eq!(TimelineRecord::from_event(&event)?.into_event()?, event, "Ida e volta do registro falhou");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_process_command_propagates_trace() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let a = format!("trace-a-{}", Uuid::new_v4().simple());
+            let b = format!("trace-b-{}", Uuid::new_v4().simple());
+            let mark = timeline::list_events().await?.len();
+            process_command(&format!("DEFINE IDEA {a} \"A\"\nTAG IDEA {a} x", a = a)).await?;
+            assert!(process_command_as("DEFINE IDEA", vec!["ana".to_string()]).await.is_err(), "Comando inválido");
+            process_command_as(&format!("DEFINE IDEA {} \"B\"", b), vec!["ana".to_string()]).await?;
+            let events = events_since(mark).await?;
+            let find = |kind: EventKind| events.iter().find(|e| e.kind == kind).cloned().expect("Evento ausente");
+
+            // Mesmo comando, mesmo trace; comandos diferentes, traces diferentes
+            let registered = find(EventKind::IdeaRegistered { id: a.clone(), text: "A".to_string() });
+            let tagged = find(EventKind::IdeaTagged { id: a.clone(), tags: vec!["x".to_string()] });
+            assert!(registered.trace_id.is_some(), "Evento sem trace");
+            assert_eq!(tagged.trace_id, registered.trace_id, "Eventos do mesmo comando deveriam compartilhar o trace");
+            assert_eq!(registered.actors, vec![trace::DEFAULT_ACTOR.to_string()], "Ator padrão ausente");
+            assert_eq!(registered.parent_id, None, "Comando de topo não tem pai");
+
+            let other = find(EventKind::IdeaRegistered { id: b.clone(), text: "B".to_string() });
+            assert_ne!(other.trace_id, registered.trace_id, "Cada comando deveria iniciar um trace");
+            assert_eq!(other.actors, vec!["ana".to_string()], "Atores do comando ausentes");
+            let error = events
+                .iter()
+                .find(|e| e.kind.name() == "ErrorOccurred" && e.actors == vec!["ana".to_string()])
+                .expect("Erro de sintaxe deveria ser registrado com os atores do comando");
+            assert_ne!(error.trace_id, other.trace_id, "Erro pertence ao trace do próprio comando");
+            assert_eq!(timeline::verify().await, Ok(()), "Cadeia deveria continuar válida");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_nested_work_forms_a_tree() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let entity = format!("trace-sim-{}", Uuid::new_v4().simple());
+            let mark = timeline::list_events().await?.len();
+            let root = TraceContext::new(vec!["dan".to_string()]);
+
+            trace::scope(root.clone(), async {
+                process_command("ORCHESTRATE sequencial").await?;
+                let start = timeline::list_events()
+                    .await?
+                    .into_iter()
+                    .rev()
+                    .find(|e| matches!(e.kind, EventKind::OrchestrationStarted { .. }) && e.trace_id == Some(root.trace_id))
+                    .expect("Início da orquestração ausente");
+                // Simulação executada dentro da orquestração
+                trace::child_of(&start, process_command(&format!("SIMULATE ENTITY {} 2", entity))).await?;
+                anyhow::Ok(())
+            })
+            .await?;
+
+            let events: Vec<Event> = events_since(mark).await?.into_iter().filter(|e| e.trace_id == Some(root.trace_id)).collect();
+            let start = events.iter().find(|e| e.kind.name() == "OrchestrationStarted").expect("Início ausente");
+            let completed = events.iter().find(|e| e.kind.name() == "OrchestrationCompleted").expect("Conclusão ausente");
+            let simulation = events.iter().find(|e| e.kind.name() == "SimulationCompleted").expect("Simulação ausente");
+
+            assert_eq!(start.parent_id, None, "Orquestração é a raiz da árvore");
+            assert_eq!(completed.parent_id, Some(start.id), "Conclusão deveria ser filha do início");
+            assert_eq!(simulation.parent_id, Some(start.id), "Simulação deveria ser filha da orquestração");
+            assert!(events.iter().all(|e| e.actors == vec!["dan".to_string()]), "Atores deveriam ser propagados");
+            Ok(())
+        })
+        .await
+}
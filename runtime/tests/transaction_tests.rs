@@ -0,0 +1,197 @@
+// === File: runtime/tests/transaction_tests.rs ===
+/*
+    Description: Testes das transações BEGIN ... COMMIT: registro conjunto dos eventos ou desfazimento de todas as alterações.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use runtime::events::{Event, EventKind};
+use runtime::transaction::{Aborted, ERROR_CONTEXT};
+use runtime::{plan_command, process_command, process_command_as, timeline, view, Runtime};
+use anyhow::Result;
+use uuid::Uuid;
+
+/// Ator único, para separar os eventos de um teste.
+fn actor(name: &str) -> String {
+    format!("transacao-{}-{}", name, Uuid::new_v4().simple())
+}
+
+/// Eventos registrados em nome do ator, na ordem da timeline.
+async fn events_of(actor: &str) -> Result<Vec<Event>> {
+    Ok(timeline::list_events().await?.into_iter().filter(|e| e.actors.iter().any(|a| a == actor)).collect())
+}
+
+fn names(events: &[Event]) -> Vec<&str> {
+    events.iter().map(|e| e.kind.name()).collect()
+}
+
+#[tokio::test]
+async fn test_transaction_commits_every_event_together() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let actor = actor("commit");
+            let script = format!(
+                "BEGIN\n\
+                 \x20   DEFINE CONTRACT {a}-contrato \"A\"\n\
+                 \x20   DEFINE IDEA {a}-ideia \"Texto\"\n\
+                 \x20   IF EXISTS IDEA {a}-ideia THEN TAG IDEA {a}-ideia nova\n\
+                 \x20   LET marca = \"confirmada\"\n\
+                 COMMIT\n\
+                 DEFINE IDEA {a}-${{marca}} \"Depois\"",
+                a = actor
+            );
+            let result = process_command_as(&script, vec![actor.clone()]).await?;
+            assert!(result.contains("Tags adicionadas"), "Passos deveriam ver o efeito dos anteriores: {}", result);
+
+            let (_, clauses, _) = view::get_contract(&format!("{}-contrato", actor)).await?;
+            assert_eq!(clauses, vec!["A".to_string()]);
+            assert_eq!(view::get_tags(&format!("{}-ideia", actor)).await?, vec!["nova"]);
+            assert!(view::get_idea(&format!("{}-confirmada", actor)).await.is_ok(), "Variáveis da transação passam ao escopo");
+
+            let events = events_of(&actor).await?;
+            assert_eq!(
+                names(&events),
+                vec!["ContractRegistered", "IdeaRegistered", "IdeaTagged", "IdeaRegistered"],
+                "Eventos da transação deveriam ser registrados"
+            );
+
+            // Os eventos da transação ficam juntos na timeline, com a cadeia intacta
+            let timeline = timeline::list_events().await?;
+            let first = timeline.iter().position(|e| e.id == events[0].id).unwrap();
+            let batch: Vec<_> = timeline[first..first + 3].iter().map(|e| e.id).collect();
+            assert_eq!(batch, events[..3].iter().map(|e| e.id).collect::<Vec<_>>(), "Nenhum evento entre os da transação");
+            timeline::verify().await?;
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_failed_transaction_rolls_back_every_store() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let actor = actor("rollback");
+            let existing = format!("{}-existente", actor);
+            process_command(&format!("DEFINE IDEA {} \"Original\"", existing)).await?;
+
+            let script = format!(
+                "BEGIN\n\
+                 \x20   DEFINE CONTRACT {a}-contrato \"A\"\n\
+                 \x20   TAG IDEA {e} parcial\n\
+                 \x20   RETIRE ENTITY {e}\n\
+                 \x20   UPDATE IDEA {a}-ausente \"Falha\"\n\
+                 COMMIT",
+                a = actor,
+                e = existing
+            );
+            let error = process_command_as(&script, vec![actor.clone()]).await.unwrap_err();
+            let aborted = error.downcast_ref::<Aborted>().expect("Erro deveria ser da transação");
+            assert!(aborted.cause.contains("Falha ao atualizar ideia"), "Causa: {}", aborted.cause);
+
+            assert!(view::get_contract(&format!("{}-contrato", actor)).await.is_err(), "Contrato deveria ser desfeito");
+            assert!(view::get_tags(&existing).await?.is_empty(), "Tag deveria ser desfeita");
+            assert!(view::fetch_entity(&format!("{}-contrato", actor)).await.is_err(), "Registro deveria ser desfeito");
+            assert!(view::fetch_entity(&existing).await.is_ok(), "Remoção deveria ser desfeita");
+            assert!(view::get_idea(&existing).await.is_ok(), "Remoção deveria ser desfeita");
+
+            let events = events_of(&actor).await?;
+            assert_eq!(names(&events), vec!["ErrorOccurred"], "Só o erro da transação deveria ser registrado");
+            assert!(
+                matches!(&events[0].kind, EventKind::ErrorOccurred { context, message } if context == ERROR_CONTEXT && message.contains("Falha ao atualizar ideia")),
+                "Erro incorreto: {:?}",
+                events[0].kind
+            );
+
+            // Simulações não podem ser desfeitas: a transação nem começa
+            let simulated = format!("{}-simulada", actor);
+            let script = format!("BEGIN\n    DEFINE IDEA {s} \"x\"\n    ORCHESTRATE parallel {{ SIMULATE ENTITY {s} 1 }}\nCOMMIT", s = simulated);
+            let error = process_command(&script).await.unwrap_err();
+            assert!(error.to_string().contains("não pode fazer parte de uma transação"), "{}", error);
+            assert!(view::get_idea(&simulated).await.is_err(), "Nenhum comando deveria ser executado");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test]
+async fn test_nested_and_planned_transactions() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let planned = actor("plano");
+            // Uma transação interna desfeita registra um único erro e é tratada pelo WITH
+            let actor = actor("aninhada");
+            let script = format!(
+                "BEGIN\n\
+                 \x20   DEFINE IDEA {a}-externa \"Fica\"\n\
+                 \x20   WITH DO {{\n\
+                 \x20       BEGIN\n\
+                 \x20           DEFINE IDEA {a}-interna \"Desfeita\"\n\
+                 \x20           REVERT nao-e-uuid\n\
+                 \x20       COMMIT\n\
+                 \x20   }} ON FAILURE LET tratada = \"sim\"\n\
+                 COMMIT",
+                a = actor
+            );
+            process_command_as(&script, vec![actor.clone()]).await?;
+            assert!(view::get_idea(&format!("{}-externa", actor)).await.is_ok(), "Transação externa deveria ser registrada");
+            assert!(view::get_idea(&format!("{}-interna", actor)).await.is_err(), "Transação interna deveria ser desfeita");
+            assert_eq!(
+                names(&events_of(&actor).await?),
+                vec!["IdeaRegistered", "ErrorOccurred", "ExecutionAttempt"],
+                "A tentativa e o erro da transação interna fazem parte da externa"
+            );
+
+            // Num plano, a transação desfeita é retirada do plano
+            let plan = plan_command(&format!(
+                "DEFINE IDEA {p}-antes \"A\"\nBEGIN\n    DEFINE IDEA {p}-dentro \"B\"\n    UPDATE IDEA {p}-ausente \"C\"\nCOMMIT",
+                p = planned
+            ))
+            .await?;
+            assert!(plan.error.as_deref().unwrap_or_default().starts_with("Transação desfeita"), "{:?}", plan.error);
+            assert_eq!(names(&plan.events), vec!["IdeaRegistered", "ErrorOccurred"]);
+            assert!(view::get_idea(&format!("{}-antes", planned)).await.is_err(), "Plano não deveria registrar nada");
+            Ok(())
+        })
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_commands_cannot_invalidate_a_transaction() -> Result<()> {
+    Runtime::in_memory()
+        .scope(async {
+            let actor = actor("concorrente");
+            let contract = format!("{}-contrato", actor);
+
+            // Transações e comandos avulsos definem o mesmo contrato ao mesmo tempo
+            let tasks: Vec<_> = (0..8)
+                .map(|i| {
+                    let script = match i % 2 {
+                        0 => format!("BEGIN\n    DEFINE IDEA {a}-{i} \"Antes\"\n    DEFINE CONTRACT {a}-contrato \"T\"\nCOMMIT", a = actor, i = i),
+                        _ => format!("DEFINE CONTRACT {}-contrato \"A\"", actor),
+                    };
+                    let actors = vec![actor.clone()];
+                    let instance = Runtime::current();
+                    tokio::spawn(async move { instance.scope(process_command_as(&script, actors)).await.is_ok() })
+                })
+                .collect();
+            let mut succeeded = 0;
+            for task in tasks {
+                succeeded += task.await? as usize;
+            }
+            assert_eq!(succeeded, 1, "Apenas uma definição do contrato deveria ter sucesso");
+
+            let registered = events_of(&actor)
+                .await?
+                .into_iter()
+                .filter(|e| matches!(&e.kind, EventKind::ContractRegistered { id, .. } if *id == contract))
+                .count();
+            assert_eq!(registered, 1, "O contrato deveria ser registrado uma única vez");
+            Ok(())
+        })
+        .await
+}
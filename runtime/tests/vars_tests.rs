@@ -0,0 +1,98 @@
+// === File: runtime/tests/vars_tests.rs ===
+/*
+    Description: Testes do escopo de variáveis e da interpolação no runtime do LogLine.
+    LogLine Motor
+    Author: @danvoulez
+    License: Apache-2.0
+    Version: v1.0.0
+    Date: 2026-10-16
+    Repository: https://git.danvoulez/loglinemotor
+    Contact: dan@danvoulez.com
+*/
+
+use parser::{parse, ast::{Command, ImperativeKind}};
+use runtime::vars::{Scope, VarError};
+
+#[test]
+fn test_interpolate_user_bindings() {
+    let mut scope = Scope::new();
+    scope.bind("tenant", "acme");
+
+    let result = scope.interpolate("contrato-${tenant}-v1");
+    assert_eq!(result, Ok("contrato-acme-v1".to_string()), "Interpolação incorreta");
+}
+
+#[test]
+fn test_interpolate_builtins() {
+    let scope = Scope::new();
+
+    let timestamp = scope.interpolate("${timestamp}").unwrap();
+    assert!(timestamp.parse::<i64>().is_ok(), "timestamp deveria ser numérico: {}", timestamp);
+
+    let current_time = scope.interpolate("${current_time}").unwrap();
+    assert!(chrono::DateTime::parse_from_rfc3339(&current_time).is_ok(), "current_time deveria ser RFC 3339");
+
+    let random_id = scope.interpolate("${random_id}").unwrap();
+    assert_eq!(random_id.len(), 6, "random_id deveria ter 6 caracteres");
+    assert!(random_id.chars().all(|c| c.is_ascii_hexdigit()), "random_id deveria ser hexadecimal");
+}
+
+#[test]
+fn test_user_binding_shadows_builtin() {
+    let mut scope = Scope::new();
+    scope.bind("timestamp", "fixo");
+
+    assert_eq!(scope.interpolate("${timestamp}"), Ok("fixo".to_string()), "LET deveria ter precedência");
+}
+
+#[test]
+fn test_undefined_variable_is_an_error() {
+    let scope = Scope::new();
+
+    assert_eq!(
+        scope.interpolate("x-${inexistente}"),
+        Err(VarError::Undefined("inexistente".to_string())),
+        "Variável indefinida deveria falhar"
+    );
+}
+
+#[test]
+fn test_resolve_command() {
+    let mut scope = Scope::new();
+    scope.bind("alvo", "entidade-7");
+
+    let kind = match parse("DEFINE IDEA ideia-${alvo} \"Ideia sobre ${alvo}\"").unwrap() {
+        Command::Imperative(imperative) => imperative.kind,
+        other => panic!("Comando inesperado: {:?}", other),
+    };
+    let resolved = scope.resolve_kind(&kind).unwrap();
+
+    assert_eq!(
+        resolved,
+        ImperativeKind::DefineIdea {
+            id: "ideia-entidade-7".to_string(),
+            text: "Ideia sobre entidade-7".to_string(),
+        },
+        "Comando resolvido incorreto"
+    );
+}
+
+#[test]
+fn test_escaped_interpolation_is_literal() {
+    let mut scope = Scope::new();
+    scope.bind("moeda", "BRL");
+
+    assert_eq!(scope.interpolate("preço em $${moeda}"), Ok("preço em ${moeda}".to_string()), "Escape incorreto");
+    assert_eq!(scope.interpolate("$${moeda} = ${moeda}"), Ok("${moeda} = BRL".to_string()), "Escape e interpolação");
+    assert_eq!(scope.interpolate("$${ sem fechar"), Ok("${ sem fechar".to_string()), "Escape não exige '}}'");
+
+    // O escape também vale em textos entre aspas do script
+    let text = match parse("DEFINE IDEA modelo \"Use $${nome} no modelo\"").unwrap() {
+        Command::Imperative(imperative) => match scope.resolve_kind(&imperative.kind).unwrap() {
+            ImperativeKind::DefineIdea { text, .. } => text,
+            other => panic!("Comando inesperado: {:?}", other),
+        },
+        other => panic!("Comando inesperado: {:?}", other),
+    };
+    assert_eq!(text, "Use ${nome} no modelo");
+}
@@ -22,7 +22,7 @@ pub mod error;
 
 use thiserror::Error;
 // rand::Rng import removed because this file does not use random number generation directly
-use registry::fetch_entity;
+use registry::Registry;
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
@@ -137,25 +137,27 @@ impl Default for SimulationConfig {
 ///
 /// # Parâmetros
 ///
+/// * `registry` - Registro onde a entidade é buscada
 /// * `entity_id` - ID da entidade a ser simulada
 /// * `rounds` - Número de rodadas de simulação a executar
 ///
 /// # Retorna
 ///
 /// Um `Result` contendo os resultados da simulação ou um erro
-pub async fn run_simulation(entity_id: &str, rounds: usize) -> Result<Vec<SimulationResult>, SimulateError> {
+pub async fn run_simulation(registry: &Registry, entity_id: &str, rounds: usize) -> Result<Vec<SimulationResult>, SimulateError> {
     let config = SimulationConfig {
         rounds,
         ..Default::default()
     };
     
-    run_simulation_with_config(entity_id, config).await
+    run_simulation_with_config(registry, entity_id, config).await
 }
 
 /// Executa uma simulação para uma entidade com configuração customizada.
 ///
 /// # Parâmetros
 ///
+/// * `registry` - Registro onde a entidade é buscada
 /// * `entity_id` - ID da entidade a ser simulada
 /// * `config` - Configuração da simulação
 ///
@@ -163,12 +165,15 @@ pub async fn run_simulation(entity_id: &str, rounds: usize) -> Result<Vec<Simula
 ///
 /// Um `Result` contendo os resultados da simulação ou um erro
 pub async fn run_simulation_with_config(
+    registry: &Registry,
     entity_id: &str, 
     config: SimulationConfig
 ) -> Result<Vec<SimulationResult>, SimulateError> {
     // Verifica se a entidade existe no registro
-    let (_id, entity_type) = fetch_entity(entity_id).await
-        .map_err(|e| SimulateError::RegistryError(e.to_string()))?;
+    let entity_type = registry.get_entity(entity_id).await
+        .map_err(|e| SimulateError::RegistryError(e.to_string()))?
+        .entity_type
+        .to_string();
     
     // Configura o motor de simulação
     let engine_config = EngineConfig {
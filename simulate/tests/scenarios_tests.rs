@@ -14,13 +14,14 @@ use simulate::{
     run_simulation_with_config, SimulationConfig, SimulationMode,
     evaluate_simulation, SimulationResult, SimulationStatus
 };
-use registry::register_entity;
+use registry::Registry;
 
 #[tokio::test]
 async fn test_deterministic_simulation() {
     // Registra uma entidade de teste
     let entity_id = "sim-test-deterministic";
-    let _ = register_entity(entity_id, "TEST").await;
+    let registry = Registry::in_memory();
+    let _ = registry.register_entity(entity_id, "TEST").await;
     
     // Configura uma simulação determinística com seed fixo
     let config = SimulationConfig {
@@ -35,11 +36,11 @@ async fn test_deterministic_simulation() {
     };
     
     // Executa a simulação determinística
-    let result1 = run_simulation_with_config(entity_id, config.clone()).await;
+    let result1 = run_simulation_with_config(&registry, entity_id, config.clone()).await;
     assert!(result1.is_ok(), "A primeira simulação determinística falhou");
     
     // Executa a mesma simulação novamente
-    let result2 = run_simulation_with_config(entity_id, config).await;
+    let result2 = run_simulation_with_config(&registry, entity_id, config).await;
     assert!(result2.is_ok(), "A segunda simulação determinística falhou");
     
     // Com o mesmo seed e configuração determinística, os resultados devem ser idênticos
@@ -61,7 +62,8 @@ async fn test_deterministic_simulation() {
 async fn test_random_simulation_different_seeds() {
     // Registra uma entidade de teste
     let entity_id = "sim-test-random";
-    let _ = register_entity(entity_id, "TEST").await;
+    let registry = Registry::in_memory();
+    let _ = registry.register_entity(entity_id, "TEST").await;
     
     // Configura uma simulação com seed 1
     let config1 = SimulationConfig {
@@ -82,8 +84,8 @@ async fn test_random_simulation_different_seeds() {
     };
     
     // Executa as simulações
-    let result1 = run_simulation_with_config(entity_id, config1).await;
-    let result2 = run_simulation_with_config(entity_id, config2).await;
+    let result1 = run_simulation_with_config(&registry, entity_id, config1).await;
+    let result2 = run_simulation_with_config(&registry, entity_id, config2).await;
     
     assert!(result1.is_ok() && result2.is_ok(), "Falha ao executar simulações aleatórias");
     
@@ -107,7 +109,8 @@ async fn test_random_simulation_different_seeds() {
 async fn test_scenario_based_simulation() {
     // Registra uma entidade de teste
     let entity_id = "sim-test-scenario";
-    let _ = register_entity(entity_id, "TEST").await;
+    let registry = Registry::in_memory();
+    let _ = registry.register_entity(entity_id, "TEST").await;
     
     // Configura uma simulação com cenário de alta performance
     let config = SimulationConfig {
@@ -119,7 +122,7 @@ async fn test_scenario_based_simulation() {
     };
     
     // Executa a simulação
-    let result = run_simulation_with_config(entity_id, config).await;
+    let result = run_simulation_with_config(&registry, entity_id, config).await;
     assert!(result.is_ok(), "Falha ao executar simulação baseada em cenário");
     
     let sim_results = result.unwrap();
@@ -11,17 +11,18 @@
 */
 
 use simulate::{run_simulation, evaluate_simulation};
-use registry::register_entity;
+use registry::Registry;
 
 #[tokio::test]
 async fn test_simulation_runs_with_valid_entity() {
     // Registra uma entidade de teste
     let entity_id = "sim-test-entity";
-    let _ = register_entity(entity_id, "TEST").await;
+    let registry = Registry::in_memory();
+    let _ = registry.register_entity(entity_id, "TEST").await;
     
     // Executa a simulação
     let rounds = 5;
-    let result = run_simulation(entity_id, rounds).await;
+    let result = run_simulation(&registry, entity_id, rounds).await;
     
     assert!(result.is_ok(), "A simulação falhou com uma entidade válida");
     
@@ -50,9 +51,10 @@ async fn test_simulation_runs_with_valid_entity() {
 #[tokio::test]
 async fn test_simulation_fails_with_invalid_entity() {
     // Tenta simular uma entidade que não existe
+    let registry = Registry::in_memory();
     let entity_id = "non-existent-entity";
     let rounds = 3;
-    let result = run_simulation(entity_id, rounds).await;
+    let result = run_simulation(&registry, entity_id, rounds).await;
     
     assert!(result.is_err(), "A simulação deveria falhar com uma entidade inválida");
 }